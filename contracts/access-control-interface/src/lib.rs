@@ -0,0 +1,84 @@
+#![no_std]
+
+//! Client interface for consulting `AccessControl` grants from other contracts.
+//!
+//! Clinical contracts store the address of a deployed `AccessControl`
+//! contract and call `check_access` through [`AccessPolicyClient`] before
+//! serving a patient's data, so that consent managed in one place is
+//! honored everywhere. [`Scope`] and [`Purpose`] are defined here and
+//! re-exported by `AccessControl`, so both sides share one definition.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, String};
+
+/// --------------------
+/// Permission Scopes
+/// --------------------
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Scope {
+    Read,
+    Write,
+    Amend,
+    Share,
+    Delete,
+}
+
+/// --------------------
+/// Purpose of Use
+/// --------------------
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Purpose {
+    Treatment,
+    Payment,
+    Operations,
+    Research,
+    Emergency,
+}
+
+/// The subset of the `AccessControl` contract other contracts rely on.
+#[contractclient(name = "AccessPolicyClient")]
+pub trait AccessPolicy {
+    /// Returns `true` if `entity` holds a live grant on `resource_id` that
+    /// includes `scope` and was issued for `purpose`.
+    fn check_access(
+        env: Env,
+        entity: Address,
+        resource_id: String,
+        scope: Scope,
+        purpose: Purpose,
+    ) -> bool;
+
+    /// Records that `old_wallet` was replaced by `new_wallet` through social
+    /// recovery, so grants on the patient's data can follow the new wallet.
+    fn record_recovery(env: Env, old_wallet: Address, new_wallet: Address);
+}
+
+/// Maximum length of a resource path, in bytes. Matches `AccessControl`.
+const MAX_RESOURCE_ID_LEN: usize = 256;
+
+const PATIENT_PREFIX: &[u8] = b"patient/";
+
+/// Builds the resource path `patient/<address>/<category>` under which a
+/// patient's data in a given contract is granted.
+///
+/// A grant on `patient_resource(env, patient, "*")` covers every category.
+pub fn patient_resource(env: &Env, patient: &Address, category: &str) -> String {
+    let address = patient.to_string();
+    let address_len = address.len() as usize;
+    let len = PATIENT_PREFIX.len() + address_len + 1 + category.len();
+    if len > MAX_RESOURCE_ID_LEN {
+        panic!("Resource path too long");
+    }
+
+    let mut buf = [0u8; MAX_RESOURCE_ID_LEN];
+    let mut end = PATIENT_PREFIX.len();
+    buf[..end].copy_from_slice(PATIENT_PREFIX);
+    address.copy_into_slice(&mut buf[end..end + address_len]);
+    end += address_len;
+    buf[end] = b'/';
+    end += 1;
+    buf[end..len].copy_from_slice(category.as_bytes());
+
+    String::from_bytes(env, &buf[..len])
+}
@@ -1,8 +1,24 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, String,
+    Symbol, Vec,
+};
 
+use access_control_interface::patient_resource;
+pub use access_control_interface::{Purpose, Scope};
+use pagination::page_limit;
+
+mod storage;
 mod test;
+#[cfg(feature = "testutils")]
+pub mod testutils;
+
+use storage::{
+    delete_permission, extend_permission_ttl, extend_record_ttl, index_get, index_len, index_page,
+    index_push, index_remove, load_legacy_access_list, load_permission, require_migrated,
+    resource_key, save_permission, upgrade_legacy_permission,
+};
 
 /// --------------------
 /// Entity Types
@@ -29,370 +45,2148 @@ pub struct EntityData {
     pub active: bool,
 }
 
+/// --------------------
+/// Entity Verification
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VerificationStatus {
+    Pending,
+    Verified,
+    Suspended,
+    Revoked,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntityVerification {
+    pub status: VerificationStatus,
+    pub attester: Option<Address>,
+    pub credential_hash: Option<BytesN<32>>, // Hash of the attested license or credential
+    pub updated_at: u64,
+    pub expires_at: u64, // Credential expiry, 0 while never attested
+}
+
 /// --------------------
 /// Access Permission
 /// --------------------
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AccessPermission {
+    pub resource_id: String,
+    pub scopes: Vec<Scope>,
+    pub purpose: Purpose,
+    pub consent_hash: BytesN<32>, // Hash of the signed consent document
+    pub granted_by: Address,
+    pub granted_at: u64,
+    pub expires_at: u64, // 0 means no expiration
+    pub can_delegate: bool,
+    pub depth: u32, // 0 for a direct grant, +1 per delegation hop
+}
+
+/// --------------------
+/// Legacy Access Permission
+/// --------------------
+/// Layout of the grants stored under `DataKey::AccessList` before scopes,
+/// purposes and delegation were introduced. Only read while migrating.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyAccessPermission {
     pub resource_id: String,
     pub granted_by: Address,
     pub granted_at: u64,
     pub expires_at: u64, // 0 means no expiration
 }
 
+/// --------------------
+/// Grant Request
+/// --------------------
+/// Terms of a new grant, shared by `grant_access` and `grant_role_access`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrantRequest {
+    pub purpose: Purpose,
+    pub consent_hash: BytesN<32>, // Hash of the signed consent document
+    pub can_delegate: bool,
+    pub expires_at: u64, // 0 means no expiration
+}
+
+/// --------------------
+/// Issued Grants
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrantRef {
+    pub grantee: Address,
+    pub resource_id: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuedGrant {
+    pub grantee: Address,
+    pub permission: AccessPermission,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PurposeGrants {
+    pub purpose: Purpose,
+    pub grants: Vec<IssuedGrant>,
+}
+
+/// --------------------
+/// Paginated Results
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PermissionPage {
+    pub permissions: Vec<AccessPermission>,
+    pub next_cursor: Option<u32>, // None when there are no more results
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartyPage {
+    pub parties: Vec<GrantRef>, // Grantee and the (possibly wildcard) resource it was granted
+    pub next_cursor: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PurposeGrantsPage {
+    pub groups: Vec<PurposeGrants>,
+    pub next_cursor: Option<u32>,
+}
+
+/// --------------------
+/// Admin Council
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Council {
+    pub members: Vec<Address>,
+    pub threshold: u32, // Approvals required to execute a proposal
+    pub timelock: u64,  // Seconds between proposal and earliest execution
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdminAction {
+    DeactivateEntity(Address),
+    AddAdmin(Address),
+    RemoveAdmin(Address),
+    RotateAdmin(Address, Address), // (Outgoing, Incoming)
+    SetThreshold(u32),
+    SetTimelock(u64),
+    SetRoleTemplate(EntityType, Vec<Scope>),
+    SetMaxDelegationDepth(u32),
+    AddAttester(Address),
+    RemoveAttester(Address),
+    AddComplianceOfficer(Address),
+    RemoveComplianceOfficer(Address),
+    RegisterAdminEntity(Address, String, String), // (Wallet, Name, Metadata)
+    SetIdentityRegistry(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProposalStatus {
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub action: AdminAction,
+    pub approvals: Vec<Address>,
+    pub created_at: u64,
+    pub executable_at: u64,
+    pub status: ProposalStatus,
+}
+
+/// --------------------
+/// Break-Glass Access
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReviewStatus {
+    Pending,
+    Overdue, // Review deadline passed without a decision
+    Justified,
+    Unjustified,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BreakGlassRecord {
+    pub id: u64,
+    pub doctor: Address,
+    pub patient: Address,
+    pub resource_id: String,
+    pub scopes: Vec<Scope>,
+    pub justification: String,
+    pub issued_at: u64,
+    pub expires_at: u64,
+    pub review_deadline: u64,
+    pub status: ReviewStatus,
+    pub reviewer: Option<Address>,
+    pub reviewed_at: u64, // 0 until reviewed
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BreakGlassPage {
+    pub records: Vec<BreakGlassRecord>,
+    pub next_cursor: Option<u64>, // None when there are no more results
+}
+
 /// --------------------
 /// Storage Keys
 /// --------------------
 #[contracttype]
 pub enum DataKey {
-    Admin,
+    Admin, // Legacy single admin, superseded by `Council`
+    Council,
+    Proposal(u64),
+    ProposalCounter,
     Entity(Address),
-    AccessList(Address),    // Entity -> Vec<AccessPermission>
-    ResourceAccess(String), // Resource -> Vec<Address> (authorized parties)
+    AccessList(Address), // Legacy: Entity -> Vec<LegacyAccessPermission>, see `migrate_entity`
+    ResourceAccess(String), // Legacy: Resource -> Vec<Address> (authorized parties)
+    RoleTemplate(EntityType), // EntityType -> Vec<Scope>
+    MaxDelegationDepth,
+    Permission(Address, BytesN<32>), // (Entity, Resource hash) -> AccessPermission
+    IndexLen(IndexKey),              // List -> u32
+    IndexItem(IndexKey, u32),        // (List, Position) -> GrantRef
+    IndexPos(IndexKey, BytesN<32>),  // (List, Grant hash) -> u32
+    Attester(Address),               // Attester -> bool
+    Verification(Address),           // Entity -> EntityVerification
+    ComplianceOfficer(Address),      // Officer -> bool
+    BreakGlass(u64),                 // Id -> BreakGlassRecord
+    BreakGlassCounter,
+    IdentityRegistry,     // Contract allowed to report recovered patient wallets
+    RecoveredTo(Address), // Recovered patient wallet -> replacement wallet
+}
+
+/// Indexed lists of grants, each stored as one ledger entry per item
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IndexKey {
+    EntityGrants(Address),            // Grants held by an entity
+    ResourceGrants(BytesN<32>),       // Grants on a resource path (by hash)
+    IssuedGrants(Address),            // Grants issued by a grantor
+    Delegations(Address, BytesN<32>), // Grants delegated from (Holder, Resource hash)
+}
+
+/// Maximum number of delegation hops allowed when the admin has not set one.
+const DEFAULT_MAX_DELEGATION_DEPTH: u32 = 2;
+
+/// How long a break-glass grant lasts, in seconds.
+const BREAK_GLASS_DURATION: u64 = 4 * 60 * 60;
+
+/// How long compliance officers have to review a break-glass grant, in seconds.
+const BREAK_GLASS_REVIEW_WINDOW: u64 = 72 * 60 * 60;
+
+/// Maximum length of a resource path, in bytes.
+const MAX_RESOURCE_ID_LEN: usize = 256;
+
+/// Copies a resource path into a fixed buffer, returning the buffer and its length.
+fn resource_bytes(resource_id: &String) -> ([u8; MAX_RESOURCE_ID_LEN], usize) {
+    let len = resource_id.len() as usize;
+    if len == 0 || len > MAX_RESOURCE_ID_LEN {
+        panic!("Invalid resource id");
+    }
+    let mut buf = [0u8; MAX_RESOURCE_ID_LEN];
+    resource_id.copy_into_slice(&mut buf[..len]);
+    (buf, len)
+}
+
+/// Ensures a wildcard only appears as a trailing `/*` segment.
+fn validate_resource_id(resource_id: &String) {
+    let (buf, len) = resource_bytes(resource_id);
+    for (i, byte) in buf[..len].iter().enumerate() {
+        if *byte == b'*' && !(i == len - 1 && i > 0 && buf[i - 1] == b'/') {
+            panic!("Invalid resource id");
+        }
+    }
+}
+
+/// Returns the resource itself followed by every wildcard path covering it,
+/// nearest first: `patient/abc/labs/1` yields `patient/abc/labs/1`,
+/// `patient/abc/labs/*`, `patient/abc/*` and `patient/*`.
+fn resource_lineage(env: &Env, resource_id: &String) -> Vec<String> {
+    let mut lineage: Vec<String> = Vec::new(env);
+    lineage.push_back(resource_id.clone());
+
+    let (buf, len) = resource_bytes(resource_id);
+    let mut candidate = [0u8; MAX_RESOURCE_ID_LEN];
+    for i in (0..len.saturating_sub(1)).rev() {
+        if buf[i] != b'/' {
+            continue;
+        }
+        candidate[..=i].copy_from_slice(&buf[..=i]);
+        candidate[i + 1] = b'*';
+        let wildcard = String::from_bytes(env, &candidate[..i + 2]);
+        if wildcard != *resource_id {
+            lineage.push_back(wildcard);
+        }
+    }
+    lineage
+}
+
+const PATIENT_PREFIX: &[u8] = b"patient/";
+
+/// Panics unless `grantor` may grant access on `resource_id`.
+///
+/// Paths under `patient/<address>/` belong to that patient. The patient may
+/// grant anything below it and the admin council may grant on their behalf;
+/// everyone else shares through `delegate_access`, which keeps the grant in the
+/// patient's chain. The patient-wide wildcard `patient/<address>/*` is reserved
+/// to the patient and the council, and `patient/*` to the council alone. Other
+/// paths are not owned by anyone in particular.
+fn require_resource_owner(env: &Env, grantor: &Address, resource_id: &String) {
+    validate_resource_id(resource_id);
+    let (buf, len) = resource_bytes(resource_id);
+    let start = PATIENT_PREFIX.len();
+    if len <= start || &buf[..start] != PATIENT_PREFIX {
+        return;
+    }
+
+    let end = buf[start..len]
+        .iter()
+        .position(|byte| *byte == b'/')
+        .map_or(len, |offset| start + offset);
+    let is_admin = load_council(env).members.contains(grantor);
+    if &buf[start..end] == b"*" {
+        if !is_admin {
+            panic!("Only the admin council can grant access to every patient");
+        }
+        return;
+    }
+    if is_admin || String::from_bytes(env, &buf[start..end]) == grantor.to_string() {
+        return;
+    }
+    if &buf[end..len] == b"/*" {
+        panic!("Only the patient or the admin council can grant patient-wide access");
+    }
+    panic!("Grantor does not control this resource");
+}
+
+/// Default scopes granted to an entity type when no role template has been
+/// configured by the admin.
+fn default_role_scopes(env: &Env, entity_type: &EntityType) -> Vec<Scope> {
+    match entity_type {
+        EntityType::Doctor => Vec::from_array(env, [Scope::Read, Scope::Write]),
+        EntityType::Hospital => Vec::from_array(env, [Scope::Read, Scope::Write, Scope::Share]),
+        EntityType::Patient => Vec::from_array(
+            env,
+            [
+                Scope::Read,
+                Scope::Write,
+                Scope::Amend,
+                Scope::Share,
+                Scope::Delete,
+            ],
+        ),
+        EntityType::Insurer | EntityType::Admin => Vec::from_array(env, [Scope::Read]),
+    }
+}
+
+/// Removes duplicate scopes and rejects an empty scope list.
+fn normalize_scopes(env: &Env, scopes: Vec<Scope>) -> Vec<Scope> {
+    let mut normalized: Vec<Scope> = Vec::new(env);
+    for scope in scopes.iter() {
+        if !normalized.contains(scope) {
+            normalized.push_back(scope);
+        }
+    }
+    if normalized.is_empty() {
+        panic!("At least one scope is required");
+    }
+    normalized
+}
+
+/// Stores a permission and adds it to the grantee, resource, grantor and
+/// delegation indexes.
+fn store_permission(env: &Env, grantee: &Address, permission: AccessPermission) {
+    let resource_id = permission.resource_id.clone();
+    validate_resource_id(&resource_id);
+    require_migrated(env, grantee);
+
+    // Check if permission already exists for this resource
+    if load_permission(env, grantee, &resource_id).is_some() {
+        panic!("Access already granted for this resource");
+    }
+
+    save_permission(env, grantee, &permission);
+
+    let grant = GrantRef {
+        grantee: grantee.clone(),
+        resource_id: resource_id.clone(),
+    };
+    index_push(env, &IndexKey::EntityGrants(grantee.clone()), &grant);
+    index_push(
+        env,
+        &IndexKey::ResourceGrants(resource_key(env, &resource_id)),
+        &grant,
+    );
+    index_push(
+        env,
+        &IndexKey::IssuedGrants(permission.granted_by.clone()),
+        &grant,
+    );
+
+    // Record delegated grants under their parent so revocation cascades
+    if permission.depth > 0 {
+        index_push(
+            env,
+            &IndexKey::Delegations(permission.granted_by, resource_key(env, &resource_id)),
+            &grant,
+        );
+    }
+}
+
+/// Returns `true` if `revoker` issued this permission or any permission it
+/// was delegated from.
+fn is_in_grant_chain(env: &Env, permission: &AccessPermission, revoker: &Address) -> bool {
+    let mut current = permission.clone();
+    loop {
+        if current.granted_by == *revoker {
+            return true;
+        }
+        if current.depth == 0 {
+            return false;
+        }
+        match load_permission(env, &current.granted_by, &current.resource_id) {
+            Some(parent) => current = parent,
+            None => return false,
+        }
+    }
+}
+
+fn is_expired(permission: &AccessPermission, current_time: u64) -> bool {
+    permission.expires_at != 0 && permission.expires_at <= current_time
+}
+
+/// Deletes a permission and drops it from every index, leaving any grants
+/// delegated from it in place.
+fn unindex_permission(env: &Env, holder: &Address, permission: &AccessPermission) {
+    let resource_id = &permission.resource_id;
+    require_migrated(env, holder);
+
+    delete_permission(env, holder, resource_id);
+
+    let grant = GrantRef {
+        grantee: holder.clone(),
+        resource_id: resource_id.clone(),
+    };
+    index_remove(env, &IndexKey::EntityGrants(holder.clone()), &grant);
+    index_remove(
+        env,
+        &IndexKey::ResourceGrants(resource_key(env, resource_id)),
+        &grant,
+    );
+    index_remove(
+        env,
+        &IndexKey::IssuedGrants(permission.granted_by.clone()),
+        &grant,
+    );
+
+    // Detach from the delegator's list of children
+    if permission.depth > 0 {
+        index_remove(
+            env,
+            &IndexKey::Delegations(
+                permission.granted_by.clone(),
+                resource_key(env, resource_id),
+            ),
+            &grant,
+        );
+    }
+}
+
+/// Removes a permission and, recursively, every grant delegated from it,
+/// publishing `topic` for each removed grant.
+fn remove_permission(env: &Env, holder: &Address, permission: &AccessPermission, topic: Symbol) {
+    let resource_id = &permission.resource_id;
+    unindex_permission(env, holder, permission);
+
+    // Cascade to every grant delegated from this one; each removal shrinks the list
+    let children = IndexKey::Delegations(holder.clone(), resource_key(env, resource_id));
+    while let Some(child) = index_get(env, &children, 0) {
+        match load_permission(env, &child.grantee, &child.resource_id) {
+            Some(child_permission) if child_permission.granted_by == *holder => {
+                remove_permission(env, &child.grantee, &child_permission, topic.clone())
+            }
+            _ => index_remove(env, &children, &child),
+        }
+    }
+
+    env.events().publish(
+        (topic, holder.clone(), resource_id.clone()),
+        symbol_short!("success"),
+    );
+}
+
+/// Moves a permission to `resource_id` on behalf of `granted_by`, carrying
+/// every grant delegated from it along. If the holder already has a grant
+/// on the new path, that grant is kept and the moved one is dropped.
+fn rekey_permission(
+    env: &Env,
+    holder: &Address,
+    permission: &AccessPermission,
+    resource_id: &String,
+    granted_by: &Address,
+) {
+    if *resource_id != permission.resource_id && load_permission(env, holder, resource_id).is_some()
+    {
+        remove_permission(env, holder, permission, symbol_short!("rekey"));
+        return;
+    }
+
+    let children =
+        IndexKey::Delegations(holder.clone(), resource_key(env, &permission.resource_id));
+    unindex_permission(env, holder, permission);
+    let mut moved = permission.clone();
+    moved.resource_id = resource_id.clone();
+    moved.granted_by = granted_by.clone();
+    store_permission(env, holder, moved);
+
+    // Each moved child leaves the old list
+    while let Some(child) = index_get(env, &children, 0) {
+        match load_permission(env, &child.grantee, &child.resource_id) {
+            Some(child_permission) if child_permission.granted_by == *holder => {
+                rekey_permission(env, &child.grantee, &child_permission, resource_id, holder)
+            }
+            _ => index_remove(env, &children, &child),
+        }
+    }
+
+    env.events().publish(
+        (symbol_short!("rekey"), holder.clone(), resource_id.clone()),
+        permission.resource_id.clone(),
+    );
+}
+
+/// Rewrites a path under `patient/<old_wallet>/` to the same path under
+/// `patient/<new_wallet>/`. Other paths are returned unchanged.
+fn rekey_resource_id(
+    env: &Env,
+    resource_id: &String,
+    old_wallet: &Address,
+    new_wallet: &Address,
+) -> String {
+    let (buf, len) = resource_bytes(resource_id);
+    let (old_prefix, old_len) = resource_bytes(&patient_resource(env, old_wallet, ""));
+    if len <= old_len || buf[..old_len] != old_prefix[..old_len] {
+        return resource_id.clone();
+    }
+
+    let (mut rekeyed, new_len) = resource_bytes(&patient_resource(env, new_wallet, ""));
+    let end = new_len + len - old_len;
+    if end > MAX_RESOURCE_ID_LEN {
+        panic!("Invalid resource id");
+    }
+    rekeyed[new_len..end].copy_from_slice(&buf[old_len..len]);
+    String::from_bytes(env, &rekeyed[..end])
+}
+
+/// Moves up to `limit` direct grants issued by a recovered wallet to its
+/// replacement, returning how many were moved. Delegated grants the old
+/// wallet issued stay with it, parked at the end of its list.
+fn migrate_issued_grants(env: &Env, old_wallet: &Address, new_wallet: &Address, limit: u32) -> u32 {
+    let list = IndexKey::IssuedGrants(old_wallet.clone());
+    let mut skipped = 0;
+    let mut moved = 0;
+    while moved < limit {
+        let len = index_len(env, &list);
+        if len <= skipped {
+            break;
+        }
+        let grant = index_get(env, &list, len - 1 - skipped).expect("Index corrupted");
+        let permission = match load_permission(env, &grant.grantee, &grant.resource_id) {
+            Some(permission) => permission,
+            None => {
+                index_remove(env, &list, &grant);
+                continue;
+            }
+        };
+        if permission.depth > 0 {
+            skipped += 1;
+            continue;
+        }
+
+        let resource_id = rekey_resource_id(env, &grant.resource_id, old_wallet, new_wallet);
+        rekey_permission(env, &grant.grantee, &permission, &resource_id, new_wallet);
+        moved += 1;
+    }
+    moved
+}
+
+/// Entity types whose credentials must be attested before they can receive
+/// grants, which is every type except patients.
+fn requires_verification(entity_type: &EntityType) -> bool {
+    !matches!(entity_type, EntityType::Patient)
+}
+
+fn store_entity(
+    env: &Env,
+    wallet: &Address,
+    entity_type: EntityType,
+    name: String,
+    metadata: String,
+) {
+    let key = DataKey::Entity(wallet.clone());
+    if env.storage().persistent().has(&key) {
+        panic!("Entity already registered");
+    }
+
+    let entity = EntityData {
+        entity_type,
+        name,
+        metadata,
+        active: true,
+    };
+
+    env.storage().persistent().set(&key, &entity);
+
+    env.events().publish(
+        (symbol_short!("reg_ent"), wallet.clone()),
+        symbol_short!("success"),
+    );
+}
+
+fn load_verification(env: &Env, wallet: &Address) -> EntityVerification {
+    let key = DataKey::Verification(wallet.clone());
+    extend_record_ttl(env, &key);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(EntityVerification {
+            status: VerificationStatus::Pending,
+            attester: None,
+            credential_hash: None,
+            updated_at: 0,
+            expires_at: 0,
+        })
+}
+
+/// Returns `true` if the entity holds an attested, unexpired credential.
+fn is_verified(env: &Env, wallet: &Address) -> bool {
+    let verification = load_verification(env, wallet);
+    verification.status == VerificationStatus::Verified
+        && verification.expires_at > env.ledger().timestamp()
+}
+
+/// Returns `true` if the entity is registered, active and, where its type
+/// requires it, holds a current verification.
+fn is_in_good_standing(env: &Env, wallet: &Address) -> bool {
+    let key = DataKey::Entity(wallet.clone());
+    let Some(entity) = env.storage().persistent().get::<_, EntityData>(&key) else {
+        return false;
+    };
+    extend_record_ttl(env, &key);
+
+    entity.active && (!requires_verification(&entity.entity_type) || is_verified(env, wallet))
+}
+
+/// Panics unless the grantee is registered and, where its type requires it,
+/// verified.
+fn require_verified_grantee(env: &Env, grantee: &Address) {
+    let key = DataKey::Entity(grantee.clone());
+    let entity: EntityData = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .expect("Grantee not registered");
+    extend_record_ttl(env, &key);
+
+    if requires_verification(&entity.entity_type) && !is_verified(env, grantee) {
+        panic!("Grantee is not verified");
+    }
+}
+
+fn require_attester(env: &Env, attester: &Address) {
+    let is_attester: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Attester(attester.clone()))
+        .unwrap_or(false);
+    if !is_attester {
+        panic!("Only attesters can change verification status");
+    }
+}
+
+fn require_compliance_officer(env: &Env, officer: &Address) {
+    let is_officer: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ComplianceOfficer(officer.clone()))
+        .unwrap_or(false);
+    if !is_officer {
+        panic!("Only compliance officers can review break-glass access");
+    }
+}
+
+fn load_break_glass(env: &Env, id: u64) -> BreakGlassRecord {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BreakGlass(id))
+        .expect("Break-glass record not found")
+}
+
+fn save_break_glass(env: &Env, record: &BreakGlassRecord) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::BreakGlass(record.id), record);
+}
+
+/// Loads the admin council, treating a legacy single admin as a council of one.
+fn load_council(env: &Env) -> Council {
+    if let Some(council) = env.storage().persistent().get(&DataKey::Council) {
+        extend_record_ttl(env, &DataKey::Council);
+        return council;
+    }
+    let admin: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Admin)
+        .expect("Contract not initialized");
+    Council {
+        members: Vec::from_array(env, [admin]),
+        threshold: 1,
+        timelock: 0,
+    }
+}
+
+/// Returns `true` if `admin` may act alone, which is only the case while
+/// they are the council's only member and no timelock applies.
+fn is_sole_admin(council: &Council, admin: &Address) -> bool {
+    council.members.len() == 1 && council.timelock == 0 && council.members.contains(admin)
+}
+
+fn require_sole_admin(env: &Env, admin: &Address, message: &str) {
+    let council = load_council(env);
+    if !council.members.contains(admin) {
+        panic!("{}", message);
+    }
+    if !is_sole_admin(&council, admin) {
+        panic!("Action requires council approval");
+    }
+}
+
+fn approval_count(council: &Council, proposal: &Proposal) -> u32 {
+    proposal
+        .approvals
+        .iter()
+        .filter(|approver| council.members.contains(approver))
+        .count() as u32
+}
+
+fn load_proposal(env: &Env, proposal_id: u64) -> Proposal {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Proposal(proposal_id))
+        .expect("Proposal not found")
+}
+
+fn apply_admin_action(env: &Env, action: AdminAction) {
+    let mut council = load_council(env);
+
+    match action {
+        AdminAction::DeactivateEntity(wallet) => {
+            let key = DataKey::Entity(wallet.clone());
+            let mut entity: EntityData = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .expect("Entity not found");
+
+            entity.active = false;
+            env.storage().persistent().set(&key, &entity);
+
+            env.events()
+                .publish((symbol_short!("deact"), wallet), symbol_short!("success"));
+        }
+        AdminAction::AddAdmin(member) => {
+            if council.members.contains(&member) {
+                panic!("Already a council member");
+            }
+            council.members.push_back(member);
+        }
+        AdminAction::RemoveAdmin(member) => {
+            let index = council
+                .members
+                .first_index_of(&member)
+                .expect("Not a council member");
+            council.members.remove(index);
+        }
+        AdminAction::RotateAdmin(outgoing, incoming) => {
+            if council.members.contains(&incoming) {
+                panic!("Already a council member");
+            }
+            let index = council
+                .members
+                .first_index_of(&outgoing)
+                .expect("Not a council member");
+            council.members.set(index, incoming);
+        }
+        AdminAction::SetThreshold(threshold) => council.threshold = threshold,
+        AdminAction::SetTimelock(timelock) => council.timelock = timelock,
+        AdminAction::SetRoleTemplate(entity_type, scopes) => {
+            let scopes = normalize_scopes(env, scopes);
+            env.storage()
+                .persistent()
+                .set(&DataKey::RoleTemplate(entity_type.clone()), &scopes);
+
+            env.events()
+                .publish((symbol_short!("role_tpl"), entity_type), scopes);
+        }
+        AdminAction::SetMaxDelegationDepth(max_depth) => {
+            env.storage()
+                .persistent()
+                .set(&DataKey::MaxDelegationDepth, &max_depth);
+
+            env.events()
+                .publish((symbol_short!("max_dpth"),), max_depth);
+        }
+        AdminAction::AddAttester(attester) => {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Attester(attester.clone()), &true);
+
+            env.events()
+                .publish((symbol_short!("attester"), attester), true);
+        }
+        AdminAction::RemoveAttester(attester) => {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Attester(attester.clone()));
+
+            env.events()
+                .publish((symbol_short!("attester"), attester), false);
+        }
+        AdminAction::AddComplianceOfficer(officer) => {
+            env.storage()
+                .persistent()
+                .set(&DataKey::ComplianceOfficer(officer.clone()), &true);
+
+            env.events()
+                .publish((symbol_short!("officer"), officer), true);
+        }
+        AdminAction::RemoveComplianceOfficer(officer) => {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::ComplianceOfficer(officer.clone()));
+
+            env.events()
+                .publish((symbol_short!("officer"), officer), false);
+        }
+        AdminAction::RegisterAdminEntity(wallet, name, metadata) => {
+            store_entity(env, &wallet, EntityType::Admin, name, metadata);
+        }
+        AdminAction::SetIdentityRegistry(registry) => {
+            env.storage()
+                .persistent()
+                .set(&DataKey::IdentityRegistry, &registry);
+
+            env.events().publish((symbol_short!("id_reg"),), registry);
+        }
+    }
+
+    if council.threshold == 0 || council.threshold > council.members.len() {
+        panic!("Invalid council threshold");
+    }
+    env.storage().persistent().set(&DataKey::Council, &council);
 }
 
 #[contract]
 pub struct AccessControl;
 
-#[contractimpl]
-impl AccessControl {
-    /// Initialize the contract with an admin
+#[contractimpl]
+impl AccessControl {
+    /// Initialize the contract with an admin
+    ///
+    /// The admin forms a council of one; further members and a higher
+    /// approval threshold are added through proposals.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address for the contract
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().persistent().has(&DataKey::Council)
+            || env.storage().persistent().has(&DataKey::Admin)
+        {
+            panic!("Contract already initialized");
+        }
+        admin.require_auth();
+
+        let council = Council {
+            members: Vec::from_array(&env, [admin.clone()]),
+            threshold: 1,
+            timelock: 0,
+        };
+        env.storage().persistent().set(&DataKey::Council, &council);
+
+        env.events()
+            .publish((symbol_short!("init"), admin), symbol_short!("success"));
+    }
+
+    /// Register a new entity in the system
+    ///
+    /// Admin entities cannot register themselves; the council registers them
+    /// through `AdminAction::RegisterAdminEntity`.
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the entity
+    /// * `entity_type` - The type of entity (Hospital, Doctor, Patient, etc.)
+    /// * `name` - The name of the entity
+    /// * `metadata` - Additional information about the entity
+    pub fn register_entity(
+        env: Env,
+        wallet: Address,
+        entity_type: EntityType,
+        name: String,
+        metadata: String,
+    ) {
+        wallet.require_auth();
+
+        if entity_type == EntityType::Admin {
+            panic!("Admin entities are registered by the council");
+        }
+
+        store_entity(&env, &wallet, entity_type, name, metadata);
+    }
+
+    /// Grant access permission to an entity for a specific resource
+    ///
+    /// Records under `patient/<address>/` can only be granted by that patient
+    /// or the admin council. Holders of a delegable grant share them through
+    /// `delegate_access` instead.
+    ///
+    /// # Arguments
+    /// * `grantor` - The address granting access (must be authorized)
+    /// * `grantee` - The address receiving access
+    /// * `resource_id` - The identifier of the resource
+    /// * `scopes` - The operations the grantee may perform on the resource
+    /// * `request` - Purpose of use, consent hash, delegability and expiry of the grant
+    pub fn grant_access(
+        env: Env,
+        grantor: Address,
+        grantee: Address,
+        resource_id: String,
+        scopes: Vec<Scope>,
+        request: GrantRequest,
+    ) {
+        grantor.require_auth();
+
+        // Verify grantor is a registered entity
+        let grantor_key = DataKey::Entity(grantor.clone());
+        if !env.storage().persistent().has(&grantor_key) {
+            panic!("Grantor not registered");
+        }
+
+        // Verify the grantor controls the resource
+        require_resource_owner(&env, &grantor, &resource_id);
+
+        // Verify grantee is a registered entity with any required credentials
+        require_verified_grantee(&env, &grantee);
+
+        let permission = AccessPermission {
+            resource_id: resource_id.clone(),
+            scopes: normalize_scopes(&env, scopes),
+            purpose: request.purpose,
+            consent_hash: request.consent_hash,
+            granted_by: grantor.clone(),
+            granted_at: env.ledger().timestamp(),
+            expires_at: request.expires_at,
+            can_delegate: request.can_delegate,
+            depth: 0,
+        };
+
+        store_permission(&env, &grantee, permission);
+
+        env.events().publish(
+            (symbol_short!("grant"), grantee, resource_id),
+            symbol_short!("success"),
+        );
+    }
+
+    /// Grant access using the role template of the grantee's entity type
+    ///
+    /// # Arguments
+    /// * `grantor` - The address granting access (must be authorized)
+    /// * `grantee` - The address receiving access
+    /// * `resource_id` - The identifier of the resource
+    /// * `request` - Purpose of use, consent hash, delegability and expiry of the grant
+    pub fn grant_role_access(
+        env: Env,
+        grantor: Address,
+        grantee: Address,
+        resource_id: String,
+        request: GrantRequest,
+    ) {
+        let grantee_entity: EntityData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Entity(grantee.clone()))
+            .expect("Grantee not registered");
+
+        let scopes = Self::get_role_template(env.clone(), grantee_entity.entity_type);
+        Self::grant_access(env, grantor, grantee, resource_id, scopes, request);
+    }
+
+    /// Delegate a subset of the delegator's own access to another entity
+    ///
+    /// The delegator must hold a grant on exactly `resource_id`; a wildcard
+    /// grant is delegated by passing the wildcard path itself. The delegated
+    /// grant carries the purpose and consent document of the delegator's grant.
+    ///
+    /// # Arguments
+    /// * `delegator` - The address forwarding access (must hold a delegable grant)
+    /// * `grantee` - The address receiving access
+    /// * `resource_id` - The identifier of the resource
+    /// * `scopes` - The operations delegated (must be a subset of the delegator's scopes)
+    /// * `can_delegate` - Whether the grantee may delegate further
+    /// * `expires_at` - Expiration timestamp (0 for no expiration, only if the delegator's grant never expires)
+    pub fn delegate_access(
+        env: Env,
+        delegator: Address,
+        grantee: Address,
+        resource_id: String,
+        scopes: Vec<Scope>,
+        can_delegate: bool,
+        expires_at: u64,
+    ) {
+        delegator.require_auth();
+
+        if delegator == grantee {
+            panic!("Cannot delegate access to self");
+        }
+
+        let grantee_key = DataKey::Entity(grantee.clone());
+        if !env.storage().persistent().has(&grantee_key) {
+            panic!("Grantee not registered");
+        }
+
+        let parent = load_permission(&env, &delegator, &resource_id)
+            .expect("Delegator has no access to this resource");
+
+        let current_time = env.ledger().timestamp();
+        if is_expired(&parent, current_time) {
+            panic!("Delegator access has expired");
+        }
+
+        if !parent.can_delegate {
+            panic!("Access is not delegable");
+        }
+
+        let depth = parent.depth + 1;
+        if depth > Self::get_max_delegation_depth(env.clone()) {
+            panic!("Maximum delegation depth exceeded");
+        }
+
+        let scopes = normalize_scopes(&env, scopes);
+        for scope in scopes.iter() {
+            if !parent.scopes.contains(scope) {
+                panic!("Delegated scopes exceed delegator scopes");
+            }
+        }
+
+        // A child grant can never outlive its parent
+        if parent.expires_at != 0 && (expires_at == 0 || expires_at > parent.expires_at) {
+            panic!("Delegated access cannot outlive delegator access");
+        }
+
+        require_verified_grantee(&env, &grantee);
+
+        let permission = AccessPermission {
+            resource_id: resource_id.clone(),
+            scopes,
+            purpose: parent.purpose,
+            consent_hash: parent.consent_hash,
+            granted_by: delegator.clone(),
+            granted_at: current_time,
+            expires_at,
+            can_delegate,
+            depth,
+        };
+
+        store_permission(&env, &grantee, permission);
+
+        env.events()
+            .publish((symbol_short!("delegate"), grantee, resource_id), delegator);
+    }
+
+    /// Revoke access permission from an entity for a specific resource
+    ///
+    /// Every grant delegated from the revoked grant, directly or transitively,
+    /// is revoked along with it.
+    ///
+    /// # Arguments
+    /// * `revoker` - The address revoking access (any grantor up the delegation chain, or the
+    ///   admin while the council requires a single approval)
+    /// * `revokee` - The address losing access
+    /// * `resource_id` - The identifier of the resource
+    pub fn revoke_access(env: Env, revoker: Address, revokee: Address, resource_id: String) {
+        revoker.require_auth();
+
+        // Get admin council for authorization check
+        let council = load_council(&env);
+
+        let permission =
+            load_permission(&env, &revokee, &resource_id).expect("Access permission not found");
+
+        // Verify revoker is either a grantor in the chain or admin
+        if !is_sole_admin(&council, &revoker) && !is_in_grant_chain(&env, &permission, &revoker) {
+            panic!("Not authorized to revoke this access");
+        }
+
+        remove_permission(&env, &revokee, &permission, symbol_short!("revoke"));
+    }
+
+    /// Get the entities that received a resource by delegation from a holder
+    ///
+    /// # Arguments
+    /// * `holder` - The address whose grant was delegated
+    /// * `resource_id` - The identifier of the resource
+    ///
+    /// # Returns
+    /// The direct children of the holder's grant in the delegation tree
+    pub fn get_delegations(env: Env, holder: Address, resource_id: String) -> Vec<Address> {
+        let children = IndexKey::Delegations(holder, resource_key(&env, &resource_id));
+        let mut delegates: Vec<Address> = Vec::new(&env);
+        for child in index_page(&env, &children, 0, index_len(&env, &children)).iter() {
+            delegates.push_back(child.grantee);
+        }
+        delegates
+    }
+
+    /// Remove expired grants held by an entity
+    ///
+    /// Anyone may call this. Grants delegated from a removed grant are removed
+    /// with it, and an `expired` event is published for each.
+    ///
+    /// # Arguments
+    /// * `entity` - The entity whose grants should be swept
+    /// * `limit` - Maximum number of grants to examine in this call
+    ///
+    /// # Returns
+    /// The number of the entity's grants that were removed
+    pub fn sweep_expired(env: Env, entity: Address, limit: u32) -> u32 {
+        let current_time = env.ledger().timestamp();
+        let list = IndexKey::EntityGrants(entity.clone());
+
+        let mut position = 0;
+        let mut examined = 0;
+        let mut removed = 0;
+        while examined < limit {
+            let grant = match index_get(&env, &list, position) {
+                Some(grant) => grant,
+                None => break,
+            };
+            examined += 1;
+
+            match load_permission(&env, &entity, &grant.resource_id) {
+                Some(permission) if is_expired(&permission, current_time) => {
+                    // The last grant is swapped into this position
+                    remove_permission(&env, &entity, &permission, symbol_short!("expired"));
+                    removed += 1;
+                }
+                Some(_) => position += 1,
+                None => index_remove(&env, &list, &grant),
+            }
+        }
+
+        removed
+    }
+
+    /// Check if an entity may perform an operation on a specific resource
+    ///
+    /// Grants on a wildcard path such as `patient/<addr>/labs/*` cover every
+    /// resource below that prefix. Every live grant consulted has its storage
+    /// TTL extended so that it is not archived while still in use.
+    ///
+    /// Grants held by a deactivated entity, or by one whose verification has
+    /// been suspended, revoked or has lapsed, are ignored until it is in good
+    /// standing again.
+    ///
+    /// # Arguments
+    /// * `entity` - The address to check
+    /// * `resource_id` - The identifier of the resource
+    /// * `scope` - The operation being requested
+    /// * `purpose` - The purpose of use declared by the caller
+    ///
+    /// # Returns
+    /// `true` if the entity is in good standing and has a valid (non-expired)
+    /// grant including `scope` that was issued for `purpose`, `false` otherwise
+    pub fn check_access(
+        env: Env,
+        entity: Address,
+        resource_id: String,
+        scope: Scope,
+        purpose: Purpose,
+    ) -> bool {
+        let current_time = env.ledger().timestamp();
+        if !is_in_good_standing(&env, &entity) {
+            return false;
+        }
+
+        for candidate in resource_lineage(&env, &resource_id).iter() {
+            if let Some(permission) = load_permission(&env, &entity, &candidate) {
+                // Skip expired permissions
+                if is_expired(&permission, current_time) {
+                    continue;
+                }
+                extend_permission_ttl(&env, &entity, &permission);
+                if permission.purpose == purpose && permission.scopes.contains(scope) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Get the entities with access to a specific resource, one page at a time
+    ///
+    /// Grants on the resource itself are listed first, followed by wildcard
+    /// grants on each parent path, nearest first.
+    ///
+    /// # Arguments
+    /// * `resource_id` - The identifier of the resource
+    /// * `cursor` - Position to start from (0 for the first page)
+    /// * `limit` - Maximum number of entries to return
+    ///
+    /// # Returns
+    /// The grantees and the resource path each was granted on, plus the cursor
+    /// of the next page
+    pub fn get_authorized_parties(
+        env: Env,
+        resource_id: String,
+        cursor: u32,
+        limit: u32,
+    ) -> PartyPage {
+        let limit = page_limit(limit);
+        let mut parties: Vec<GrantRef> = Vec::new(&env);
+        let mut skip = cursor;
+        let mut has_more = false;
+
+        for candidate in resource_lineage(&env, &resource_id).iter() {
+            // Entities not yet migrated are still listed in the legacy vector
+            let legacy: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::ResourceAccess(candidate.clone()))
+                .unwrap_or(Vec::new(&env));
+            let list = IndexKey::ResourceGrants(resource_key(&env, &candidate));
+            let segment_len = legacy.len() + index_len(&env, &list);
+
+            if skip >= segment_len {
+                skip -= segment_len;
+                continue;
+            }
+            if parties.len() == limit {
+                has_more = true;
+                break;
+            }
+
+            for position in skip..segment_len {
+                if parties.len() == limit {
+                    has_more = true;
+                    break;
+                }
+                if position < legacy.len() {
+                    parties.push_back(GrantRef {
+                        grantee: legacy.get(position).unwrap(),
+                        resource_id: candidate.clone(),
+                    });
+                } else if let Some(grant) = index_get(&env, &list, position - legacy.len()) {
+                    parties.push_back(grant);
+                }
+            }
+            skip = 0;
+        }
+
+        PartyPage {
+            next_cursor: if has_more {
+                Some(cursor + parties.len())
+            } else {
+                None
+            },
+            parties,
+        }
+    }
+
+    /// Get entity details by wallet address
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the entity
+    ///
+    /// # Returns
+    /// The EntityData for the given wallet address
+    pub fn get_entity(env: Env, wallet: Address) -> EntityData {
+        let key = DataKey::Entity(wallet);
+        extend_record_ttl(&env, &key);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .expect("Entity not found")
+    }
+
+    /// Get the access permissions held by an entity, one page at a time
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the entity
+    /// * `cursor` - Position to start from (0 for the first page)
+    /// * `limit` - Maximum number of permissions to return
+    ///
+    /// # Returns
+    /// The permissions on this page plus the cursor of the next page
+    pub fn get_entity_permissions(
+        env: Env,
+        wallet: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> PermissionPage {
+        let limit = page_limit(limit);
+        let mut permissions: Vec<AccessPermission> = Vec::new(&env);
+
+        if let Some(legacy) = load_legacy_access_list(&env, &wallet) {
+            let end = cursor.saturating_add(limit).min(legacy.len());
+            for position in cursor..end {
+                permissions.push_back(upgrade_legacy_permission(
+                    &env,
+                    legacy.get(position).unwrap(),
+                ));
+            }
+            return PermissionPage {
+                permissions,
+                next_cursor: if end < legacy.len() { Some(end) } else { None },
+            };
+        }
+
+        let list = IndexKey::EntityGrants(wallet.clone());
+        for grant in index_page(&env, &list, cursor, limit).iter() {
+            if let Some(permission) = load_permission(&env, &wallet, &grant.resource_id) {
+                permissions.push_back(permission);
+            }
+        }
+
+        let end = cursor.saturating_add(limit);
+        PermissionPage {
+            permissions,
+            next_cursor: if end < index_len(&env, &list) {
+                Some(end)
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Get the grants issued by an address, grouped by purpose of use
+    ///
+    /// # Arguments
+    /// * `grantor` - The address that issued the grants (e.g. a patient)
+    /// * `cursor` - Position to start from (0 for the first page)
+    /// * `limit` - Maximum number of grants to group on this page
+    ///
+    /// # Returns
+    /// One entry per purpose with at least one grant on this page, plus the
+    /// cursor of the next page
+    pub fn get_grants_by_purpose(
+        env: Env,
+        grantor: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> PurposeGrantsPage {
+        let limit = page_limit(limit);
+        let list = IndexKey::IssuedGrants(grantor);
+        let issued = index_page(&env, &list, cursor, limit);
+
+        let purposes = [
+            Purpose::Treatment,
+            Purpose::Payment,
+            Purpose::Operations,
+            Purpose::Research,
+            Purpose::Emergency,
+        ];
+
+        let mut groups: Vec<PurposeGrants> = Vec::new(&env);
+        for purpose in purposes {
+            let mut grants: Vec<IssuedGrant> = Vec::new(&env);
+            for grant in issued.iter() {
+                if let Some(permission) = load_permission(&env, &grant.grantee, &grant.resource_id)
+                {
+                    if permission.purpose == purpose {
+                        grants.push_back(IssuedGrant {
+                            grantee: grant.grantee,
+                            permission,
+                        });
+                    }
+                }
+            }
+            if !grants.is_empty() {
+                groups.push_back(PurposeGrants { purpose, grants });
+            }
+        }
+
+        let end = cursor.saturating_add(limit);
+        PurposeGrantsPage {
+            groups,
+            next_cursor: if end < index_len(&env, &list) {
+                Some(end)
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Move part of an entity's legacy access list into per-resource storage
+    ///
+    /// Anyone may call this; it only relocates existing grants. Legacy grants
+    /// predate scopes and purposes, so they are migrated as read-only direct
+    /// grants for treatment. The grantor index is rebuilt from the migrated
+    /// grants, so it is complete once every grantee has been migrated.
+    ///
+    /// # Arguments
+    /// * `entity` - The entity whose legacy access list should be migrated
+    /// * `limit` - Maximum number of permissions to migrate in this call
+    ///
+    /// # Returns
+    /// The number of permissions still waiting to be migrated
+    pub fn migrate_entity(env: Env, entity: Address, limit: u32) -> u32 {
+        let access_key = DataKey::AccessList(entity.clone());
+        let mut legacy = match load_legacy_access_list(&env, &entity) {
+            Some(legacy) => legacy,
+            None => return 0,
+        };
+
+        let mut migrated = 0;
+        while migrated < limit {
+            let permission = match legacy.pop_back() {
+                Some(permission) => upgrade_legacy_permission(&env, permission),
+                None => break,
+            };
+            let resource_id = permission.resource_id.clone();
+
+            save_permission(&env, &entity, &permission);
+
+            let grant = GrantRef {
+                grantee: entity.clone(),
+                resource_id: resource_id.clone(),
+            };
+            index_push(&env, &IndexKey::EntityGrants(entity.clone()), &grant);
+            index_push(
+                &env,
+                &IndexKey::ResourceGrants(resource_key(&env, &resource_id)),
+                &grant,
+            );
+            index_push(
+                &env,
+                &IndexKey::IssuedGrants(permission.granted_by.clone()),
+                &grant,
+            );
+
+            // Drop the entity from the legacy authorized parties of the resource
+            let resource_key = DataKey::ResourceAccess(resource_id);
+            let authorized: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&resource_key)
+                .unwrap_or(Vec::new(&env));
+            let mut remaining: Vec<Address> = Vec::new(&env);
+            for addr in authorized.iter() {
+                if addr != entity {
+                    remaining.push_back(addr);
+                }
+            }
+            if remaining.is_empty() {
+                env.storage().persistent().remove(&resource_key);
+            } else {
+                env.storage().persistent().set(&resource_key, &remaining);
+            }
+
+            migrated += 1;
+        }
+
+        if legacy.is_empty() {
+            env.storage().persistent().remove(&access_key);
+        } else {
+            env.storage().persistent().set(&access_key, &legacy);
+        }
+
+        env.events()
+            .publish((symbol_short!("migrate"), entity), migrated);
+
+        legacy.len()
+    }
+
+    /// Record that a patient wallet was replaced through social recovery
+    ///
+    /// Only the identity registry set by the council may call this. The
+    /// replacement takes over the patient's entity record, and the grants the
+    /// old wallet issued move to the replacement, with paths under
+    /// `patient/<old_wallet>/` re-keyed to `patient/<new_wallet>/`. Grants
+    /// are moved in pages with `migrate_recovered_grants`, since a patient
+    /// may have issued more than one transaction can touch.
+    ///
+    /// # Arguments
+    /// * `old_wallet` - The wallet that was recovered
+    /// * `new_wallet` - The wallet now controlled by the patient
+    pub fn record_recovery(env: Env, old_wallet: Address, new_wallet: Address) {
+        let registry: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::IdentityRegistry)
+            .expect("Identity registry not configured");
+        registry.require_auth();
+
+        let recovered_key = DataKey::RecoveredTo(old_wallet.clone());
+        if env.storage().persistent().has(&recovered_key) {
+            panic!("Wallet already recovered");
+        }
+        env.storage().persistent().set(&recovered_key, &new_wallet);
+
+        let old_key = DataKey::Entity(old_wallet.clone());
+        if let Some(mut entity) = env.storage().persistent().get::<_, EntityData>(&old_key) {
+            let new_key = DataKey::Entity(new_wallet.clone());
+            if !env.storage().persistent().has(&new_key) {
+                env.storage().persistent().set(&new_key, &entity);
+            }
+            entity.active = false;
+            env.storage().persistent().set(&old_key, &entity);
+        }
+
+        env.events()
+            .publish((symbol_short!("recovered"), old_wallet), new_wallet);
+    }
+
+    /// Move more of a recovered wallet's issued grants to its replacement
+    ///
+    /// Anyone may call this; it only relocates existing grants.
+    ///
+    /// # Arguments
+    /// * `old_wallet` - The wallet that was recovered
+    /// * `limit` - Maximum number of grants to move in this call
+    ///
+    /// # Returns
+    /// The number of grants moved, 0 once none are left
+    pub fn migrate_recovered_grants(env: Env, old_wallet: Address, limit: u32) -> u32 {
+        let new_wallet: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RecoveredTo(old_wallet.clone()))
+            .expect("Wallet has not been recovered");
+        migrate_issued_grants(&env, &old_wallet, &new_wallet, page_limit(limit))
+    }
+
+    /// Update entity metadata
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the entity
+    /// * `metadata` - Updated metadata information
+    pub fn update_entity(env: Env, wallet: Address, metadata: String) {
+        wallet.require_auth();
+
+        let key = DataKey::Entity(wallet.clone());
+        let mut entity: EntityData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Entity not found");
+
+        entity.metadata = metadata;
+        env.storage().persistent().set(&key, &entity);
+
+        env.events()
+            .publish((symbol_short!("upd_ent"), wallet), symbol_short!("success"));
+    }
+
+    /// Deactivate an entity (admin only)
+    ///
+    /// Once the council requires more than one approval this must go through
+    /// `propose_admin_action` instead.
     ///
     /// # Arguments
-    /// * `admin` - The admin address for the contract
-    pub fn initialize(env: Env, admin: Address) {
-        if env.storage().persistent().has(&DataKey::Admin) {
-            panic!("Contract already initialized");
-        }
+    /// * `admin` - The admin address
+    /// * `wallet` - The wallet address of the entity to deactivate
+    pub fn deactivate_entity(env: Env, admin: Address, wallet: Address) {
         admin.require_auth();
-        env.storage().persistent().set(&DataKey::Admin, &admin);
+        require_sole_admin(&env, &admin, "Only admin can deactivate entities");
 
-        env.events()
-            .publish((symbol_short!("init"), admin), symbol_short!("success"));
+        apply_admin_action(&env, AdminAction::DeactivateEntity(wallet));
     }
 
-    /// Register a new entity in the system
+    /// Set the default scopes granted to an entity type (admin only)
     ///
     /// # Arguments
-    /// * `wallet` - The wallet address of the entity
-    /// * `entity_type` - The type of entity (Hospital, Doctor, Patient, etc.)
-    /// * `name` - The name of the entity
-    /// * `metadata` - Additional information about the entity
-    pub fn register_entity(
+    /// * `admin` - The admin address
+    /// * `entity_type` - The entity type the template applies to
+    /// * `scopes` - The scopes granted by `grant_role_access`
+    pub fn set_role_template(
         env: Env,
-        wallet: Address,
+        admin: Address,
         entity_type: EntityType,
-        name: String,
-        metadata: String,
+        scopes: Vec<Scope>,
     ) {
-        wallet.require_auth();
+        admin.require_auth();
+        require_sole_admin(&env, &admin, "Only admin can set role templates");
 
-        let key = DataKey::Entity(wallet.clone());
-        if env.storage().persistent().has(&key) {
-            panic!("Entity already registered");
-        }
+        apply_admin_action(&env, AdminAction::SetRoleTemplate(entity_type, scopes));
+    }
 
-        let entity = EntityData {
-            entity_type,
-            name,
-            metadata,
-            active: true,
-        };
+    /// Get the scopes granted to an entity type by `grant_role_access`
+    ///
+    /// # Arguments
+    /// * `entity_type` - The entity type
+    ///
+    /// # Returns
+    /// The configured role template, or the built-in default for the type
+    pub fn get_role_template(env: Env, entity_type: EntityType) -> Vec<Scope> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RoleTemplate(entity_type.clone()))
+            .unwrap_or_else(|| default_role_scopes(&env, &entity_type))
+    }
 
-        env.storage().persistent().set(&key, &entity);
+    /// Set the maximum number of delegation hops below a direct grant (admin only)
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `max_depth` - The maximum delegation depth (0 disables delegation)
+    pub fn set_max_delegation_depth(env: Env, admin: Address, max_depth: u32) {
+        admin.require_auth();
+        require_sole_admin(&env, &admin, "Only admin can set delegation depth");
+
+        apply_admin_action(&env, AdminAction::SetMaxDelegationDepth(max_depth));
+    }
 
-        // Initialize empty access list for the entity
-        let empty_access: Vec<AccessPermission> = Vec::new(&env);
+    /// Get the maximum number of delegation hops below a direct grant
+    pub fn get_max_delegation_depth(env: Env) -> u32 {
         env.storage()
             .persistent()
-            .set(&DataKey::AccessList(wallet.clone()), &empty_access);
-
-        env.events()
-            .publish((symbol_short!("reg_ent"), wallet), symbol_short!("success"));
+            .get(&DataKey::MaxDelegationDepth)
+            .unwrap_or(DEFAULT_MAX_DELEGATION_DEPTH)
     }
 
-    /// Grant access permission to an entity for a specific resource
+    /// Attest an entity's credential, marking it verified until `expires_at`
+    ///
+    /// Also reinstates a suspended entity. Revoked entities cannot be
+    /// verified again.
     ///
     /// # Arguments
-    /// * `grantor` - The address granting access (must be authorized)
-    /// * `grantee` - The address receiving access
-    /// * `resource_id` - The identifier of the resource
-    /// * `expires_at` - Expiration timestamp (0 for no expiration)
-    pub fn grant_access(
+    /// * `attester` - A designated attester (e.g. a licensing board)
+    /// * `wallet` - The entity being verified
+    /// * `credential_hash` - Hash of the license or credential attested
+    /// * `expires_at` - When the credential expires
+    pub fn attest_entity(
         env: Env,
-        grantor: Address,
-        grantee: Address,
-        resource_id: String,
+        attester: Address,
+        wallet: Address,
+        credential_hash: BytesN<32>,
         expires_at: u64,
     ) {
-        grantor.require_auth();
+        attester.require_auth();
+        require_attester(&env, &attester);
 
-        // Verify grantor is a registered entity
-        let grantor_key = DataKey::Entity(grantor.clone());
-        if !env.storage().persistent().has(&grantor_key) {
-            panic!("Grantor not registered");
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Entity(wallet.clone()))
+        {
+            panic!("Entity not found");
         }
 
-        // Verify grantee is a registered entity
-        let grantee_key = DataKey::Entity(grantee.clone());
-        if !env.storage().persistent().has(&grantee_key) {
-            panic!("Grantee not registered");
+        let current_time = env.ledger().timestamp();
+        if expires_at <= current_time {
+            panic!("Credential already expired");
         }
 
-        let permission = AccessPermission {
-            resource_id: resource_id.clone(),
-            granted_by: grantor.clone(),
-            granted_at: env.ledger().timestamp(),
+        if load_verification(&env, &wallet).status == VerificationStatus::Revoked {
+            panic!("Verification has been revoked");
+        }
+
+        let verification = EntityVerification {
+            status: VerificationStatus::Verified,
+            attester: Some(attester.clone()),
+            credential_hash: Some(credential_hash),
+            updated_at: current_time,
             expires_at,
         };
-
-        // Add permission to grantee's access list
-        let access_key = DataKey::AccessList(grantee.clone());
-        let mut access_list: Vec<AccessPermission> = env
-            .storage()
+        env.storage()
             .persistent()
-            .get(&access_key)
-            .unwrap_or(Vec::new(&env));
-
-        // Check if permission already exists for this resource
-        let mut exists = false;
-        for i in 0..access_list.len() {
-            if let Some(existing) = access_list.get(i) {
-                if existing.resource_id == resource_id {
-                    exists = true;
-                    break;
-                }
-            }
-        }
-        if exists {
-            panic!("Access already granted for this resource");
+            .set(&DataKey::Verification(wallet.clone()), &verification);
+
+        env.events()
+            .publish((symbol_short!("verified"), wallet), attester);
+    }
+
+    /// Suspend a verified entity until it is attested again
+    ///
+    /// # Arguments
+    /// * `attester` - A designated attester
+    /// * `wallet` - The entity being suspended
+    pub fn suspend_entity(env: Env, attester: Address, wallet: Address) {
+        attester.require_auth();
+        require_attester(&env, &attester);
+
+        let mut verification = load_verification(&env, &wallet);
+        if verification.status != VerificationStatus::Verified {
+            panic!("Entity is not verified");
         }
 
-        access_list.push_back(permission);
-        env.storage().persistent().set(&access_key, &access_list);
+        verification.status = VerificationStatus::Suspended;
+        verification.attester = Some(attester.clone());
+        verification.updated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Verification(wallet.clone()), &verification);
+
+        env.events()
+            .publish((symbol_short!("suspended"), wallet), attester);
+    }
+
+    /// Permanently revoke an entity's verification
+    ///
+    /// # Arguments
+    /// * `attester` - A designated attester
+    /// * `wallet` - The entity whose verification is revoked
+    pub fn revoke_verification(env: Env, attester: Address, wallet: Address) {
+        attester.require_auth();
+        require_attester(&env, &attester);
 
-        // Add grantee to resource's authorized parties
-        let resource_key = DataKey::ResourceAccess(resource_id.clone());
-        let mut authorized: Vec<Address> = env
-            .storage()
+        let mut verification = load_verification(&env, &wallet);
+        if verification.status == VerificationStatus::Revoked {
+            panic!("Verification has been revoked");
+        }
+
+        verification.status = VerificationStatus::Revoked;
+        verification.attester = Some(attester.clone());
+        verification.updated_at = env.ledger().timestamp();
+        env.storage()
             .persistent()
-            .get(&resource_key)
-            .unwrap_or(Vec::new(&env));
+            .set(&DataKey::Verification(wallet.clone()), &verification);
 
-        authorized.push_back(grantee.clone());
-        env.storage().persistent().set(&resource_key, &authorized);
+        env.events()
+            .publish((symbol_short!("ver_revok"), wallet), attester);
+    }
 
-        env.events().publish(
-            (symbol_short!("grant"), grantee, resource_id),
-            symbol_short!("success"),
-        );
+    /// Get the verification record of an entity
+    ///
+    /// # Returns
+    /// The stored record, or a `Pending` record if the entity was never attested
+    pub fn get_verification(env: Env, wallet: Address) -> EntityVerification {
+        load_verification(&env, &wallet)
     }
 
-    /// Revoke access permission from an entity for a specific resource
+    /// Check whether an entity currently holds an attested, unexpired credential
+    pub fn is_entity_verified(env: Env, wallet: Address) -> bool {
+        is_verified(&env, &wallet)
+    }
+
+    /// Check whether an address is a designated attester
+    pub fn is_attester(env: Env, attester: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Attester(attester))
+            .unwrap_or(false)
+    }
+
+    /// Self-issue short-lived emergency access to a patient's resource
+    ///
+    /// The grant carries the `Emergency` purpose, lasts `BREAK_GLASS_DURATION`
+    /// and is limited to `Read` and `Write`. It is logged, announced to the
+    /// patient through an event, and queued for compliance review. An expired
+    /// grant or earlier break-glass grant the doctor holds on the same path is
+    /// replaced, but a live grant from the patient's chain is never touched.
     ///
     /// # Arguments
-    /// * `revoker` - The address revoking access (must be the original grantor or admin)
-    /// * `revokee` - The address losing access
-    /// * `resource_id` - The identifier of the resource
-    pub fn revoke_access(env: Env, revoker: Address, revokee: Address, resource_id: String) {
-        revoker.require_auth();
+    /// * `doctor` - A verified doctor
+    /// * `patient` - The patient whose resource is accessed
+    /// * `resource_id` - A resource under `patient/<patient>/`, or a trailing `/*` wildcard path
+    /// * `scopes` - The scopes needed, a subset of `Read` and `Write`
+    /// * `justification` - Why normal consent could not be obtained
+    ///
+    /// # Returns
+    /// The id of the break-glass record
+    pub fn break_glass(
+        env: Env,
+        doctor: Address,
+        patient: Address,
+        resource_id: String,
+        scopes: Vec<Scope>,
+        justification: String,
+    ) -> u64 {
+        doctor.require_auth();
 
-        // Get admin for authorization check
-        let admin: Address = env
+        let entity: EntityData = env
             .storage()
             .persistent()
-            .get(&DataKey::Admin)
-            .expect("Contract not initialized");
+            .get(&DataKey::Entity(doctor.clone()))
+            .expect("Doctor not registered");
+        if entity.entity_type != EntityType::Doctor || !entity.active {
+            panic!("Only active doctors can break glass");
+        }
+        if !is_verified(&env, &doctor) {
+            panic!("Doctor is not verified");
+        }
 
-        // Remove from grantee's access list
-        let access_key = DataKey::AccessList(revokee.clone());
-        let access_list: Vec<AccessPermission> = env
+        let patient_entity: EntityData = env
             .storage()
             .persistent()
-            .get(&access_key)
-            .unwrap_or(Vec::new(&env));
-
-        let mut new_access_list: Vec<AccessPermission> = Vec::new(&env);
-        let mut found = false;
-
-        for i in 0..access_list.len() {
-            if let Some(permission) = access_list.get(i) {
-                if permission.resource_id == resource_id {
-                    // Verify revoker is either the original grantor or admin
-                    if permission.granted_by != revoker && revoker != admin {
-                        panic!("Not authorized to revoke this access");
-                    }
-                    found = true;
-                    // Skip this permission (effectively removing it)
-                } else {
-                    new_access_list.push_back(permission);
-                }
+            .get(&DataKey::Entity(patient.clone()))
+            .expect("Patient not registered");
+        if patient_entity.entity_type != EntityType::Patient {
+            panic!("Patient not registered");
+        }
+
+        if justification.is_empty() {
+            panic!("Justification is required");
+        }
+
+        validate_resource_id(&resource_id);
+        let (buf, len) = resource_bytes(&resource_id);
+        let (prefix, prefix_len) = resource_bytes(&patient_resource(&env, &patient, ""));
+        if len <= prefix_len || buf[..prefix_len] != prefix[..prefix_len] {
+            panic!("Resource does not belong to the patient");
+        }
+
+        let scopes = normalize_scopes(&env, scopes);
+        for scope in scopes.iter() {
+            if scope != Scope::Read && scope != Scope::Write {
+                panic!("Break-glass access is limited to read and write");
             }
         }
 
-        if !found {
-            panic!("Access permission not found");
+        let issued_at = env.ledger().timestamp();
+        let expires_at = issued_at + BREAK_GLASS_DURATION;
+        let permission = AccessPermission {
+            resource_id: resource_id.clone(),
+            scopes: scopes.clone(),
+            purpose: Purpose::Emergency,
+            consent_hash: env
+                .crypto()
+                .sha256(&justification.clone().to_xdr(&env))
+                .into(),
+            granted_by: doctor.clone(),
+            granted_at: issued_at,
+            expires_at,
+            can_delegate: false,
+            depth: 0,
+        };
+        if let Some(existing) = load_permission(&env, &doctor, &resource_id) {
+            let is_break_glass =
+                existing.purpose == Purpose::Emergency && existing.granted_by == doctor;
+            if !is_break_glass && !is_expired(&existing, issued_at) {
+                panic!("Doctor already holds live access to this resource");
+            }
+            remove_permission(&env, &doctor, &existing, symbol_short!("bg_repl"));
         }
+        store_permission(&env, &doctor, permission);
 
+        let id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BreakGlassCounter)
+            .unwrap_or(0u64)
+            + 1;
         env.storage()
             .persistent()
-            .set(&access_key, &new_access_list);
+            .set(&DataKey::BreakGlassCounter, &id);
 
-        // Remove from resource's authorized parties
-        let resource_key = DataKey::ResourceAccess(resource_id.clone());
-        let authorized: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&resource_key)
-            .unwrap_or(Vec::new(&env));
-
-        let mut new_authorized: Vec<Address> = Vec::new(&env);
-        for i in 0..authorized.len() {
-            if let Some(addr) = authorized.get(i) {
-                if addr != revokee {
-                    new_authorized.push_back(addr);
+        let record = BreakGlassRecord {
+            id,
+            doctor: doctor.clone(),
+            patient: patient.clone(),
+            resource_id: resource_id.clone(),
+            scopes,
+            justification,
+            issued_at,
+            expires_at,
+            review_deadline: issued_at + BREAK_GLASS_REVIEW_WINDOW,
+            status: ReviewStatus::Pending,
+            reviewer: None,
+            reviewed_at: 0,
+        };
+        save_break_glass(&env, &record);
+
+        env.events().publish(
+            (symbol_short!("brk_glass"), patient, doctor),
+            (id, resource_id, expires_at),
+        );
+
+        id
+    }
+
+    /// Record a compliance decision on a break-glass grant
+    ///
+    /// Grants found unjustified are revoked immediately if still active.
+    ///
+    /// # Arguments
+    /// * `officer` - A compliance officer
+    /// * `id` - The break-glass record
+    /// * `justified` - Whether the emergency access was warranted
+    pub fn review_break_glass(env: Env, officer: Address, id: u64, justified: bool) {
+        officer.require_auth();
+        require_compliance_officer(&env, &officer);
+
+        let mut record = load_break_glass(&env, id);
+        if record.status != ReviewStatus::Pending && record.status != ReviewStatus::Overdue {
+            panic!("Break-glass access already reviewed");
+        }
+
+        record.status = if justified {
+            ReviewStatus::Justified
+        } else {
+            ReviewStatus::Unjustified
+        };
+        record.reviewer = Some(officer.clone());
+        record.reviewed_at = env.ledger().timestamp();
+        save_break_glass(&env, &record);
+
+        if !justified {
+            // Only remove the grant this record created, not a later one
+            if let Some(permission) = load_permission(&env, &record.doctor, &record.resource_id) {
+                if permission.purpose == Purpose::Emergency
+                    && permission.granted_by == record.doctor
+                    && permission.granted_at == record.issued_at
+                {
+                    remove_permission(
+                        &env,
+                        &record.doctor,
+                        &permission,
+                        symbol_short!("bg_revoke"),
+                    );
                 }
             }
         }
-        env.storage()
-            .persistent()
-            .set(&resource_key, &new_authorized);
+
+        env.events()
+            .publish((symbol_short!("bg_review"), id), (officer, justified));
+    }
+
+    /// Flag a break-glass grant whose review deadline has passed
+    ///
+    /// Callable by anyone so missed reviews are escalated without relying on
+    /// the compliance team. The record can still be reviewed afterwards.
+    pub fn flag_overdue_review(env: Env, id: u64) {
+        let mut record = load_break_glass(&env, id);
+        if record.status != ReviewStatus::Pending {
+            panic!("Break-glass access is not pending review");
+        }
+        if env.ledger().timestamp() <= record.review_deadline {
+            panic!("Review deadline has not passed");
+        }
+
+        record.status = ReviewStatus::Overdue;
+        save_break_glass(&env, &record);
 
         env.events().publish(
-            (symbol_short!("revoke"), revokee, resource_id),
-            symbol_short!("success"),
+            (symbol_short!("bg_overdu"), id),
+            (record.doctor, record.patient),
         );
     }
 
-    /// Check if an entity has access to a specific resource
+    /// Get a break-glass record
+    pub fn get_break_glass(env: Env, id: u64) -> BreakGlassRecord {
+        load_break_glass(&env, id)
+    }
+
+    /// Get break-glass records awaiting review, one page at a time
     ///
     /// # Arguments
-    /// * `entity` - The address to check
-    /// * `resource_id` - The identifier of the resource
+    /// * `cursor` - Record id to start from (0 for the first page)
+    /// * `limit` - Maximum number of records to scan
     ///
     /// # Returns
-    /// `true` if the entity has valid (non-expired) access, `false` otherwise
-    pub fn check_access(env: Env, entity: Address, resource_id: String) -> bool {
-        let access_key = DataKey::AccessList(entity);
-        let access_list: Vec<AccessPermission> = env
+    /// Pending and overdue records among those scanned, plus the cursor of the
+    /// next page
+    pub fn get_review_queue(env: Env, cursor: u64, limit: u32) -> BreakGlassPage {
+        let limit = page_limit(limit) as u64;
+        let count: u64 = env
             .storage()
             .persistent()
-            .get(&access_key)
-            .unwrap_or(Vec::new(&env));
+            .get(&DataKey::BreakGlassCounter)
+            .unwrap_or(0u64);
 
-        let current_time = env.ledger().timestamp();
+        let start = cursor.max(1);
+        let end = start.saturating_add(limit).min(count + 1);
 
-        for i in 0..access_list.len() {
-            if let Some(permission) = access_list.get(i) {
-                if permission.resource_id == resource_id {
-                    // Check if permission is expired
-                    if permission.expires_at == 0 || permission.expires_at > current_time {
-                        return true;
-                    }
-                }
+        let mut records = Vec::new(&env);
+        for id in start..end {
+            let record = load_break_glass(&env, id);
+            if record.status == ReviewStatus::Pending || record.status == ReviewStatus::Overdue {
+                records.push_back(record);
             }
         }
 
-        false
+        BreakGlassPage {
+            records,
+            next_cursor: if end <= count { Some(end) } else { None },
+        }
     }
 
-    /// Get all entities with access to a specific resource
-    ///
-    /// # Arguments
-    /// * `resource_id` - The identifier of the resource
-    ///
-    /// # Returns
-    /// A vector of addresses that have access to the resource
-    pub fn get_authorized_parties(env: Env, resource_id: String) -> Vec<Address> {
-        let resource_key = DataKey::ResourceAccess(resource_id);
+    /// Check whether an address is a compliance officer
+    pub fn is_compliance_officer(env: Env, officer: Address) -> bool {
         env.storage()
             .persistent()
-            .get(&resource_key)
-            .unwrap_or(Vec::new(&env))
+            .get(&DataKey::ComplianceOfficer(officer))
+            .unwrap_or(false)
     }
 
-    /// Get entity details by wallet address
+    /// Propose an admin action for council approval
     ///
     /// # Arguments
-    /// * `wallet` - The wallet address of the entity
+    /// * `proposer` - A council member; counts as the first approval
+    /// * `action` - The admin action to perform once approved
     ///
     /// # Returns
-    /// The EntityData for the given wallet address
-    pub fn get_entity(env: Env, wallet: Address) -> EntityData {
-        let key = DataKey::Entity(wallet);
+    /// The ID of the new proposal
+    pub fn propose_admin_action(env: Env, proposer: Address, action: AdminAction) -> u64 {
+        proposer.require_auth();
+
+        let council = load_council(&env);
+        if !council.members.contains(&proposer) {
+            panic!("Only council members can propose");
+        }
+
+        let proposal_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProposalCounter)
+            .unwrap_or(0u64)
+            + 1;
+
+        let created_at = env.ledger().timestamp();
+        let proposal = Proposal {
+            id: proposal_id,
+            proposer: proposer.clone(),
+            action,
+            approvals: Vec::from_array(&env, [proposer.clone()]),
+            created_at,
+            executable_at: created_at + council.timelock,
+            status: ProposalStatus::Pending,
+        };
+
         env.storage()
             .persistent()
-            .get(&key)
-            .expect("Entity not found")
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ProposalCounter, &proposal_id);
+
+        env.events()
+            .publish((symbol_short!("proposed"), proposal_id), proposer);
+
+        proposal_id
     }
 
-    /// Get all access permissions for an entity
+    /// Approve a pending proposal
     ///
     /// # Arguments
-    /// * `wallet` - The wallet address of the entity
-    ///
-    /// # Returns
-    /// A vector of all access permissions granted to the entity
-    pub fn get_entity_permissions(env: Env, wallet: Address) -> Vec<AccessPermission> {
-        let access_key = DataKey::AccessList(wallet);
+    /// * `member` - A council member who has not yet approved
+    /// * `proposal_id` - The proposal to approve
+    pub fn approve_proposal(env: Env, member: Address, proposal_id: u64) {
+        member.require_auth();
+
+        let council = load_council(&env);
+        if !council.members.contains(&member) {
+            panic!("Only council members can approve");
+        }
+
+        let mut proposal = load_proposal(&env, proposal_id);
+        if proposal.status != ProposalStatus::Pending {
+            panic!("Proposal is not pending");
+        }
+        if proposal.approvals.contains(&member) {
+            panic!("Proposal already approved by member");
+        }
+
+        proposal.approvals.push_back(member.clone());
         env.storage()
             .persistent()
-            .get(&access_key)
-            .unwrap_or(Vec::new(&env))
+            .set(&DataKey::Proposal(proposal_id), &proposal);
+
+        env.events()
+            .publish((symbol_short!("approved"), proposal_id), member);
     }
 
-    /// Update entity metadata
+    /// Execute a proposal that has enough approvals and whose time lock has passed
     ///
     /// # Arguments
-    /// * `wallet` - The wallet address of the entity
-    /// * `metadata` - Updated metadata information
-    pub fn update_entity(env: Env, wallet: Address, metadata: String) {
-        wallet.require_auth();
+    /// * `member` - A council member
+    /// * `proposal_id` - The proposal to execute
+    pub fn execute_proposal(env: Env, member: Address, proposal_id: u64) {
+        member.require_auth();
 
-        let key = DataKey::Entity(wallet.clone());
-        let mut entity: EntityData = env
-            .storage()
+        let council = load_council(&env);
+        if !council.members.contains(&member) {
+            panic!("Only council members can execute");
+        }
+
+        let mut proposal = load_proposal(&env, proposal_id);
+        if proposal.status != ProposalStatus::Pending {
+            panic!("Proposal is not pending");
+        }
+        if approval_count(&council, &proposal) < council.threshold {
+            panic!("Not enough approvals");
+        }
+        if env.ledger().timestamp() < proposal.executable_at {
+            panic!("Proposal is time-locked");
+        }
+
+        proposal.status = ProposalStatus::Executed;
+        env.storage()
             .persistent()
-            .get(&key)
-            .expect("Entity not found");
+            .set(&DataKey::Proposal(proposal_id), &proposal);
 
-        entity.metadata = metadata;
-        env.storage().persistent().set(&key, &entity);
+        apply_admin_action(&env, proposal.action);
 
         env.events()
-            .publish((symbol_short!("upd_ent"), wallet), symbol_short!("success"));
+            .publish((symbol_short!("executed"), proposal_id), member);
     }
 
-    /// Deactivate an entity (admin only)
+    /// Cancel a pending proposal
+    ///
+    /// Any council member may cancel, which lets the council veto an action
+    /// during its time lock.
     ///
     /// # Arguments
-    /// * `admin` - The admin address
-    /// * `wallet` - The wallet address of the entity to deactivate
-    pub fn deactivate_entity(env: Env, admin: Address, wallet: Address) {
-        admin.require_auth();
+    /// * `member` - A council member
+    /// * `proposal_id` - The proposal to cancel
+    pub fn cancel_proposal(env: Env, member: Address, proposal_id: u64) {
+        member.require_auth();
 
-        let stored_admin: Address = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Admin)
-            .expect("Contract not initialized");
+        let council = load_council(&env);
+        if !council.members.contains(&member) {
+            panic!("Only council members can cancel");
+        }
 
-        if admin != stored_admin {
-            panic!("Only admin can deactivate entities");
+        let mut proposal = load_proposal(&env, proposal_id);
+        if proposal.status != ProposalStatus::Pending {
+            panic!("Proposal is not pending");
         }
 
-        let key = DataKey::Entity(wallet.clone());
-        let mut entity: EntityData = env
-            .storage()
+        proposal.status = ProposalStatus::Cancelled;
+        env.storage()
             .persistent()
-            .get(&key)
-            .expect("Entity not found");
-
-        entity.active = false;
-        env.storage().persistent().set(&key, &entity);
+            .set(&DataKey::Proposal(proposal_id), &proposal);
 
         env.events()
-            .publish((symbol_short!("deact"), wallet), symbol_short!("success"));
+            .publish((symbol_short!("cancelled"), proposal_id), member);
+    }
+
+    /// Get a proposal by ID
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Proposal {
+        load_proposal(&env, proposal_id)
+    }
+
+    /// Get the admin council members, approval threshold and time lock
+    pub fn get_council(env: Env) -> Council {
+        load_council(&env)
     }
 }
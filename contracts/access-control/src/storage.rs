@@ -0,0 +1,241 @@
+use soroban_sdk::{xdr::ToXdr, Address, BytesN, Env, String, Vec};
+
+use crate::{
+    AccessPermission, DataKey, GrantRef, IndexKey, LegacyAccessPermission, Purpose, Scope,
+};
+
+const BUMP_AMOUNT: u32 = 518400; // ~60 days in ledgers (assuming 5s ledger)
+const BUMP_THRESHOLD: u32 = 259200; // ~30 days
+
+/// Hashes a resource path so ledger keys stay within the key size limit.
+pub fn resource_key(env: &Env, resource_id: &String) -> BytesN<32> {
+    env.crypto().sha256(&resource_id.clone().to_xdr(env)).into()
+}
+
+/// Identifies a grant inside an index by hashing its grantee and resource.
+fn grant_key(env: &Env, grant: &GrantRef) -> BytesN<32> {
+    env.crypto()
+        .sha256(&(grant.grantee.clone(), grant.resource_id.clone()).to_xdr(env))
+        .into()
+}
+
+// --------------------
+// Indexed lists
+// --------------------
+//
+// Each list is stored as a count plus one ledger entry per item, with a
+// reverse position entry so removal is a constant-cost swap with the last
+// item instead of a rewrite of the whole list.
+
+pub fn index_len(env: &Env, list: &IndexKey) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IndexLen(list.clone()))
+        .unwrap_or(0)
+}
+
+pub fn index_get(env: &Env, list: &IndexKey, position: u32) -> Option<GrantRef> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IndexItem(list.clone(), position))
+}
+
+pub fn index_push(env: &Env, list: &IndexKey, item: &GrantRef) {
+    let pos_key = DataKey::IndexPos(list.clone(), grant_key(env, item));
+    if env.storage().persistent().has(&pos_key) {
+        return;
+    }
+
+    let len = index_len(env, list);
+    let item_key = DataKey::IndexItem(list.clone(), len);
+    let len_key = DataKey::IndexLen(list.clone());
+    env.storage().persistent().set(&item_key, item);
+    env.storage().persistent().set(&pos_key, &len);
+    env.storage().persistent().set(&len_key, &(len + 1));
+
+    for key in [item_key, pos_key, len_key] {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+    }
+}
+
+pub fn index_remove(env: &Env, list: &IndexKey, item: &GrantRef) {
+    let pos_key = DataKey::IndexPos(list.clone(), grant_key(env, item));
+    let position: u32 = match env.storage().persistent().get(&pos_key) {
+        Some(position) => position,
+        None => return,
+    };
+
+    let last = index_len(env, list) - 1;
+    if position != last {
+        // Move the last item into the vacated slot
+        let moved: GrantRef = index_get(env, list, last).expect("Index corrupted");
+        env.storage()
+            .persistent()
+            .set(&DataKey::IndexItem(list.clone(), position), &moved);
+        env.storage().persistent().set(
+            &DataKey::IndexPos(list.clone(), grant_key(env, &moved)),
+            &position,
+        );
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::IndexItem(list.clone(), last));
+    env.storage().persistent().remove(&pos_key);
+    if last == 0 {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::IndexLen(list.clone()));
+    } else {
+        env.storage()
+            .persistent()
+            .set(&DataKey::IndexLen(list.clone()), &last);
+    }
+}
+
+/// Keeps the entries that place `item` in `list` from being archived.
+pub fn index_extend_ttl(env: &Env, list: &IndexKey, item: &GrantRef) {
+    let pos_key = DataKey::IndexPos(list.clone(), grant_key(env, item));
+    let position: u32 = match env.storage().persistent().get(&pos_key) {
+        Some(position) => position,
+        None => return,
+    };
+
+    for key in [
+        DataKey::IndexItem(list.clone(), position),
+        pos_key,
+        DataKey::IndexLen(list.clone()),
+    ] {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+    }
+}
+
+/// Returns up to `limit` items starting at `cursor`.
+pub fn index_page(env: &Env, list: &IndexKey, cursor: u32, limit: u32) -> Vec<GrantRef> {
+    let len = index_len(env, list);
+    let end = cursor.saturating_add(limit).min(len);
+
+    let mut items: Vec<GrantRef> = Vec::new(env);
+    for position in cursor..end {
+        if let Some(item) = index_get(env, list, position) {
+            items.push_back(item);
+        }
+    }
+    items
+}
+
+// --------------------
+// Permissions
+// --------------------
+
+pub fn load_permission(
+    env: &Env,
+    holder: &Address,
+    resource_id: &String,
+) -> Option<AccessPermission> {
+    let key = DataKey::Permission(holder.clone(), resource_key(env, resource_id));
+    if let Some(permission) = env.storage().persistent().get(&key) {
+        return Some(permission);
+    }
+
+    // Fall back to a list written before per-resource keys were introduced
+    load_legacy_access_list(env, holder)?
+        .iter()
+        .find(|permission| permission.resource_id == *resource_id)
+        .map(|permission| upgrade_legacy_permission(env, permission))
+}
+
+pub fn save_permission(env: &Env, holder: &Address, permission: &AccessPermission) {
+    let key = DataKey::Permission(holder.clone(), resource_key(env, &permission.resource_id));
+    env.storage().persistent().set(&key, permission);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+}
+
+/// Keeps a live permission and its index entries from being archived.
+/// Legacy permissions have no entries of their own and are skipped.
+pub fn extend_permission_ttl(env: &Env, holder: &Address, permission: &AccessPermission) {
+    let resource_hash = resource_key(env, &permission.resource_id);
+    let key = DataKey::Permission(holder.clone(), resource_hash.clone());
+    if !env.storage().persistent().has(&key) {
+        return;
+    }
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+
+    let grant = GrantRef {
+        grantee: holder.clone(),
+        resource_id: permission.resource_id.clone(),
+    };
+    index_extend_ttl(env, &IndexKey::EntityGrants(holder.clone()), &grant);
+    index_extend_ttl(
+        env,
+        &IndexKey::ResourceGrants(resource_hash.clone()),
+        &grant,
+    );
+    index_extend_ttl(
+        env,
+        &IndexKey::IssuedGrants(permission.granted_by.clone()),
+        &grant,
+    );
+    if permission.depth > 0 {
+        index_extend_ttl(
+            env,
+            &IndexKey::Delegations(permission.granted_by.clone(), resource_hash),
+            &grant,
+        );
+    }
+}
+
+/// Keeps a record that is read on every access, such as an entity or the
+/// council, from being archived.
+pub fn extend_record_ttl(env: &Env, key: &DataKey) {
+    if env.storage().persistent().has(key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, BUMP_THRESHOLD, BUMP_AMOUNT);
+    }
+}
+
+pub fn delete_permission(env: &Env, holder: &Address, resource_id: &String) {
+    let key = DataKey::Permission(holder.clone(), resource_key(env, resource_id));
+    env.storage().persistent().remove(&key);
+}
+
+pub fn load_legacy_access_list(env: &Env, holder: &Address) -> Option<Vec<LegacyAccessPermission>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AccessList(holder.clone()))
+}
+
+/// Maps a grant in the original layout onto the current one: a read-only,
+/// non-delegable direct grant for treatment with no consent document on file.
+pub fn upgrade_legacy_permission(env: &Env, legacy: LegacyAccessPermission) -> AccessPermission {
+    AccessPermission {
+        resource_id: legacy.resource_id,
+        scopes: Vec::from_array(env, [Scope::Read]),
+        purpose: Purpose::Treatment,
+        consent_hash: BytesN::from_array(env, &[0; 32]),
+        granted_by: legacy.granted_by,
+        granted_at: legacy.granted_at,
+        expires_at: legacy.expires_at,
+        can_delegate: false,
+        depth: 0,
+    }
+}
+
+pub fn require_migrated(env: &Env, holder: &Address) {
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::AccessList(holder.clone()))
+    {
+        panic!("Legacy access list must be migrated first");
+    }
+}
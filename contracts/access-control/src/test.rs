@@ -1,7 +1,12 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use access_control_interface::patient_resource;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String, Vec};
+
+fn consent_hash(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[7u8; 32])
+}
 
 #[test]
 fn test_initialize() {
@@ -100,16 +105,28 @@ fn test_grant_access() {
         &String::from_str(&env, "Dr. Smith"),
         &String::from_str(&env, "metadata"),
     );
+    attest(&env, &client, &admin, &doctor);
 
     // Hospital grants access to doctor for patient records
     let resource_id = String::from_str(&env, "patient-123-records");
-    client.grant_access(&hospital, &doctor, &resource_id, &0);
+    client.grant_access(
+        &hospital,
+        &doctor,
+        &resource_id,
+        &vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
 
     // Check that doctor has access
-    assert!(client.check_access(&doctor, &resource_id));
+    assert!(client.check_access(&doctor, &resource_id, &Scope::Read, &Purpose::Treatment));
 
     // Check authorized parties
-    let authorized = client.get_authorized_parties(&resource_id);
+    let authorized = client.get_authorized_parties(&resource_id, &0, &10).parties;
     assert_eq!(authorized.len(), 1);
 }
 
@@ -140,21 +157,33 @@ fn test_revoke_access() {
         &String::from_str(&env, "Dr. Smith"),
         &String::from_str(&env, "metadata"),
     );
+    attest(&env, &client, &admin, &doctor);
 
     let resource_id = String::from_str(&env, "patient-123-records");
-    client.grant_access(&hospital, &doctor, &resource_id, &0);
+    client.grant_access(
+        &hospital,
+        &doctor,
+        &resource_id,
+        &vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
 
     // Verify access exists
-    assert!(client.check_access(&doctor, &resource_id));
+    assert!(client.check_access(&doctor, &resource_id, &Scope::Read, &Purpose::Treatment));
 
     // Revoke access
     client.revoke_access(&hospital, &doctor, &resource_id);
 
     // Verify access is revoked
-    assert!(!client.check_access(&doctor, &resource_id));
+    assert!(!client.check_access(&doctor, &resource_id, &Scope::Read, &Purpose::Treatment));
 
     // Verify authorized parties is empty
-    let authorized = client.get_authorized_parties(&resource_id);
+    let authorized = client.get_authorized_parties(&resource_id, &0, &10).parties;
     assert_eq!(authorized.len(), 0);
 }
 
@@ -187,19 +216,31 @@ fn test_check_access_expired() {
         &String::from_str(&env, "Dr. Smith"),
         &String::from_str(&env, "metadata"),
     );
+    attest(&env, &client, &admin, &doctor);
 
     // Grant access with expiration at timestamp 100
     let resource_id = String::from_str(&env, "patient-123-records");
-    client.grant_access(&hospital, &doctor, &resource_id, &100);
+    client.grant_access(
+        &hospital,
+        &doctor,
+        &resource_id,
+        &vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 100,
+        },
+    );
 
     // Access should be valid before expiration
-    assert!(client.check_access(&doctor, &resource_id));
+    assert!(client.check_access(&doctor, &resource_id, &Scope::Read, &Purpose::Treatment));
 
     // Advance ledger time past expiration
     env.ledger().set_timestamp(200);
 
     // Access should now be denied (expired)
-    assert!(!client.check_access(&doctor, &resource_id));
+    assert!(!client.check_access(&doctor, &resource_id, &Scope::Read, &Purpose::Treatment));
 }
 
 #[test]
@@ -229,16 +270,39 @@ fn test_get_entity_permissions() {
         &String::from_str(&env, "Dr. Smith"),
         &String::from_str(&env, "metadata"),
     );
+    attest(&env, &client, &admin, &doctor);
 
     // Grant multiple access permissions
     let resource_1 = String::from_str(&env, "patient-123-records");
     let resource_2 = String::from_str(&env, "patient-456-records");
 
-    client.grant_access(&hospital, &doctor, &resource_1, &0);
-    client.grant_access(&hospital, &doctor, &resource_2, &0);
+    client.grant_access(
+        &hospital,
+        &doctor,
+        &resource_1,
+        &vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    client.grant_access(
+        &hospital,
+        &doctor,
+        &resource_2,
+        &vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
 
     // Get all permissions for the doctor
-    let permissions = client.get_entity_permissions(&doctor);
+    let permissions = client.get_entity_permissions(&doctor, &0, &10).permissions;
     assert_eq!(permissions.len(), 2);
 }
 
@@ -321,3 +385,2340 @@ fn test_update_entity() {
     let entity = client.get_entity(&hospital);
     assert_eq!(entity.metadata, new_metadata);
 }
+
+#[test]
+fn test_check_access_scope_denied() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let hospital = Address::generate(&env);
+    let insurer = Address::generate(&env);
+
+    client.register_entity(
+        &hospital,
+        &EntityType::Hospital,
+        &String::from_str(&env, "City Hospital"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    client.register_entity(
+        &insurer,
+        &EntityType::Insurer,
+        &String::from_str(&env, "Acme Insurance"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &insurer);
+
+    // Insurer may only read the claim records
+    let resource_id = String::from_str(&env, "patient-123-claims");
+    client.grant_access(
+        &hospital,
+        &insurer,
+        &resource_id,
+        &vec![&env, Scope::Read, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+
+    assert!(client.check_access(&insurer, &resource_id, &Scope::Read, &Purpose::Treatment));
+    assert!(!client.check_access(&insurer, &resource_id, &Scope::Write, &Purpose::Treatment));
+    assert!(!client.check_access(&insurer, &resource_id, &Scope::Delete, &Purpose::Treatment));
+
+    // Duplicate scopes are collapsed
+    let permissions = client.get_entity_permissions(&insurer, &0, &10).permissions;
+    assert_eq!(permissions.get(0).unwrap().scopes.len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "At least one scope is required")]
+fn test_grant_access_empty_scopes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let hospital = Address::generate(&env);
+    let doctor = Address::generate(&env);
+
+    client.register_entity(
+        &hospital,
+        &EntityType::Hospital,
+        &String::from_str(&env, "City Hospital"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    client.register_entity(
+        &doctor,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. Smith"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &doctor);
+
+    let resource_id = String::from_str(&env, "patient-123-records");
+    client.grant_access(
+        &hospital,
+        &doctor,
+        &resource_id,
+        &vec![&env],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+}
+
+#[test]
+fn test_grant_role_access_uses_template() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let hospital = Address::generate(&env);
+    let doctor = Address::generate(&env);
+    let insurer = Address::generate(&env);
+
+    client.register_entity(
+        &hospital,
+        &EntityType::Hospital,
+        &String::from_str(&env, "City Hospital"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    client.register_entity(
+        &doctor,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. Smith"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &doctor);
+
+    client.register_entity(
+        &insurer,
+        &EntityType::Insurer,
+        &String::from_str(&env, "Acme Insurance"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &insurer);
+
+    // Default Doctor template is read + write
+    let records = String::from_str(&env, "patient-123-records");
+    client.grant_role_access(
+        &hospital,
+        &doctor,
+        &records,
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    assert!(client.check_access(&doctor, &records, &Scope::Read, &Purpose::Treatment));
+    assert!(client.check_access(&doctor, &records, &Scope::Write, &Purpose::Treatment));
+    assert!(!client.check_access(&doctor, &records, &Scope::Amend, &Purpose::Treatment));
+
+    // Admin narrows the Insurer template
+    client.set_role_template(&admin, &EntityType::Insurer, &vec![&env, Scope::Read]);
+    assert_eq!(
+        client.get_role_template(&EntityType::Insurer),
+        vec![&env, Scope::Read]
+    );
+
+    let claims = String::from_str(&env, "patient-123-claims");
+    client.grant_role_access(
+        &hospital,
+        &insurer,
+        &claims,
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    assert!(client.check_access(&insurer, &claims, &Scope::Read, &Purpose::Treatment));
+    assert!(!client.check_access(&insurer, &claims, &Scope::Write, &Purpose::Treatment));
+}
+
+#[test]
+#[should_panic(expected = "Only admin can set role templates")]
+fn test_set_role_template_non_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let non_admin = Address::generate(&env);
+    client.set_role_template(&non_admin, &EntityType::Doctor, &vec![&env, Scope::Delete]);
+}
+
+fn attest(env: &Env, client: &AccessControlClient, admin: &Address, entity: &Address) {
+    let attester = Address::generate(env);
+    let id = client.propose_admin_action(admin, &AdminAction::AddAttester(attester.clone()));
+    client.execute_proposal(admin, &id);
+    client.attest_entity(
+        &attester,
+        entity,
+        &BytesN::from_array(env, &[9u8; 32]),
+        &(env.ledger().timestamp() + 1_000_000),
+    );
+}
+
+fn setup_delegation_chain(
+    env: &Env,
+) -> (
+    AccessControlClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+    String,
+) {
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let patient = Address::generate(env);
+    let primary = Address::generate(env);
+    let specialist = Address::generate(env);
+
+    client.register_entity(
+        &patient,
+        &EntityType::Patient,
+        &String::from_str(env, "Jane Doe"),
+        &String::from_str(env, "metadata"),
+    );
+
+    client.register_entity(
+        &primary,
+        &EntityType::Doctor,
+        &String::from_str(env, "Dr. Primary"),
+        &String::from_str(env, "metadata"),
+    );
+    attest(env, &client, &admin, &primary);
+
+    client.register_entity(
+        &specialist,
+        &EntityType::Doctor,
+        &String::from_str(env, "Dr. Specialist"),
+        &String::from_str(env, "metadata"),
+    );
+    attest(env, &client, &admin, &specialist);
+
+    let resource_id = String::from_str(env, "patient-123-records");
+    client.grant_access(
+        &patient,
+        &primary,
+        &resource_id,
+        &vec![env, Scope::Read, Scope::Write, Scope::Share],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(env),
+            can_delegate: true,
+            expires_at: 0,
+        },
+    );
+
+    (client, admin, patient, primary, specialist, resource_id)
+}
+
+#[test]
+fn test_delegate_access() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, primary, specialist, resource_id) = setup_delegation_chain(&env);
+
+    client.delegate_access(
+        &primary,
+        &specialist,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &false,
+        &0,
+    );
+
+    assert!(client.check_access(&specialist, &resource_id, &Scope::Read, &Purpose::Treatment));
+    assert!(!client.check_access(
+        &specialist,
+        &resource_id,
+        &Scope::Write,
+        &Purpose::Treatment
+    ));
+
+    let permission = client
+        .get_entity_permissions(&specialist, &0, &10)
+        .permissions
+        .get(0)
+        .unwrap();
+    assert_eq!(permission.granted_by, primary);
+    assert_eq!(permission.depth, 1);
+
+    assert_eq!(
+        client.get_delegations(&primary, &resource_id),
+        vec![&env, specialist.clone()]
+    );
+    assert_eq!(
+        client
+            .get_authorized_parties(&resource_id, &0, &10)
+            .parties
+            .len(),
+        2
+    );
+}
+
+#[test]
+#[should_panic(expected = "Delegated scopes exceed delegator scopes")]
+fn test_delegate_access_scope_escalation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, primary, specialist, resource_id) = setup_delegation_chain(&env);
+
+    client.delegate_access(
+        &primary,
+        &specialist,
+        &resource_id,
+        &vec![&env, Scope::Read, Scope::Delete],
+        &false,
+        &0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Access is not delegable")]
+fn test_delegate_access_not_delegable() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, primary, specialist, resource_id) = setup_delegation_chain(&env);
+
+    let nurse = Address::generate(&env);
+    client.register_entity(
+        &nurse,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Nurse"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &nurse);
+
+    client.delegate_access(
+        &primary,
+        &specialist,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &false,
+        &0,
+    );
+
+    // Specialist received a non-delegable grant
+    client.delegate_access(
+        &specialist,
+        &nurse,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &false,
+        &0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Maximum delegation depth exceeded")]
+fn test_delegate_access_depth_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, primary, specialist, resource_id) = setup_delegation_chain(&env);
+
+    client.set_max_delegation_depth(&admin, &1);
+    assert_eq!(client.get_max_delegation_depth(), 1);
+
+    let nurse = Address::generate(&env);
+    client.register_entity(
+        &nurse,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Nurse"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &nurse);
+
+    client.delegate_access(
+        &primary,
+        &specialist,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &true,
+        &0,
+    );
+
+    // Second hop exceeds the configured depth of 1
+    client.delegate_access(
+        &specialist,
+        &nurse,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &false,
+        &0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Delegated access cannot outlive delegator access")]
+fn test_delegate_access_outlives_parent() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, primary, specialist, _) = setup_delegation_chain(&env);
+
+    let resource_id = String::from_str(&env, "patient-123-imaging");
+    client.grant_access(
+        &patient,
+        &primary,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: true,
+            expires_at: 100,
+        },
+    );
+
+    client.delegate_access(
+        &primary,
+        &specialist,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &false,
+        &0,
+    );
+}
+
+#[test]
+fn test_revoke_access_cascades_to_delegates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, patient, primary, specialist, resource_id) = setup_delegation_chain(&env);
+
+    let nurse = Address::generate(&env);
+    client.register_entity(
+        &nurse,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Nurse"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &nurse);
+
+    client.delegate_access(
+        &primary,
+        &specialist,
+        &resource_id,
+        &vec![&env, Scope::Read, Scope::Share],
+        &true,
+        &0,
+    );
+    client.delegate_access(
+        &specialist,
+        &nurse,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &false,
+        &0,
+    );
+    assert_eq!(
+        client
+            .get_authorized_parties(&resource_id, &0, &10)
+            .parties
+            .len(),
+        3
+    );
+
+    // Patient revokes the root grant; the whole chain goes with it
+    client.revoke_access(&patient, &primary, &resource_id);
+
+    assert!(!client.check_access(&primary, &resource_id, &Scope::Read, &Purpose::Treatment));
+    assert!(!client.check_access(&specialist, &resource_id, &Scope::Read, &Purpose::Treatment));
+    assert!(!client.check_access(&nurse, &resource_id, &Scope::Read, &Purpose::Treatment));
+    assert_eq!(
+        client
+            .get_authorized_parties(&resource_id, &0, &10)
+            .parties
+            .len(),
+        0
+    );
+    assert_eq!(client.get_delegations(&primary, &resource_id).len(), 0);
+}
+
+#[test]
+fn test_ancestor_can_revoke_delegated_access() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, primary, specialist, resource_id) = setup_delegation_chain(&env);
+
+    client.delegate_access(
+        &primary,
+        &specialist,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &false,
+        &0,
+    );
+
+    // The patient never signed the specialist's grant but may still revoke it
+    client.revoke_access(&patient, &specialist, &resource_id);
+
+    assert!(client.check_access(&primary, &resource_id, &Scope::Read, &Purpose::Treatment));
+    assert!(!client.check_access(&specialist, &resource_id, &Scope::Read, &Purpose::Treatment));
+    assert_eq!(client.get_delegations(&primary, &resource_id).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Not authorized to revoke this access")]
+fn test_delegate_cannot_revoke_upstream() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, primary, specialist, resource_id) = setup_delegation_chain(&env);
+
+    client.delegate_access(
+        &primary,
+        &specialist,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &false,
+        &0,
+    );
+
+    client.revoke_access(&specialist, &primary, &resource_id);
+}
+
+#[test]
+fn test_wildcard_grant_covers_children() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let patient = Address::generate(&env);
+    let lab = Address::generate(&env);
+    let radiologist = Address::generate(&env);
+
+    client.register_entity(
+        &patient,
+        &EntityType::Patient,
+        &String::from_str(&env, "Jane Doe"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    client.register_entity(
+        &lab,
+        &EntityType::Hospital,
+        &String::from_str(&env, "Central Lab"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &lab);
+
+    client.register_entity(
+        &radiologist,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. Ray"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &radiologist);
+
+    client.grant_access(
+        &patient,
+        &lab,
+        &patient_resource(&env, &patient, "labs/*"),
+        &vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    client.grant_access(
+        &patient,
+        &radiologist,
+        &patient_resource(&env, &patient, "imaging/study-1"),
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+
+    // Wildcard covers every record below the prefix
+    let cbc = patient_resource(&env, &patient, "labs/cbc-2024");
+    let nested = patient_resource(&env, &patient, "labs/panel/lipids");
+    assert!(client.check_access(&lab, &cbc, &Scope::Write, &Purpose::Treatment));
+    assert!(client.check_access(&lab, &nested, &Scope::Read, &Purpose::Treatment));
+
+    // ...but nothing outside it
+    let imaging = patient_resource(&env, &patient, "imaging/study-1");
+    assert!(!client.check_access(&lab, &imaging, &Scope::Read, &Purpose::Treatment));
+    let other_patient = patient_resource(&env, &Address::generate(&env), "labs/cbc-2024");
+    assert!(!client.check_access(&lab, &other_patient, &Scope::Read, &Purpose::Treatment));
+
+    // An exact grant does not extend to children
+    assert!(client.check_access(&radiologist, &imaging, &Scope::Read, &Purpose::Treatment));
+    let series = patient_resource(&env, &patient, "imaging/study-1/series-2");
+    assert!(!client.check_access(&radiologist, &series, &Scope::Read, &Purpose::Treatment));
+
+    // Authorized parties include inherited grants
+    let parties = client.get_authorized_parties(&cbc, &0, &10).parties;
+    assert_eq!(parties.len(), 1);
+    assert_eq!(parties.get(0).unwrap().grantee, lab);
+    assert_eq!(
+        parties.get(0).unwrap().resource_id,
+        patient_resource(&env, &patient, "labs/*")
+    );
+
+    client.grant_access(
+        &patient,
+        &radiologist,
+        &patient_resource(&env, &patient, "*"),
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    let parties = client.get_authorized_parties(&cbc, &0, &10).parties;
+    assert_eq!(parties.len(), 2);
+    assert_eq!(parties.get(1).unwrap().grantee, radiologist);
+}
+
+#[test]
+#[should_panic(expected = "Invalid resource id")]
+fn test_grant_access_invalid_wildcard() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let patient = Address::generate(&env);
+    let doctor = Address::generate(&env);
+
+    client.register_entity(
+        &patient,
+        &EntityType::Patient,
+        &String::from_str(&env, "Jane Doe"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    client.register_entity(
+        &doctor,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. Smith"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &doctor);
+
+    client.grant_access(
+        &patient,
+        &doctor,
+        &String::from_str(&env, "patient/*/labs"),
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+}
+
+/// Registers a patient and a verified hospital and doctor, returning
+/// `(client, admin, patient, hospital, doctor)`.
+fn setup_patient_records(
+    env: &Env,
+) -> (
+    AccessControlClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+
+    let patient = Address::generate(env);
+    let hospital = Address::generate(env);
+    let doctor = Address::generate(env);
+
+    client.register_entity(
+        &patient,
+        &EntityType::Patient,
+        &String::from_str(env, "Jane Doe"),
+        &String::from_str(env, "metadata"),
+    );
+    client.register_entity(
+        &hospital,
+        &EntityType::Hospital,
+        &String::from_str(env, "City Hospital"),
+        &String::from_str(env, "metadata"),
+    );
+    attest(env, &client, &admin, &hospital);
+    client.register_entity(
+        &doctor,
+        &EntityType::Doctor,
+        &String::from_str(env, "Dr. Smith"),
+        &String::from_str(env, "metadata"),
+    );
+    attest(env, &client, &admin, &doctor);
+
+    (client, admin, patient, hospital, doctor)
+}
+
+fn treatment_request(env: &Env, can_delegate: bool) -> GrantRequest {
+    GrantRequest {
+        purpose: Purpose::Treatment,
+        consent_hash: consent_hash(env),
+        can_delegate,
+        expires_at: 0,
+    }
+}
+
+#[test]
+#[should_panic(expected = "Grantor does not control this resource")]
+fn test_third_party_cannot_grant_patient_records() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, hospital, doctor) = setup_patient_records(&env);
+
+    client.grant_access(
+        &hospital,
+        &doctor,
+        &patient_resource(&env, &patient, "records"),
+        &vec![&env, Scope::Read],
+        &treatment_request(&env, false),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Only the patient or the admin council can grant patient-wide access")]
+fn test_delegate_cannot_grant_patient_wide_wildcard() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, hospital, doctor) = setup_patient_records(&env);
+    let wildcard = patient_resource(&env, &patient, "*");
+    client.grant_access(
+        &patient,
+        &hospital,
+        &wildcard,
+        &vec![&env, Scope::Read, Scope::Share],
+        &treatment_request(&env, true),
+    );
+
+    client.grant_access(
+        &hospital,
+        &doctor,
+        &wildcard,
+        &vec![&env, Scope::Read],
+        &treatment_request(&env, false),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Only the admin council can grant access to every patient")]
+fn test_patient_cannot_grant_every_patient() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, _, doctor) = setup_patient_records(&env);
+
+    client.grant_access(
+        &patient,
+        &doctor,
+        &String::from_str(&env, "patient/*"),
+        &vec![&env, Scope::Read],
+        &treatment_request(&env, false),
+    );
+}
+
+#[test]
+fn test_delegates_and_admins_grant_patient_records() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, patient, hospital, doctor) = setup_patient_records(&env);
+    let labs = patient_resource(&env, &patient, "labs/*");
+    let panel = patient_resource(&env, &patient, "labs/panel-1");
+    let expires_at = env.ledger().timestamp() + 1000;
+
+    client.grant_access(
+        &patient,
+        &hospital,
+        &labs,
+        &vec![&env, Scope::Read, Scope::Share],
+        &GrantRequest {
+            expires_at,
+            ..treatment_request(&env, true)
+        },
+    );
+
+    // A holder of a delegable grant cannot issue grants of its own...
+    assert!(client
+        .try_grant_access(
+            &hospital,
+            &doctor,
+            &panel,
+            &vec![&env, Scope::Read],
+            &treatment_request(&env, false),
+        )
+        .is_err());
+
+    // ...and may only delegate within its scopes and lifetime
+    assert!(client
+        .try_delegate_access(
+            &hospital,
+            &doctor,
+            &labs,
+            &vec![&env, Scope::Write],
+            &false,
+            &expires_at,
+        )
+        .is_err());
+    assert!(client
+        .try_delegate_access(
+            &hospital,
+            &doctor,
+            &labs,
+            &vec![&env, Scope::Read],
+            &false,
+            &0
+        )
+        .is_err());
+
+    client.delegate_access(
+        &hospital,
+        &doctor,
+        &labs,
+        &vec![&env, Scope::Read],
+        &false,
+        &expires_at,
+    );
+    assert_eq!(
+        client.get_delegations(&hospital, &labs),
+        vec![&env, doctor.clone()]
+    );
+    assert!(client.check_access(&doctor, &panel, &Scope::Read, &Purpose::Treatment));
+
+    // The patient stays in the chain and can revoke the delegated grant
+    client.revoke_access(&patient, &doctor, &labs);
+    assert!(!client.check_access(&doctor, &panel, &Scope::Read, &Purpose::Treatment));
+
+    // Revoking the hospital takes its delegations with it
+    client.delegate_access(
+        &hospital,
+        &doctor,
+        &labs,
+        &vec![&env, Scope::Read],
+        &false,
+        &expires_at,
+    );
+    client.revoke_access(&patient, &hospital, &labs);
+    assert!(!client.check_access(&doctor, &panel, &Scope::Read, &Purpose::Treatment));
+
+    // A council member registered as an entity may grant patient-wide access
+    let id = client.propose_admin_action(
+        &admin,
+        &AdminAction::RegisterAdminEntity(
+            admin.clone(),
+            String::from_str(&env, "Records Office"),
+            String::from_str(&env, "metadata"),
+        ),
+    );
+    client.execute_proposal(&admin, &id);
+    client.grant_access(
+        &admin,
+        &doctor,
+        &patient_resource(&env, &patient, "*"),
+        &vec![&env, Scope::Read],
+        &treatment_request(&env, false),
+    );
+    let imaging = patient_resource(&env, &patient, "imaging/ct-1");
+    assert!(client.check_access(&doctor, &imaging, &Scope::Read, &Purpose::Treatment));
+}
+
+#[test]
+fn test_check_access_purpose_mismatch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let patient = Address::generate(&env);
+    let researcher = Address::generate(&env);
+
+    client.register_entity(
+        &patient,
+        &EntityType::Patient,
+        &String::from_str(&env, "Jane Doe"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    client.register_entity(
+        &researcher,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. Research"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &researcher);
+
+    let resource_id = patient_resource(&env, &patient, "labs/*");
+    client.grant_access(
+        &patient,
+        &researcher,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Research,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+
+    let record = patient_resource(&env, &patient, "labs/cbc-2024");
+    assert!(client.check_access(&researcher, &record, &Scope::Read, &Purpose::Research));
+    assert!(!client.check_access(&researcher, &record, &Scope::Read, &Purpose::Treatment));
+
+    let permission = client
+        .get_entity_permissions(&researcher, &0, &10)
+        .permissions
+        .get(0)
+        .unwrap();
+    assert_eq!(permission.purpose, Purpose::Research);
+    assert_eq!(permission.consent_hash, consent_hash(&env));
+}
+
+#[test]
+fn test_delegated_access_inherits_purpose() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, primary, specialist, resource_id) = setup_delegation_chain(&env);
+
+    client.delegate_access(
+        &primary,
+        &specialist,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &false,
+        &0,
+    );
+
+    let permission = client
+        .get_entity_permissions(&specialist, &0, &10)
+        .permissions
+        .get(0)
+        .unwrap();
+    assert_eq!(permission.purpose, Purpose::Treatment);
+    assert_eq!(permission.consent_hash, consent_hash(&env));
+    assert!(!client.check_access(&specialist, &resource_id, &Scope::Read, &Purpose::Payment));
+}
+
+#[test]
+fn test_get_grants_by_purpose() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let patient = Address::generate(&env);
+    let doctor = Address::generate(&env);
+    let insurer = Address::generate(&env);
+
+    client.register_entity(
+        &patient,
+        &EntityType::Patient,
+        &String::from_str(&env, "Jane Doe"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    client.register_entity(
+        &doctor,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. Smith"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &doctor);
+
+    client.register_entity(
+        &insurer,
+        &EntityType::Insurer,
+        &String::from_str(&env, "Acme Insurance"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &insurer);
+
+    let records = patient_resource(&env, &patient, "records/*");
+    let labs = patient_resource(&env, &patient, "labs/*");
+    let claims = patient_resource(&env, &patient, "claims/*");
+
+    client.grant_access(
+        &patient,
+        &doctor,
+        &records,
+        &vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    client.grant_access(
+        &patient,
+        &doctor,
+        &labs,
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    client.grant_access(
+        &patient,
+        &insurer,
+        &claims,
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Payment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+
+    let grouped = client.get_grants_by_purpose(&patient, &0, &10).groups;
+    assert_eq!(grouped.len(), 2);
+
+    let treatment = grouped.get(0).unwrap();
+    assert_eq!(treatment.purpose, Purpose::Treatment);
+    assert_eq!(treatment.grants.len(), 2);
+
+    let payment = grouped.get(1).unwrap();
+    assert_eq!(payment.purpose, Purpose::Payment);
+    assert_eq!(payment.grants.get(0).unwrap().grantee, insurer);
+
+    // Revoked grants drop out of the listing
+    client.revoke_access(&patient, &insurer, &claims);
+    let grouped = client.get_grants_by_purpose(&patient, &0, &10).groups;
+    assert_eq!(grouped.len(), 1);
+}
+
+#[test]
+fn test_paginated_entity_permissions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let hospital = Address::generate(&env);
+    let doctor = Address::generate(&env);
+
+    client.register_entity(
+        &hospital,
+        &EntityType::Hospital,
+        &String::from_str(&env, "City Hospital"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    client.register_entity(
+        &doctor,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. Smith"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &doctor);
+
+    let resources = [
+        "records/a",
+        "records/b",
+        "records/c",
+        "records/d",
+        "records/e",
+    ];
+    for resource in resources {
+        client.grant_access(
+            &hospital,
+            &doctor,
+            &String::from_str(&env, resource),
+            &vec![&env, Scope::Read],
+            &GrantRequest {
+                purpose: Purpose::Treatment,
+                consent_hash: consent_hash(&env),
+                can_delegate: false,
+                expires_at: 0,
+            },
+        );
+    }
+
+    let first = client.get_entity_permissions(&doctor, &0, &2);
+    assert_eq!(first.permissions.len(), 2);
+    assert_eq!(first.next_cursor, Some(2));
+
+    let second = client.get_entity_permissions(&doctor, &2, &2);
+    assert_eq!(second.permissions.len(), 2);
+    assert_eq!(second.next_cursor, Some(4));
+
+    let last = client.get_entity_permissions(&doctor, &4, &2);
+    assert_eq!(last.permissions.len(), 1);
+    assert_eq!(last.next_cursor, None);
+
+    // Revoking from the middle keeps the index dense
+    client.revoke_access(&hospital, &doctor, &String::from_str(&env, "records/b"));
+    let all = client.get_entity_permissions(&doctor, &0, &10);
+    assert_eq!(all.permissions.len(), 4);
+    assert_eq!(all.next_cursor, None);
+    assert!(!client.check_access(
+        &doctor,
+        &String::from_str(&env, "records/b"),
+        &Scope::Read,
+        &Purpose::Treatment
+    ));
+    assert!(client.check_access(
+        &doctor,
+        &String::from_str(&env, "records/e"),
+        &Scope::Read,
+        &Purpose::Treatment
+    ));
+}
+
+#[test]
+fn test_paginated_authorized_parties() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let patient = Address::generate(&env);
+    client.register_entity(
+        &patient,
+        &EntityType::Patient,
+        &String::from_str(&env, "Jane Doe"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    let exact = patient_resource(&env, &patient, "labs/cbc");
+    let wildcard = patient_resource(&env, &patient, "labs/*");
+    for i in 0..3 {
+        let doctor = Address::generate(&env);
+        client.register_entity(
+            &doctor,
+            &EntityType::Doctor,
+            &String::from_str(&env, "Dr. Smith"),
+            &String::from_str(&env, "metadata"),
+        );
+        attest(&env, &client, &admin, &doctor);
+        client.grant_access(
+            &patient,
+            &doctor,
+            if i == 0 { &exact } else { &wildcard },
+            &vec![&env, Scope::Read],
+            &GrantRequest {
+                purpose: Purpose::Treatment,
+                consent_hash: consent_hash(&env),
+                can_delegate: false,
+                expires_at: 0,
+            },
+        );
+    }
+
+    // Exact grants come first, then the wildcard level
+    let first = client.get_authorized_parties(&exact, &0, &2);
+    assert_eq!(first.parties.len(), 2);
+    assert_eq!(first.parties.get(0).unwrap().resource_id, exact);
+    assert_eq!(first.parties.get(1).unwrap().resource_id, wildcard);
+    assert_eq!(first.next_cursor, Some(2));
+
+    let second = client.get_authorized_parties(&exact, &2, &2);
+    assert_eq!(second.parties.len(), 1);
+    assert_eq!(second.next_cursor, None);
+}
+
+#[test]
+fn test_migrate_legacy_access_list() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let hospital = Address::generate(&env);
+    let doctor = Address::generate(&env);
+
+    client.register_entity(
+        &hospital,
+        &EntityType::Hospital,
+        &String::from_str(&env, "City Hospital"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    client.register_entity(
+        &doctor,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. Smith"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &doctor);
+
+    // Seed storage in the original layout
+    let records = String::from_str(&env, "patient-123-records");
+    let labs = String::from_str(&env, "patient-123-labs");
+    env.as_contract(&contract_id, || {
+        let mut legacy: Vec<LegacyAccessPermission> = Vec::new(&env);
+        for resource_id in [records.clone(), labs.clone()] {
+            legacy.push_back(LegacyAccessPermission {
+                resource_id: resource_id.clone(),
+                granted_by: hospital.clone(),
+                granted_at: 0,
+                expires_at: 0,
+            });
+            env.storage().persistent().set(
+                &DataKey::ResourceAccess(resource_id),
+                &vec![&env, doctor.clone()],
+            );
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::AccessList(doctor.clone()), &legacy);
+    });
+
+    // Legacy grants are readable before migration, as read-only treatment grants
+    assert!(client.check_access(&doctor, &records, &Scope::Read, &Purpose::Treatment));
+    assert!(!client.check_access(&doctor, &records, &Scope::Write, &Purpose::Treatment));
+    assert_eq!(
+        client
+            .get_entity_permissions(&doctor, &0, &10)
+            .permissions
+            .len(),
+        2
+    );
+    assert_eq!(
+        client.get_authorized_parties(&labs, &0, &10).parties.len(),
+        1
+    );
+
+    assert_eq!(client.migrate_entity(&doctor, &1), 1);
+    assert_eq!(client.migrate_entity(&doctor, &1), 0);
+
+    env.as_contract(&contract_id, || {
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&DataKey::AccessList(doctor.clone())));
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&DataKey::ResourceAccess(records.clone())));
+    });
+
+    assert!(client.check_access(&doctor, &records, &Scope::Read, &Purpose::Treatment));
+    assert!(client.check_access(&doctor, &labs, &Scope::Read, &Purpose::Treatment));
+    let permissions = client.get_entity_permissions(&doctor, &0, &10).permissions;
+    assert_eq!(permissions.len(), 2);
+    for permission in permissions.iter() {
+        assert_eq!(permission.scopes, vec![&env, Scope::Read]);
+        assert_eq!(permission.granted_by, hospital);
+        assert!(!permission.can_delegate);
+        assert_eq!(permission.depth, 0);
+    }
+    assert_eq!(
+        client.get_authorized_parties(&labs, &0, &10).parties.len(),
+        1
+    );
+    assert_eq!(
+        client
+            .get_grants_by_purpose(&hospital, &0, &10)
+            .groups
+            .len(),
+        1
+    );
+
+    // Migrated grants can be revoked as usual
+    client.revoke_access(&hospital, &doctor, &labs);
+    assert!(!client.check_access(&doctor, &labs, &Scope::Read, &Purpose::Treatment));
+}
+
+#[test]
+#[should_panic(expected = "Legacy access list must be migrated first")]
+fn test_grant_to_unmigrated_entity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let hospital = Address::generate(&env);
+    let doctor = Address::generate(&env);
+
+    client.register_entity(
+        &hospital,
+        &EntityType::Hospital,
+        &String::from_str(&env, "City Hospital"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    client.register_entity(
+        &doctor,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. Smith"),
+        &String::from_str(&env, "metadata"),
+    );
+    attest(&env, &client, &admin, &doctor);
+
+    env.as_contract(&contract_id, || {
+        let legacy: Vec<LegacyAccessPermission> = Vec::new(&env);
+        env.storage()
+            .persistent()
+            .set(&DataKey::AccessList(doctor.clone()), &legacy);
+    });
+
+    client.grant_access(
+        &hospital,
+        &doctor,
+        &String::from_str(&env, "patient-123-records"),
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+}
+
+#[test]
+fn test_sweep_expired() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, primary, specialist, _) = setup_delegation_chain(&env);
+
+    let short = patient_resource(&env, &patient, "labs/*");
+    let long = patient_resource(&env, &patient, "imaging/*");
+    client.grant_access(
+        &patient,
+        &primary,
+        &short,
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: true,
+            expires_at: 100,
+        },
+    );
+    client.grant_access(
+        &patient,
+        &primary,
+        &long,
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 1_000,
+        },
+    );
+    client.delegate_access(
+        &primary,
+        &specialist,
+        &short,
+        &vec![&env, Scope::Read],
+        &false,
+        &100,
+    );
+
+    // Nothing has expired yet
+    assert_eq!(client.sweep_expired(&primary, &10), 0);
+
+    env.ledger().set_timestamp(500);
+    assert_eq!(client.sweep_expired(&primary, &10), 1);
+
+    // The expired grant and its delegate are gone, the rest remain
+    let permissions = client.get_entity_permissions(&primary, &0, &10).permissions;
+    assert_eq!(permissions.len(), 2);
+    assert!(permissions.iter().all(|p| p.resource_id != short));
+    assert_eq!(
+        client
+            .get_entity_permissions(&specialist, &0, &10)
+            .permissions
+            .len(),
+        0
+    );
+    assert_eq!(
+        client.get_authorized_parties(&short, &0, &10).parties.len(),
+        0
+    );
+    assert!(client.check_access(
+        &primary,
+        &patient_resource(&env, &patient, "imaging/ct-1"),
+        &Scope::Read,
+        &Purpose::Treatment
+    ));
+}
+
+#[test]
+fn test_sweep_expired_respects_limit() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, primary, _, _) = setup_delegation_chain(&env);
+
+    for resource in ["a", "b", "c"] {
+        client.grant_access(
+            &patient,
+            &primary,
+            &patient_resource(&env, &patient, resource),
+            &vec![&env, Scope::Read],
+            &GrantRequest {
+                purpose: Purpose::Treatment,
+                consent_hash: consent_hash(&env),
+                can_delegate: false,
+                expires_at: 100,
+            },
+        );
+    }
+
+    env.ledger().set_timestamp(500);
+
+    // The live setup grant counts towards the limit
+    assert_eq!(client.sweep_expired(&primary, &2), 1);
+    assert_eq!(client.sweep_expired(&primary, &10), 2);
+    assert_eq!(client.sweep_expired(&primary, &10), 0);
+    assert_eq!(
+        client
+            .get_entity_permissions(&primary, &0, &10)
+            .permissions
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_check_access_extends_ttl() {
+    use soroban_sdk::testutils::{storage::Persistent as _, Ledger};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, primary, _, resource_id) = setup_delegation_chain(&env);
+
+    let resource_hash = env.as_contract(&client.address, || {
+        storage::resource_key(&env, &resource_id)
+    });
+    let key = DataKey::Permission(primary.clone(), resource_hash.clone());
+    let entity_grants = IndexKey::EntityGrants(primary.clone());
+    let resource_grants = IndexKey::ResourceGrants(resource_hash);
+    let ttl = |key: &DataKey| {
+        env.as_contract(&client.address, || env.storage().persistent().get_ttl(key))
+    };
+
+    let initial = ttl(&key);
+
+    // Reading the grant also keeps the grantee's entity record alive
+    assert!(client.check_access(&primary, &resource_id, &Scope::Read, &Purpose::Treatment));
+    assert!(ttl(&DataKey::Entity(primary.clone())) >= initial);
+
+    // Let the entries age past the bump threshold
+    env.ledger().with_mut(|li| li.sequence_number += 300_000);
+    assert!(ttl(&key) < initial);
+    assert!(ttl(&DataKey::IndexLen(entity_grants.clone())) < initial);
+
+    assert!(client.check_access(&primary, &resource_id, &Scope::Read, &Purpose::Treatment));
+    assert!(ttl(&key) >= initial);
+    assert!(ttl(&DataKey::Entity(primary.clone())) >= initial);
+    for list in [entity_grants, resource_grants] {
+        assert!(ttl(&DataKey::IndexLen(list.clone())) >= initial);
+        assert!(ttl(&DataKey::IndexItem(list, 0)) >= initial);
+    }
+}
+
+fn setup_council(env: &Env) -> (AccessControlClient<'static>, Address, Address, Address) {
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let second = Address::generate(env);
+    let third = Address::generate(env);
+    client.initialize(&admin);
+
+    // A council of one executes its own proposals immediately
+    for action in [
+        AdminAction::AddAdmin(second.clone()),
+        AdminAction::AddAdmin(third.clone()),
+        AdminAction::SetThreshold(2),
+    ] {
+        let proposal_id = client.propose_admin_action(&admin, &action);
+        client.execute_proposal(&admin, &proposal_id);
+    }
+
+    let proposal_id = client.propose_admin_action(&admin, &AdminAction::SetTimelock(100));
+    client.approve_proposal(&second, &proposal_id);
+    client.execute_proposal(&admin, &proposal_id);
+
+    (client, admin, second, third)
+}
+
+#[test]
+fn test_council_threshold_and_timelock() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, second, _) = setup_council(&env);
+
+    let council = client.get_council();
+    assert_eq!(council.members.len(), 3);
+    assert_eq!(council.threshold, 2);
+    assert_eq!(council.timelock, 100);
+
+    let hospital = Address::generate(&env);
+    client.register_entity(
+        &hospital,
+        &EntityType::Hospital,
+        &String::from_str(&env, "City Hospital"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    let proposal_id =
+        client.propose_admin_action(&admin, &AdminAction::DeactivateEntity(hospital.clone()));
+    client.approve_proposal(&second, &proposal_id);
+
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.approvals.len(), 2);
+    assert_eq!(proposal.executable_at, proposal.created_at + 100);
+
+    env.ledger().set_timestamp(proposal.executable_at);
+    client.execute_proposal(&second, &proposal_id);
+
+    assert!(!client.get_entity(&hospital).active);
+    assert_eq!(
+        client.get_proposal(&proposal_id).status,
+        ProposalStatus::Executed
+    );
+}
+
+#[test]
+#[should_panic(expected = "Not enough approvals")]
+fn test_council_execute_without_quorum() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _) = setup_council(&env);
+
+    let proposal_id = client.propose_admin_action(&admin, &AdminAction::SetMaxDelegationDepth(5));
+    client.execute_proposal(&admin, &proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Proposal is time-locked")]
+fn test_council_execute_before_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, second, _) = setup_council(&env);
+
+    let proposal_id = client.propose_admin_action(&admin, &AdminAction::SetMaxDelegationDepth(5));
+    client.approve_proposal(&second, &proposal_id);
+    client.execute_proposal(&admin, &proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Proposal is not pending")]
+fn test_council_cancel_proposal() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, second, third) = setup_council(&env);
+
+    let proposal_id = client.propose_admin_action(&admin, &AdminAction::RemoveAdmin(third.clone()));
+    client.approve_proposal(&second, &proposal_id);
+
+    // The targeted member vetoes during the time lock
+    client.cancel_proposal(&third, &proposal_id);
+    assert_eq!(
+        client.get_proposal(&proposal_id).status,
+        ProposalStatus::Cancelled
+    );
+
+    env.ledger().set_timestamp(1_000);
+    client.execute_proposal(&admin, &proposal_id);
+}
+
+#[test]
+fn test_council_rotate_admin() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, second, third) = setup_council(&env);
+    let replacement = Address::generate(&env);
+
+    let proposal_id = client.propose_admin_action(
+        &second,
+        &AdminAction::RotateAdmin(admin.clone(), replacement.clone()),
+    );
+    client.approve_proposal(&third, &proposal_id);
+    env.ledger().set_timestamp(100);
+    client.execute_proposal(&third, &proposal_id);
+
+    let council = client.get_council();
+    assert!(council.members.contains(&replacement));
+    assert!(!council.members.contains(&admin));
+    assert_eq!(council.members.len(), 3);
+}
+
+#[test]
+#[should_panic(expected = "Action requires council approval")]
+fn test_direct_admin_action_blocked_by_council() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _) = setup_council(&env);
+
+    let hospital = Address::generate(&env);
+    client.register_entity(
+        &hospital,
+        &EntityType::Hospital,
+        &String::from_str(&env, "City Hospital"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    client.deactivate_entity(&admin, &hospital);
+}
+
+#[test]
+fn test_direct_admin_action_needs_single_member_council() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_max_delegation_depth(&admin, &2);
+
+    // A second member keeps the threshold at one but ends direct actions
+    let proposal_id =
+        client.propose_admin_action(&admin, &AdminAction::AddAdmin(Address::generate(&env)));
+    client.execute_proposal(&admin, &proposal_id);
+    assert_eq!(client.get_council().threshold, 1);
+    assert!(client.try_set_max_delegation_depth(&admin, &3).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Invalid council threshold")]
+fn test_council_threshold_above_members() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, second, _) = setup_council(&env);
+
+    let proposal_id = client.propose_admin_action(&admin, &AdminAction::SetThreshold(4));
+    client.approve_proposal(&second, &proposal_id);
+    env.ledger().set_timestamp(100);
+    client.execute_proposal(&admin, &proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "Only council members can propose")]
+fn test_non_member_cannot_propose() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _) = setup_council(&env);
+
+    let outsider = Address::generate(&env);
+    client.propose_admin_action(&outsider, &AdminAction::AddAdmin(outsider.clone()));
+}
+
+#[test]
+fn test_attestation_lifecycle() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, patient, _, _, resource_id) = setup_delegation_chain(&env);
+
+    let attester = Address::generate(&env);
+    let doctor = Address::generate(&env);
+    client.register_entity(
+        &doctor,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. New"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    let verification = client.get_verification(&doctor);
+    assert_eq!(verification.status, VerificationStatus::Pending);
+    assert!(!client.is_entity_verified(&doctor));
+
+    let id = client.propose_admin_action(&admin, &AdminAction::AddAttester(attester.clone()));
+    client.execute_proposal(&admin, &id);
+    assert!(client.is_attester(&attester));
+
+    let credential = BytesN::from_array(&env, &[3u8; 32]);
+    client.attest_entity(&attester, &doctor, &credential, &1000);
+    let verification = client.get_verification(&doctor);
+    assert_eq!(verification.status, VerificationStatus::Verified);
+    assert_eq!(verification.attester, Some(attester.clone()));
+    assert_eq!(verification.credential_hash, Some(credential.clone()));
+    assert!(client.is_entity_verified(&doctor));
+
+    client.grant_access(
+        &patient,
+        &doctor,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+
+    // Suspension blocks new grants until the entity is attested again
+    client.suspend_entity(&attester, &doctor);
+    assert_eq!(
+        client.get_verification(&doctor).status,
+        VerificationStatus::Suspended
+    );
+    assert!(!client.is_entity_verified(&doctor));
+
+    client.attest_entity(&attester, &doctor, &credential, &1000);
+    assert!(client.is_entity_verified(&doctor));
+
+    // An expired credential no longer counts as verified
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert!(!client.is_entity_verified(&doctor));
+}
+
+#[test]
+fn test_check_access_requires_good_standing() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, patient, _, _, resource_id) = setup_delegation_chain(&env);
+
+    let attester = Address::generate(&env);
+    let id = client.propose_admin_action(&admin, &AdminAction::AddAttester(attester.clone()));
+    client.execute_proposal(&admin, &id);
+
+    let doctor = Address::generate(&env);
+    client.register_entity(
+        &doctor,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. New"),
+        &String::from_str(&env, "metadata"),
+    );
+    let credential = BytesN::from_array(&env, &[3u8; 32]);
+    client.attest_entity(&attester, &doctor, &credential, &1000);
+    client.grant_access(
+        &patient,
+        &doctor,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    let check = || client.check_access(&doctor, &resource_id, &Scope::Read, &Purpose::Treatment);
+    assert!(check());
+
+    // A suspended doctor's grants stop working until it is attested again
+    client.suspend_entity(&attester, &doctor);
+    assert!(!check());
+    client.attest_entity(&attester, &doctor, &credential, &1000);
+    assert!(check());
+
+    // So do the grants of a doctor whose credential has lapsed
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    assert!(!check());
+    client.attest_entity(&attester, &doctor, &credential, &2000);
+    assert!(check());
+
+    // A deactivated doctor is denied even with a current credential
+    let id = client.propose_admin_action(&admin, &AdminAction::DeactivateEntity(doctor.clone()));
+    client.execute_proposal(&admin, &id);
+    assert!(!check());
+}
+
+#[test]
+#[should_panic(expected = "Grantee is not verified")]
+fn test_grant_to_unverified_entity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, _, _, resource_id) = setup_delegation_chain(&env);
+
+    let insurer = Address::generate(&env);
+    client.register_entity(
+        &insurer,
+        &EntityType::Insurer,
+        &String::from_str(&env, "Acme Insurance"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    client.grant_access(
+        &patient,
+        &insurer,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Payment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "Admin entities are registered by the council")]
+fn test_self_registered_admin_entity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(AccessControl, ());
+    let client = AccessControlClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env));
+
+    client.register_entity(
+        &Address::generate(&env),
+        &EntityType::Admin,
+        &String::from_str(&env, "Mallory"),
+        &String::from_str(&env, "metadata"),
+    );
+}
+
+#[test]
+fn test_council_registers_admin_entity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, patient, _, _, resource_id) = setup_delegation_chain(&env);
+
+    let operator = Address::generate(&env);
+    let id = client.propose_admin_action(
+        &admin,
+        &AdminAction::RegisterAdminEntity(
+            operator.clone(),
+            String::from_str(&env, "Records Office"),
+            String::from_str(&env, "metadata"),
+        ),
+    );
+    client.execute_proposal(&admin, &id);
+    assert_eq!(client.get_entity(&operator).entity_type, EntityType::Admin);
+
+    // Admin entities need an attested credential like any other non-patient
+    let request = GrantRequest {
+        purpose: Purpose::Operations,
+        consent_hash: consent_hash(&env),
+        can_delegate: false,
+        expires_at: 0,
+    };
+    let scopes = vec![&env, Scope::Read];
+    assert!(client
+        .try_grant_access(&patient, &operator, &resource_id, &scopes, &request)
+        .is_err());
+
+    attest(&env, &client, &admin, &operator);
+    client.grant_access(&patient, &operator, &resource_id, &scopes, &request);
+    assert!(client.check_access(&operator, &resource_id, &Scope::Read, &Purpose::Operations));
+}
+
+#[test]
+#[should_panic(expected = "Grantee is not verified")]
+fn test_delegate_to_suspended_entity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, primary, specialist, resource_id) = setup_delegation_chain(&env);
+
+    let attester = Address::generate(&env);
+    let id = client.propose_admin_action(&admin, &AdminAction::AddAttester(attester.clone()));
+    client.execute_proposal(&admin, &id);
+    client.suspend_entity(&attester, &specialist);
+
+    client.delegate_access(
+        &primary,
+        &specialist,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &false,
+        &0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Verification has been revoked")]
+fn test_revoked_verification_is_final() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _, _, specialist, _) = setup_delegation_chain(&env);
+
+    let attester = Address::generate(&env);
+    let id = client.propose_admin_action(&admin, &AdminAction::AddAttester(attester.clone()));
+    client.execute_proposal(&admin, &id);
+    client.revoke_verification(&attester, &specialist);
+    assert_eq!(
+        client.get_verification(&specialist).status,
+        VerificationStatus::Revoked
+    );
+
+    client.attest_entity(
+        &attester,
+        &specialist,
+        &BytesN::from_array(&env, &[3u8; 32]),
+        &1000,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Only attesters can change verification status")]
+fn test_non_attester_cannot_attest() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, specialist, _) = setup_delegation_chain(&env);
+
+    client.attest_entity(
+        &Address::generate(&env),
+        &specialist,
+        &BytesN::from_array(&env, &[3u8; 32]),
+        &1000,
+    );
+}
+
+fn add_compliance_officer(env: &Env, client: &AccessControlClient, admin: &Address) -> Address {
+    let officer = Address::generate(env);
+    let id =
+        client.propose_admin_action(admin, &AdminAction::AddComplianceOfficer(officer.clone()));
+    client.execute_proposal(admin, &id);
+    officer
+}
+
+#[test]
+fn test_break_glass_grants_emergency_access() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, patient, primary, _, _) = setup_delegation_chain(&env);
+    let officer = add_compliance_officer(&env, &client, &admin);
+    assert!(client.is_compliance_officer(&officer));
+
+    let resource_id = patient_resource(&env, &patient, "imaging");
+    let id = client.break_glass(
+        &primary,
+        &patient,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &String::from_str(&env, "Unconscious patient in ER"),
+    );
+
+    assert!(client.check_access(&primary, &resource_id, &Scope::Read, &Purpose::Emergency));
+    assert!(!client.check_access(&primary, &resource_id, &Scope::Write, &Purpose::Emergency));
+    assert!(!client.check_access(&primary, &resource_id, &Scope::Read, &Purpose::Treatment));
+
+    let record = client.get_break_glass(&id);
+    assert_eq!(record.status, ReviewStatus::Pending);
+    assert_eq!(record.patient, patient);
+    assert_eq!(record.expires_at, 4 * 60 * 60);
+
+    let queue = client.get_review_queue(&0, &10);
+    assert_eq!(queue.records.len(), 1);
+    assert_eq!(queue.next_cursor, None);
+
+    // The grant lapses on its own
+    env.ledger()
+        .with_mut(|li| li.timestamp = record.expires_at + 1);
+    assert!(!client.check_access(&primary, &resource_id, &Scope::Read, &Purpose::Emergency));
+
+    client.review_break_glass(&officer, &id, &true);
+    let record = client.get_break_glass(&id);
+    assert_eq!(record.status, ReviewStatus::Justified);
+    assert_eq!(record.reviewer, Some(officer));
+    assert_eq!(client.get_review_queue(&0, &10).records.len(), 0);
+}
+
+#[test]
+fn test_break_glass_unjustified_revokes_access() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, patient, primary, _, _) = setup_delegation_chain(&env);
+    let officer = add_compliance_officer(&env, &client, &admin);
+
+    let resource_id = patient_resource(&env, &patient, "imaging");
+    let id = client.break_glass(
+        &primary,
+        &patient,
+        &resource_id,
+        &vec![&env, Scope::Read, Scope::Write],
+        &String::from_str(&env, "Curious"),
+    );
+
+    client.review_break_glass(&officer, &id, &false);
+
+    assert_eq!(
+        client.get_break_glass(&id).status,
+        ReviewStatus::Unjustified
+    );
+    assert!(!client.check_access(&primary, &resource_id, &Scope::Read, &Purpose::Emergency));
+    assert_eq!(
+        client
+            .get_authorized_parties(&resource_id, &0, &10)
+            .parties
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_flag_overdue_review() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, patient, primary, _, _) = setup_delegation_chain(&env);
+    let officer = add_compliance_officer(&env, &client, &admin);
+
+    let id = client.break_glass(
+        &primary,
+        &patient,
+        &patient_resource(&env, &patient, "imaging"),
+        &vec![&env, Scope::Read],
+        &String::from_str(&env, "Unconscious patient in ER"),
+    );
+
+    let deadline = client.get_break_glass(&id).review_deadline;
+    assert!(client.try_flag_overdue_review(&id).is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = deadline + 1);
+    client.flag_overdue_review(&id);
+    assert_eq!(client.get_break_glass(&id).status, ReviewStatus::Overdue);
+    assert_eq!(client.get_review_queue(&0, &10).records.len(), 1);
+
+    // Overdue records can still be reviewed
+    client.review_break_glass(&officer, &id, &true);
+    assert_eq!(client.get_break_glass(&id).status, ReviewStatus::Justified);
+}
+
+#[test]
+#[should_panic(expected = "Doctor is not verified")]
+fn test_break_glass_requires_verified_doctor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, _, _, _) = setup_delegation_chain(&env);
+
+    let doctor = Address::generate(&env);
+    client.register_entity(
+        &doctor,
+        &EntityType::Doctor,
+        &String::from_str(&env, "Dr. New"),
+        &String::from_str(&env, "metadata"),
+    );
+
+    client.break_glass(
+        &doctor,
+        &patient,
+        &patient_resource(&env, &patient, "imaging"),
+        &vec![&env, Scope::Read],
+        &String::from_str(&env, "Unconscious patient in ER"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Resource does not belong to the patient")]
+fn test_break_glass_bound_to_patient_resources() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, primary, _, _) = setup_delegation_chain(&env);
+
+    let other_patient = Address::generate(&env);
+    client.break_glass(
+        &primary,
+        &patient,
+        &patient_resource(&env, &other_patient, "imaging"),
+        &vec![&env, Scope::Read],
+        &String::from_str(&env, "Unconscious patient in ER"),
+    );
+}
+
+#[test]
+fn test_break_glass_replaces_existing_grant() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, primary, _, _) = setup_delegation_chain(&env);
+
+    let resource_id = patient_resource(&env, &patient, "imaging");
+    client.grant_access(
+        &patient,
+        &primary,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: false,
+            expires_at: 100,
+        },
+    );
+    env.ledger().set_timestamp(500);
+    assert!(!client.check_access(&primary, &resource_id, &Scope::Read, &Purpose::Treatment));
+
+    client.break_glass(
+        &primary,
+        &patient,
+        &resource_id,
+        &vec![&env, Scope::Read, Scope::Write],
+        &String::from_str(&env, "Unconscious patient in ER"),
+    );
+    assert!(client.check_access(&primary, &resource_id, &Scope::Write, &Purpose::Emergency));
+    assert_eq!(
+        client
+            .get_authorized_parties(&resource_id, &0, &10)
+            .parties
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_break_glass_keeps_live_grant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, primary, _, _) = setup_delegation_chain(&env);
+
+    let resource_id = patient_resource(&env, &patient, "imaging");
+    client.grant_access(
+        &patient,
+        &primary,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &treatment_request(&env, true),
+    );
+
+    assert!(client
+        .try_break_glass(
+            &primary,
+            &patient,
+            &resource_id,
+            &vec![&env, Scope::Read, Scope::Write],
+            &String::from_str(&env, "Unconscious patient in ER"),
+        )
+        .is_err());
+    assert!(client.check_access(&primary, &resource_id, &Scope::Read, &Purpose::Treatment));
+    assert!(!client.check_access(&primary, &resource_id, &Scope::Write, &Purpose::Emergency));
+}
+
+#[test]
+#[should_panic(expected = "Break-glass access is limited to read and write")]
+fn test_break_glass_scope_limited() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, primary, _, _) = setup_delegation_chain(&env);
+
+    client.break_glass(
+        &primary,
+        &patient,
+        &patient_resource(&env, &patient, "imaging"),
+        &vec![&env, Scope::Read, Scope::Share],
+        &String::from_str(&env, "Unconscious patient in ER"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Only compliance officers can review break-glass access")]
+fn test_review_break_glass_requires_officer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, patient, primary, _, _) = setup_delegation_chain(&env);
+
+    let id = client.break_glass(
+        &primary,
+        &patient,
+        &patient_resource(&env, &patient, "imaging"),
+        &vec![&env, Scope::Read],
+        &String::from_str(&env, "Unconscious patient in ER"),
+    );
+
+    client.review_break_glass(&admin, &id, &true);
+}
+
+#[test]
+fn test_record_recovery_rekeys_patient_grants() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, patient, primary, specialist, _) = setup_delegation_chain(&env);
+
+    let registry = Address::generate(&env);
+    let id =
+        client.propose_admin_action(&admin, &AdminAction::SetIdentityRegistry(registry.clone()));
+    client.execute_proposal(&admin, &id);
+
+    let records = patient_resource(&env, &patient, "records");
+    client.grant_access(
+        &patient,
+        &primary,
+        &records,
+        &vec![&env, Scope::Read, Scope::Share],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: consent_hash(&env),
+            can_delegate: true,
+            expires_at: 0,
+        },
+    );
+    client.delegate_access(
+        &primary,
+        &specialist,
+        &records,
+        &vec![&env, Scope::Read],
+        &false,
+        &0,
+    );
+
+    let new_wallet = Address::generate(&env);
+    client.record_recovery(&patient, &new_wallet);
+    while client.migrate_recovered_grants(&patient, &1) > 0 {}
+
+    // The grant and its delegation follow the patient to the new wallet
+    let new_records = patient_resource(&env, &new_wallet, "records");
+    assert!(!client.check_access(&primary, &records, &Scope::Read, &Purpose::Treatment));
+    assert!(client.check_access(&primary, &new_records, &Scope::Read, &Purpose::Treatment));
+    assert!(client.check_access(&specialist, &new_records, &Scope::Read, &Purpose::Treatment));
+
+    // Paths outside the patient's namespace keep their id
+    let other = String::from_str(&env, "patient-123-records");
+    assert!(client.check_access(&primary, &other, &Scope::Read, &Purpose::Treatment));
+
+    // The new wallet now controls the grants and the entity record
+    assert_eq!(
+        client.get_entity(&new_wallet).entity_type,
+        EntityType::Patient
+    );
+    assert!(!client.get_entity(&patient).active);
+    client.revoke_access(&new_wallet, &primary, &new_records);
+    assert!(!client.check_access(&specialist, &new_records, &Scope::Read, &Purpose::Treatment));
+    assert_eq!(client.migrate_recovered_grants(&patient, &10), 0);
+}
+
+#[test]
+#[should_panic(expected = "Identity registry not configured")]
+fn test_record_recovery_requires_registry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, patient, _, _, _) = setup_delegation_chain(&env);
+
+    client.record_recovery(&patient, &Address::generate(&env));
+}
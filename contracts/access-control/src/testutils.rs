@@ -0,0 +1,46 @@
+//! Fixtures for testing contracts that consult an `AccessControl` deployment.
+
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+
+use crate::{AccessControl, AccessControlClient, AdminAction, EntityType};
+
+/// Deploys an `AccessControl` contract with `patient` registered and `grantee`
+/// registered as an entity of `grantee_type` holding an attested credential.
+///
+/// Auths must already be mocked on `env`.
+pub fn setup_access_policy<'a>(
+    env: &Env,
+    patient: &Address,
+    grantee: &Address,
+    grantee_type: EntityType,
+) -> (Address, AccessControlClient<'a>) {
+    let policy_id = env.register(AccessControl, ());
+    let policy = AccessControlClient::new(env, &policy_id);
+
+    let admin = Address::generate(env);
+    let attester = Address::generate(env);
+    policy.initialize(&admin);
+    let proposal = policy.propose_admin_action(&admin, &AdminAction::AddAttester(attester.clone()));
+    policy.execute_proposal(&admin, &proposal);
+
+    policy.register_entity(
+        patient,
+        &EntityType::Patient,
+        &String::from_str(env, "Jane Doe"),
+        &String::from_str(env, "metadata"),
+    );
+    policy.register_entity(
+        grantee,
+        &grantee_type,
+        &String::from_str(env, "Grantee"),
+        &String::from_str(env, "metadata"),
+    );
+    policy.attest_entity(
+        &attester,
+        grantee,
+        &BytesN::from_array(env, &[1u8; 32]),
+        &(env.ledger().timestamp() + 1_000_000),
+    );
+
+    (policy_id, policy)
+}
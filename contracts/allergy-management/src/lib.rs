@@ -43,6 +43,12 @@ pub struct AccessRevoked {
     pub provider_id: Address,
 }
 
+#[contractevent]
+pub struct AccessPolicySet {
+    pub patient_id: Address,
+    pub policy: Option<Address>,
+}
+
 /// Error codes for allergy management operations
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -88,6 +94,11 @@ impl AllergyManagement {
         validation::validate_allergen_type(&request.allergen_type)?;
         validation::validate_severity(&request.severity)?;
 
+        // Check write permission
+        if !storage::check_write_permission(&env, &patient_id, &provider_id) {
+            return Err(Error::Unauthorized);
+        }
+
         // Check for duplicate allergy
         if storage::check_duplicate_allergy(&env, &patient_id, &request.allergen, &request.allergen_type) {
             return Err(Error::DuplicateAllergy);
@@ -143,6 +154,11 @@ impl AllergyManagement {
         // Load allergy record
         let mut allergy = storage::get_allergy(&env, allergy_id)?;
 
+        // Check write permission
+        if !storage::check_write_permission(&env, &allergy.patient_id, &provider_id) {
+            return Err(Error::Unauthorized);
+        }
+
         // Check if already resolved
         if allergy.status == AllergyStatus::Resolved {
             return Err(Error::AlreadyResolved);
@@ -192,6 +208,11 @@ impl AllergyManagement {
         // Load allergy record
         let mut allergy = storage::get_allergy(&env, allergy_id)?;
 
+        // Check write permission
+        if !storage::check_write_permission(&env, &allergy.patient_id, &provider_id) {
+            return Err(Error::Unauthorized);
+        }
+
         // Check if already resolved
         if allergy.status == AllergyStatus::Resolved {
             return Err(Error::AlreadyResolved);
@@ -332,6 +353,27 @@ impl AllergyManagement {
         .publish(&env);
     }
 
+    /// Delegate access decisions for a patient's allergies to an AccessControl
+    /// contract, or clear the delegation with `None`
+    ///
+    /// While set, provider reads and writes are checked against grants on
+    /// `patient/<patient_id>/allergies` and the local grants are ignored.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        storage::set_access_policy(&env, &patient_id, &policy);
+
+        AccessPolicySet {
+            patient_id: patient_id.clone(),
+            policy,
+        }
+        .publish(&env);
+    }
+
+    /// Get the AccessControl contract governing a patient's allergies, if any
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        storage::get_access_policy(&env, &patient_id)
+    }
+
     /// Get allergy by ID (requires access)
     pub fn get_allergy(
         env: Env,
@@ -1,3 +1,4 @@
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
 use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
 use crate::{AllergyRecord, DataKey, Error};
@@ -88,6 +89,50 @@ pub fn revoke_access(env: &Env, patient_id: &Address, provider_id: &Address) {
     env.storage().persistent().remove(&key);
 }
 
+/// Set or clear the AccessControl contract that governs a patient's allergies
+pub fn set_access_policy(env: &Env, patient_id: &Address, policy: &Option<Address>) {
+    let key = DataKey::AccessPolicy(patient_id.clone());
+    match policy {
+        Some(policy) => env.storage().persistent().set(&key, policy),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get the AccessControl contract that governs a patient's allergies, if any
+pub fn get_access_policy(env: &Env, patient_id: &Address) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AccessPolicy(patient_id.clone()))
+}
+
+/// Ask the patient's AccessControl contract whether `requester` holds `scope`.
+/// Returns `None` when the patient has not opted into a policy contract.
+fn policy_allows(
+    env: &Env,
+    patient_id: &Address,
+    requester: &Address,
+    scope: Scope,
+) -> Option<bool> {
+    let policy = get_access_policy(env, patient_id)?;
+    let resource_id = patient_resource(env, patient_id, "allergies");
+    Some(AccessPolicyClient::new(env, &policy).check_access(
+        requester,
+        &resource_id,
+        &scope,
+        &Purpose::Treatment,
+    ))
+}
+
+/// Check if a provider may write to patient allergies. Without a policy
+/// contract any provider may record allergies, as before.
+pub fn check_write_permission(env: &Env, patient_id: &Address, provider_id: &Address) -> bool {
+    if patient_id == provider_id {
+        return true;
+    }
+
+    policy_allows(env, patient_id, provider_id, Scope::Write).unwrap_or(true)
+}
+
 /// Check if a requester has access to patient allergies
 pub fn check_access_permission(env: &Env, patient_id: &Address, requester: &Address) -> bool {
     // Patient always has access to their own data
@@ -101,6 +146,11 @@ pub fn check_access_permission(env: &Env, patient_id: &Address, requester: &Addr
             return true;
         }
     }
+
+    // A policy contract replaces the local grants below
+    if let Some(allowed) = policy_allows(env, patient_id, requester, Scope::Read) {
+        return allowed;
+    }
     
     // Check explicit access grant
     let key = DataKey::AccessControl(patient_id.clone(), requester.clone());
@@ -1,8 +1,14 @@
 #![cfg(test)]
 
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, String, Symbol, Vec};
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest, Purpose, Scope};
+use access_control_interface::patient_resource;
+use soroban_sdk::{
+    symbol_short, testutils::Address as _, vec, Address, BytesN, Env, String, Symbol, Vec,
+};
 
-use crate::{AllergyManagement, AllergyManagementClient, AllergyStatus, RecordAllergyRequest};
+use crate::{
+    AllergyManagement, AllergyManagementClient, AllergyStatus, Error, RecordAllergyRequest,
+};
 
 fn create_test_env() -> (Env, Address, Address, Address, AllergyManagementClient<'static>) {
     let env = Env::default();
@@ -680,3 +686,61 @@ fn test_food_allergy() {
     assert_eq!(allergy.severity, symbol_short!("critical"));
     assert_eq!(allergy.onset_date, Some(500u64));
 }
+
+#[test]
+fn test_access_policy_contract() {
+    let (env, _, patient, provider, client) = create_test_env();
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &provider, EntityType::Doctor);
+
+    client.set_access_policy(&patient, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient), Some(policy_id));
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "rash"));
+
+    let request = create_allergy_request(
+        &env,
+        "Penicillin",
+        symbol_short!("med"),
+        reactions,
+        symbol_short!("mild"),
+        None,
+        true,
+    );
+
+    // Local grants are ignored once the patient opts into the policy contract
+    client.grant_access(&patient, &provider);
+    assert_eq!(
+        client.try_record_allergy(&patient, &provider, &request),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    let resource_id = patient_resource(&env, &patient, "allergies");
+    policy.grant_access(
+        &patient,
+        &provider,
+        &resource_id,
+        &vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+
+    let allergy_id = client.record_allergy(&patient, &provider, &request);
+    let allergy = client.get_allergy(&allergy_id, &provider);
+    assert_eq!(allergy.patient_id, patient);
+
+    // Revoking the grant in access-control blocks the downstream read
+    policy.revoke_access(&patient, &provider, &resource_id);
+    assert_eq!(
+        client.try_get_allergy(&allergy_id, &provider).err(),
+        Some(Ok(Error::AccessDenied))
+    );
+
+    // Clearing the policy falls back to the local grants
+    client.set_access_policy(&patient, &None);
+    client.get_allergy(&allergy_id, &provider);
+}
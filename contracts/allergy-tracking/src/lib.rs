@@ -1,5 +1,6 @@
 #![no_std]
 
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, String,
     Symbol, Vec,
@@ -102,6 +103,7 @@ pub enum DataKey {
     PatientAllergies(Address),
     SeverityHistory(u64),
     DrugCrossSensitivity(String),
+    AccessPolicy(Address), // patient -> AccessControl contract
 }
 
 // Validation constants
@@ -110,11 +112,53 @@ const MIN_ALLERGEN_LENGTH: u32 = 1;
 const MAX_REASON_LENGTH: u32 = 500;
 const MAX_REACTION_LENGTH: u32 = 200;
 
+/// Ask the patient's AccessControl contract whether `requester` holds `scope`
+/// on their allergies. Returns `None` when the patient has not set a policy.
+fn policy_allows(
+    env: &Env,
+    patient_id: &Address,
+    requester: &Address,
+    scope: Scope,
+) -> Option<bool> {
+    if patient_id == requester {
+        return Some(true);
+    }
+    let policy: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AccessPolicy(patient_id.clone()))?;
+    Some(AccessPolicyClient::new(env, &policy).check_access(
+        requester,
+        &patient_resource(env, patient_id, "allergies"),
+        &scope,
+        &Purpose::Treatment,
+    ))
+}
+
 #[contract]
 pub struct AllergyTrackingContract;
 
 #[contractimpl]
 impl AllergyTrackingContract {
+    /// Delegate access decisions for a patient's allergies to an AccessControl
+    /// contract, or clear the delegation with `None`. While set, providers need
+    /// a grant on `patient/<patient>/allergies` to record, update or read them.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        let key = DataKey::AccessPolicy(patient_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Get the AccessControl contract governing a patient's allergies, if any
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccessPolicy(patient_id))
+    }
+
     /// Record a new allergy for a patient
     pub fn record_allergy(
         env: Env,
@@ -128,6 +172,9 @@ impl AllergyTrackingContract {
         verified: bool,
     ) -> Result<u64, Error> {
         provider_id.require_auth();
+        if policy_allows(&env, &patient_id, &provider_id, Scope::Write) == Some(false) {
+            return Err(Error::Unauthorized);
+        }
 
         // Validate allergen name
         Self::validate_allergen(&allergen)?;
@@ -241,6 +288,9 @@ impl AllergyTrackingContract {
             .persistent()
             .get(&allergy_key)
             .ok_or(Error::AllergyNotFound)?;
+        if policy_allows(&env, &allergy.patient_id, &provider_id, Scope::Write) == Some(false) {
+            return Err(Error::Unauthorized);
+        }
 
         if allergy.status == AllergyStatus::Resolved {
             return Err(Error::AlreadyResolved);
@@ -307,6 +357,9 @@ impl AllergyTrackingContract {
             .persistent()
             .get(&allergy_key)
             .ok_or(Error::AllergyNotFound)?;
+        if policy_allows(&env, &allergy.patient_id, &provider_id, Scope::Write) == Some(false) {
+            return Err(Error::Unauthorized);
+        }
 
         if allergy.status == AllergyStatus::Resolved {
             return Err(Error::AlreadyResolved);
@@ -392,6 +445,9 @@ impl AllergyTrackingContract {
         requester: Address,
     ) -> Result<Vec<AllergyRecord>, Error> {
         requester.require_auth();
+        if policy_allows(&env, &patient_id, &requester, Scope::Read) == Some(false) {
+            return Err(Error::Unauthorized);
+        }
 
         let patient_key = DataKey::PatientAllergies(patient_id);
         let patient_allergies: Vec<u64> = env
@@ -1,9 +1,10 @@
 #![cfg(test)]
 
 use super::*;
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest};
 use soroban_sdk::{
     testutils::{Address as _, Ledger as _},
-    Address, Env, String, Symbol, Vec,
+    vec, Address, BytesN, Env, String, Symbol, Vec,
 };
 
 fn create_test_env() -> (Env, Address, Address, Address, Address) {
@@ -692,3 +693,49 @@ fn test_valid_allergen_length_accepted() {
     );
     assert_eq!(allergy_id2, 1);
 }
+
+#[test]
+fn test_access_policy_governs_allergies() {
+    let (env, contract_id, patient, provider, _) = create_test_env();
+    let client = AllergyTrackingContractClient::new(&env, &contract_id);
+
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &provider, EntityType::Doctor);
+    client.set_access_policy(&patient, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient), Some(policy_id));
+
+    let reactions = vec![&env, String::from_str(&env, "rash")];
+    let record = |client: &AllergyTrackingContractClient| {
+        client.try_record_allergy(
+            &patient,
+            &provider,
+            &String::from_str(&env, "Penicillin"),
+            &Symbol::new(&env, "medication"),
+            &reactions,
+            &Symbol::new(&env, "moderate"),
+            &Some(1000u64),
+            &true,
+        )
+    };
+    assert!(record(&client).is_err());
+
+    let resource_id = patient_resource(&env, &patient, "allergies");
+    policy.grant_access(
+        &patient,
+        &provider,
+        &resource_id,
+        &vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    assert!(record(&client).is_ok());
+    assert_eq!(client.get_active_allergies(&patient, &provider).len(), 1);
+
+    // Revoking the grant in access-control blocks the downstream read
+    policy.revoke_access(&patient, &provider, &resource_id);
+    assert!(client.try_get_active_allergies(&patient, &provider).is_err());
+    assert_eq!(client.get_active_allergies(&patient, &patient).len(), 1);
+}
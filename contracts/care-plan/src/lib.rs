@@ -6,15 +6,67 @@ mod types;
 #[cfg(test)]
 mod test;
 
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
 use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
 use storage::*;
 use types::*;
 
+/// Fails unless `caller` is the patient or, when the patient has set an
+/// AccessControl contract, holds `scope` on their care plans.
+fn require_policy_access(
+    env: &Env,
+    patient_id: &Address,
+    caller: &Address,
+    scope: Scope,
+) -> Result<(), Error> {
+    if patient_id == caller {
+        return Ok(());
+    }
+    let Some(policy) = get_access_policy(env, patient_id) else {
+        return Ok(());
+    };
+    if !AccessPolicyClient::new(env, &policy).check_access(
+        caller,
+        &patient_resource(env, patient_id, "care-plans"),
+        &scope,
+        &Purpose::Treatment,
+    ) {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Loads a care plan once `caller` has passed the patient's access policy.
+fn load_plan_for(
+    env: &Env,
+    care_plan_id: u64,
+    caller: &Address,
+    scope: Scope,
+) -> Result<CarePlan, Error> {
+    let plan = load_care_plan(env, care_plan_id).ok_or(Error::CarePlanNotFound)?;
+    require_policy_access(env, &plan.patient_id, caller, scope)?;
+    Ok(plan)
+}
+
 #[contract]
 pub struct CarePlanContract;
 
 #[contractimpl]
 impl CarePlanContract {
+    /// Delegate access decisions for a patient's care plans to an
+    /// AccessControl contract, or clear the delegation with `None`. While set,
+    /// providers need a grant on `patient/<patient>/care-plans` to change or
+    /// read them.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        storage::set_access_policy(&env, &patient_id, &policy);
+    }
+
+    /// Get the AccessControl contract governing a patient's care plans, if any.
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        storage::get_access_policy(&env, &patient_id)
+    }
+
     /// Create a new care plan for a patient.
     pub fn create_care_plan(
         env: Env,
@@ -27,6 +79,7 @@ impl CarePlanContract {
         review_frequency_days: u32,
     ) -> Result<u64, Error> {
         provider_id.require_auth();
+        require_policy_access(&env, &patient_id, &provider_id, Scope::Write)?;
 
         let care_plan_id = next_care_plan_id(&env);
         let next_review_date = start_date + (review_frequency_days as u64 * 86_400);
@@ -68,10 +121,7 @@ impl CarePlanContract {
         priority: Symbol,
     ) -> Result<u64, Error> {
         provider_id.require_auth();
-
-        if load_care_plan(&env, care_plan_id).is_none() {
-            return Err(Error::CarePlanNotFound);
-        }
+        load_plan_for(&env, care_plan_id, &provider_id, Scope::Write)?;
 
         let goal_id = next_goal_id(&env);
 
@@ -112,10 +162,7 @@ impl CarePlanContract {
         responsible_party: Symbol,
     ) -> Result<u64, Error> {
         provider_id.require_auth();
-
-        if load_care_plan(&env, care_plan_id).is_none() {
-            return Err(Error::CarePlanNotFound);
-        }
+        load_plan_for(&env, care_plan_id, &provider_id, Scope::Write)?;
 
         let intervention_id = next_intervention_id(&env);
 
@@ -153,6 +200,7 @@ impl CarePlanContract {
         patient_id.require_auth();
 
         let mut goal = load_goal(&env, goal_id).ok_or(Error::GoalNotFound)?;
+        load_plan_for(&env, goal.care_plan_id, &patient_id, Scope::Write)?;
 
         if matches!(goal.status, GoalStatus::Achieved) {
             return Err(Error::GoalAlreadyAchieved);
@@ -191,6 +239,7 @@ impl CarePlanContract {
         provider_id.require_auth();
 
         let mut goal = load_goal(&env, goal_id).ok_or(Error::GoalNotFound)?;
+        load_plan_for(&env, goal.care_plan_id, &provider_id, Scope::Write)?;
 
         if matches!(goal.status, GoalStatus::Achieved) {
             return Err(Error::GoalAlreadyAchieved);
@@ -223,10 +272,7 @@ impl CarePlanContract {
         identified_date: u64,
     ) -> Result<u64, Error> {
         reporter.require_auth();
-
-        if load_care_plan(&env, care_plan_id).is_none() {
-            return Err(Error::CarePlanNotFound);
-        }
+        load_plan_for(&env, care_plan_id, &reporter, Scope::Write)?;
 
         let barrier_id = next_barrier_id(&env);
 
@@ -265,6 +311,7 @@ impl CarePlanContract {
         provider_id.require_auth();
 
         let mut barrier = load_barrier(&env, barrier_id).ok_or(Error::BarrierNotFound)?;
+        load_plan_for(&env, barrier.care_plan_id, &provider_id, Scope::Write)?;
 
         if barrier.resolved {
             return Err(Error::BarrierAlreadyResolved);
@@ -294,10 +341,7 @@ impl CarePlanContract {
         review_type: Symbol,
     ) -> Result<u64, Error> {
         provider_id.require_auth();
-
-        if load_care_plan(&env, care_plan_id).is_none() {
-            return Err(Error::CarePlanNotFound);
-        }
+        load_plan_for(&env, care_plan_id, &provider_id, Scope::Write)?;
 
         let review_id = next_review_id(&env);
 
@@ -338,6 +382,7 @@ impl CarePlanContract {
         provider_id.require_auth();
 
         let mut review = load_review(&env, review_id).ok_or(Error::ReviewNotFound)?;
+        load_plan_for(&env, review.care_plan_id, &provider_id, Scope::Write)?;
 
         if review.conducted {
             return Err(Error::ReviewAlreadyConducted);
@@ -385,10 +430,7 @@ impl CarePlanContract {
         responsibilities: Vec<String>,
     ) -> Result<(), Error> {
         coordinating_provider.require_auth();
-
-        if load_care_plan(&env, care_plan_id).is_none() {
-            return Err(Error::CarePlanNotFound);
-        }
+        load_plan_for(&env, care_plan_id, &coordinating_provider, Scope::Write)?;
 
         let mut team = load_care_team(&env, care_plan_id);
 
@@ -420,7 +462,7 @@ impl CarePlanContract {
     ) -> Result<CarePlanSummary, Error> {
         requester.require_auth();
 
-        let plan = load_care_plan(&env, care_plan_id).ok_or(Error::CarePlanNotFound)?;
+        let plan = load_plan_for(&env, care_plan_id, &requester, Scope::Read)?;
 
         // Collect active goals
         let goal_ids = load_plan_goals(&env, care_plan_id);
@@ -257,4 +257,22 @@ pub fn save_care_team(env: &Env, care_plan_id: u64, team: &Vec<CareTeamMember>)
     env.storage()
         .persistent()
         .set(&DataKey::PlanCareTeam(care_plan_id), team);
-}
\ No newline at end of file
+}
+
+// -----------------------------------------------------------------------
+// Access policy
+// -----------------------------------------------------------------------
+
+pub fn set_access_policy(env: &Env, patient_id: &Address, policy: &Option<Address>) {
+    let key = DataKey::AccessPolicy(patient_id.clone());
+    match policy {
+        Some(policy) => env.storage().persistent().set(&key, policy),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_access_policy(env: &Env, patient_id: &Address) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AccessPolicy(patient_id.clone()))
+}
@@ -1,6 +1,7 @@
 #![cfg(test)]
 
 use super::*;
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest};
 use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Symbol, Vec};
 
 // -----------------------------------------------------------------------
@@ -1149,4 +1150,52 @@ fn test_full_care_plan_workflow() {
     assert_eq!(summary.barriers.len(), 1);
     assert!(summary.barriers.get(0).unwrap().resolved);
     assert!(summary.last_review_date.is_some());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_access_policy_governs_care_plans() {
+    let (env, provider, patient) = setup();
+    let contract_id = env.register(CarePlanContract, ());
+    let client = CarePlanContractClient::new(&env, &contract_id);
+
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &provider, EntityType::Doctor);
+    client.set_access_policy(&patient, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient), Some(policy_id));
+
+    let conditions = soroban_sdk::vec![&env, String::from_str(&env, "Type 2 Diabetes")];
+    let plan_type = Symbol::new(&env, "chronic_disease");
+    let goals = Vec::new(&env);
+    let create = || {
+        client.try_create_care_plan(
+            &patient,
+            &provider,
+            &plan_type,
+            &conditions,
+            &goals,
+            &1_000_000u64,
+            &30u32,
+        )
+    };
+    assert!(create().is_err());
+
+    let resource_id = patient_resource(&env, &patient, "care-plans");
+    policy.grant_access(
+        &patient,
+        &provider,
+        &resource_id,
+        &soroban_sdk::vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    let plan_id = create().unwrap().unwrap();
+    assert_eq!(client.get_care_plan_summary(&plan_id, &provider).patient_id, patient);
+
+    // Revoking the grant in access-control blocks the downstream read
+    policy.revoke_access(&patient, &provider, &resource_id);
+    assert!(client.try_get_care_plan_summary(&plan_id, &provider).is_err());
+    assert_eq!(client.get_care_plan_summary(&plan_id, &patient).patient_id, patient);
+}
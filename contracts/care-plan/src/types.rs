@@ -222,4 +222,6 @@ pub enum DataKey {
     PlanCareTeam(u64),
     /// patient_id -> Vec<u64> (care plan ids)
     PatientPlans(Address),
+    /// patient_id -> AccessControl contract governing their care plans
+    AccessPolicy(Address),
 }
\ No newline at end of file
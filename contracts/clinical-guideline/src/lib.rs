@@ -1,4 +1,5 @@
 #![no_std]
+use access_control_interface::{AccessPolicyClient, Purpose, Scope, patient_resource};
 use soroban_sdk::{
     Address, BytesN, Env, String, Symbol, Vec, contract, contracterror, contractimpl, contracttype,
 };
@@ -45,11 +46,49 @@ pub struct CarePathway {
     pub steps: Vec<String>,
 }
 
+#[contracttype]
+pub enum DataKey {
+    AccessPolicy(Address), // patient -> AccessControl contract
+}
+
+/// Ask the patient's AccessControl contract whether `provider` may write
+/// reminders for them. Returns `None` when the patient has not set a policy.
+fn policy_allows(env: &Env, patient_id: &Address, provider: &Address) -> Option<bool> {
+    let policy: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AccessPolicy(patient_id.clone()))?;
+    Some(AccessPolicyClient::new(env, &policy).check_access(
+        provider,
+        &patient_resource(env, patient_id, "reminders"),
+        &Scope::Write,
+        &Purpose::Treatment,
+    ))
+}
+
 #[contract]
 pub struct ClinicalGuidelineContract;
 
 #[contractimpl]
 impl ClinicalGuidelineContract {
+    /// Delegate access decisions for a patient's reminders to an AccessControl
+    /// contract, or clear the delegation with `None`. While set, providers need
+    /// a write grant on `patient/<patient>/reminders` to create reminders.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        let key = DataKey::AccessPolicy(patient_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccessPolicy(patient_id))
+    }
+
     pub fn register_clinical_guideline(
         env: Env,
         admin: Address,
@@ -145,11 +184,15 @@ impl ClinicalGuidelineContract {
     pub fn create_reminder(
         env: Env,
         patient_id: Address,
-        _provider_id: Address,
+        provider_id: Address,
         _reminder_type: Symbol,
         due_date: u64,
         _priority: Symbol,
     ) -> Result<u64, Error> {
+        provider_id.require_auth();
+        if !policy_allows(&env, &patient_id, &provider_id).unwrap_or(true) {
+            return Err(Error::NotAuthorized);
+        }
         // Use ledger timestamp + patient address hash as a simple ID
         let reminder_id = env.ledger().timestamp();
         env.storage().temporary().set(&patient_id, &due_date);
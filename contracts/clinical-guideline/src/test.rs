@@ -1,6 +1,7 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec, testutils::Address as _};
+use access_control::{EntityType, GrantRequest, testutils::setup_access_policy};
+use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec, testutils::Address as _, vec};
 
 #[test]
 fn test_register_and_evaluate_guideline() {
@@ -100,3 +101,47 @@ fn test_unauthorized_registration() {
         &Symbol::new(&env, "B"),
     );
 }
+
+#[test]
+fn test_access_policy_gates_reminders() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, ClinicalGuidelineContract);
+    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &provider, EntityType::Doctor);
+    client.set_access_policy(&patient, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient), Some(policy_id));
+
+    let remind = |client: &ClinicalGuidelineContractClient| {
+        client.try_create_reminder(
+            &patient,
+            &provider,
+            &Symbol::new(&env, "Screening"),
+            &1_700_000_000,
+            &Symbol::new(&env, "High"),
+        )
+    };
+    assert_eq!(remind(&client), Err(Ok(Error::NotAuthorized)));
+
+    let resource_id = patient_resource(&env, &patient, "reminders");
+    policy.grant_access(
+        &patient,
+        &provider,
+        &resource_id,
+        &vec![&env, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    assert!(remind(&client).is_ok());
+
+    policy.revoke_access(&patient, &provider, &resource_id);
+    assert_eq!(remind(&client), Err(Ok(Error::NotAuthorized)));
+}
@@ -0,0 +1,58 @@
+#![no_std]
+
+//! Client interface for consulting the `DoctorRegistry` from other contracts.
+//!
+//! The doctor registry is the source of truth for doctor profiles and their
+//! institution affiliations. Contracts that act on a doctor's behalf store
+//! the address of a deployed registry and query it through
+//! [`DoctorDirectoryClient`].
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, String};
+
+/// --------------------
+/// Doctor Profiles
+/// --------------------
+/// Stored by the registry, which re-exports this type.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DoctorProfileData {
+    pub name: String,
+    pub specialization: String,
+    pub metadata: String,
+    pub accepting_new_patients: bool,
+    pub telehealth: bool, // Offers telemedicine visits
+}
+
+/// --------------------
+/// Clinical Privileges
+/// --------------------
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Privilege {
+    Admit,
+    Prescribe,
+    Surgery,
+    OrderTests,
+    Telehealth,
+}
+
+/// The subset of the `DoctorRegistry` contract other contracts rely on.
+#[contractclient(name = "DoctorDirectoryClient")]
+pub trait DoctorDirectory {
+    fn has_doctor_profile(env: Env, wallet: Address) -> bool;
+
+    /// Panics if the doctor has no profile.
+    fn get_doctor_profile(env: Env, wallet: Address) -> DoctorProfileData;
+
+    /// Returns `true` if the doctor holds an affiliation with `hospital`
+    /// that both sides confirmed and neither has ended.
+    fn is_affiliated(env: Env, doctor: Address, hospital: Address) -> bool;
+
+    /// Returns `true` if the doctor's active affiliation with `hospital`
+    /// includes `privilege`.
+    fn has_privilege(env: Env, doctor: Address, hospital: Address, privilege: Privilege) -> bool;
+
+    /// Returns `true` if the doctor holds a license, directly or through a
+    /// licensure compact, covering a patient in `jurisdiction` at time `at`.
+    fn can_practice_in(env: Env, doctor: Address, jurisdiction: String, at: u64) -> bool;
+}
@@ -1,17 +1,114 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
+use pagination::page_limit;
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
+    String, Vec,
+};
+
+pub use doctor_registry_interface::{DoctorProfileData, Privilege};
+pub use pagination::MAX_PAGE_SIZE;
+
+/// --------------------
+/// Licensure
+/// --------------------
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Compact {
+    Imlc,    // Interstate Medical Licensure Compact
+    PsyPact, // Psychology Interjurisdictional Compact
+    Nlc,     // Nurse Licensure Compact
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompactMembership {
+    pub jurisdictions: Vec<String>,
+    // Whether a member license lets the holder practice in every member
+    // jurisdiction. False for pathways such as the IMLC, which only expedite
+    // getting a separate license in each jurisdiction.
+    pub grants_privilege: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LicenseRequest {
+    pub jurisdiction: String, // e.g. "NY"
+    pub license_number_hash: BytesN<32>,
+    pub issued_at: u64,
+    pub expires_at: u64,
+    pub compacts: Vec<Compact>, // Compacts the license participates in
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct License {
+    pub jurisdiction: String,
+    pub license_number_hash: BytesN<32>,
+    pub issued_at: u64,
+    pub expires_at: u64,
+    pub compacts: Vec<Compact>,
+    pub verified_by: Address,
+    pub revoked_at: Option<u64>,
+}
 
 /// --------------------
-/// Doctor Structures
+/// Directory Search
 /// --------------------
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct DoctorProfileData {
-    pub name: String,
-    pub specialization: String,
-    pub institution_wallet: Address,
-    pub metadata: String,
+pub struct DoctorSearchFilter {
+    pub specialization: Option<String>, // Exact match
+    pub institution: Option<Address>,   // Active affiliation required
+    pub accepting_new_patients: bool,   // `true` keeps only doctors taking new patients
+    pub telehealth: bool,               // `true` keeps only telehealth-capable doctors
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DoctorSearchResult {
+    pub wallet: Address,
+    pub profile: DoctorProfileData,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DoctorSearchPage {
+    pub doctors: Vec<DoctorSearchResult>,
+    pub next_cursor: Option<u32>, // None once the index is exhausted
+}
+
+/// --------------------
+/// Affiliation Structures
+/// --------------------
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AffiliationRole {
+    Attending,
+    Resident,
+    Consultant,
+    Visiting,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AffiliationStatus {
+    Pending, // Requested by the doctor, awaiting the hospital
+    Active,
+    Ended,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Affiliation {
+    pub doctor: Address,
+    pub hospital: Address,
+    pub role: AffiliationRole,
+    pub privileges: Vec<Privilege>,
+    pub status: AffiliationStatus,
+    pub requested_at: u64,
+    pub confirmed_at: u64, // 0 until the hospital confirms
+    pub ended_at: u64,     // 0 while pending or active
 }
 
 /// --------------------
@@ -20,6 +117,174 @@ pub struct DoctorProfileData {
 #[contracttype]
 pub enum DataKey {
     Doctor(Address),
+    Admin,
+    HospitalRegistry,
+    Affiliation(Address, Address), // (Doctor, Hospital) -> Affiliation
+    DoctorAffiliations(Address),   // Doctor -> Vec<Address> of hospitals
+    IndexLen(IndexKey),            // List -> u32
+    IndexItem(IndexKey, u32),      // (List, Position) -> Address
+    IndexPos(IndexKey, Address),   // (List, Doctor) -> u32
+    LicenseVerifier(Address),      // Licensing board -> Vec<String> of jurisdictions it covers
+    Licenses(Address),             // Doctor -> Vec<License>, one per jurisdiction
+    CompactMembership(Compact),    // Compact -> CompactMembership
+}
+
+/// Doctor lists stored one entry per item
+#[contracttype]
+#[derive(Clone)]
+pub enum IndexKey {
+    Hospital(Address), // Doctors with an active affiliation at a hospital
+    All,               // Every profile
+    Specialty(String), // Profiles by specialization
+}
+
+/// The subset of `hospital-registry` this contract relies on.
+#[contractclient(name = "HospitalRegistryClient")]
+pub trait HospitalRegistry {
+    fn is_hospital_registered(env: Env, wallet: Address) -> bool;
+}
+
+fn load_affiliation(env: &Env, doctor: &Address, hospital: &Address) -> Option<Affiliation> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Affiliation(doctor.clone(), hospital.clone()))
+}
+
+fn save_affiliation(env: &Env, affiliation: &Affiliation) {
+    let key = DataKey::Affiliation(affiliation.doctor.clone(), affiliation.hospital.clone());
+    env.storage().persistent().set(&key, affiliation);
+}
+
+fn load_list(env: &Env, key: &DataKey) -> Vec<Address> {
+    env.storage().persistent().get(key).unwrap_or(Vec::new(env))
+}
+
+fn add_to_list(env: &Env, key: &DataKey, wallet: &Address) {
+    let mut list = load_list(env, key);
+    if !list.contains(wallet) {
+        list.push_back(wallet.clone());
+        env.storage().persistent().set(key, &list);
+    }
+}
+
+// Each index is a count plus one ledger entry per doctor, with a reverse
+// position entry so removal swaps in the last doctor instead of rewriting
+// the whole list.
+
+fn index_len(env: &Env, list: &IndexKey) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IndexLen(list.clone()))
+        .unwrap_or(0)
+}
+
+fn index_get(env: &Env, list: &IndexKey, position: u32) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IndexItem(list.clone(), position))
+}
+
+fn index_push(env: &Env, list: &IndexKey, wallet: &Address) {
+    let pos_key = DataKey::IndexPos(list.clone(), wallet.clone());
+    if env.storage().persistent().has(&pos_key) {
+        return;
+    }
+
+    let len = index_len(env, list);
+    env.storage()
+        .persistent()
+        .set(&DataKey::IndexItem(list.clone(), len), wallet);
+    env.storage().persistent().set(&pos_key, &len);
+    env.storage()
+        .persistent()
+        .set(&DataKey::IndexLen(list.clone()), &(len + 1));
+}
+
+fn index_remove(env: &Env, list: &IndexKey, wallet: &Address) {
+    let pos_key = DataKey::IndexPos(list.clone(), wallet.clone());
+    let Some(position) = env.storage().persistent().get::<_, u32>(&pos_key) else {
+        return;
+    };
+
+    let last = index_len(env, list) - 1;
+    if position != last {
+        let moved = index_get(env, list, last).expect("Index corrupted");
+        env.storage()
+            .persistent()
+            .set(&DataKey::IndexItem(list.clone(), position), &moved);
+        env.storage()
+            .persistent()
+            .set(&DataKey::IndexPos(list.clone(), moved), &position);
+    }
+    env.storage()
+        .persistent()
+        .remove(&DataKey::IndexItem(list.clone(), last));
+    env.storage().persistent().remove(&pos_key);
+    env.storage()
+        .persistent()
+        .set(&DataKey::IndexLen(list.clone()), &last);
+}
+
+/// Positions `cursor..cursor + limit` of an index, and where the next page
+/// starts if any remain.
+fn index_page(env: &Env, list: &IndexKey, cursor: u32, limit: u32) -> (Vec<Address>, Option<u32>) {
+    let len = index_len(env, list);
+    let end = cursor.saturating_add(page_limit(limit)).min(len);
+
+    let mut wallets = Vec::new(env);
+    for position in cursor..end {
+        if let Some(wallet) = index_get(env, list, position) {
+            wallets.push_back(wallet);
+        }
+    }
+    (wallets, if end < len { Some(end) } else { None })
+}
+
+fn require_admin(env: &Env, admin: &Address) {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .expect("Contract not initialized");
+    if stored != *admin {
+        panic!("Unauthorized");
+    }
+}
+
+/// Panics unless `verifier` is a licensing board authorised for `jurisdiction`
+fn require_license_verifier(env: &Env, verifier: &Address, jurisdiction: &String) {
+    verifier.require_auth();
+    let jurisdictions: Vec<String> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::LicenseVerifier(verifier.clone()))
+        .expect("Not a license verifier");
+    if !jurisdictions.contains(jurisdiction) {
+        panic!("Verifier not authorized for this jurisdiction");
+    }
+}
+
+fn load_licenses(env: &Env, doctor: &Address) -> Vec<License> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Licenses(doctor.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn compact_membership(env: &Env, compact: Compact) -> CompactMembership {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CompactMembership(compact))
+        .unwrap_or(CompactMembership {
+            jurisdictions: Vec::new(env),
+            grants_privilege: false,
+        })
+}
+
+fn active_affiliation(env: &Env, doctor: &Address, hospital: &Address) -> Option<Affiliation> {
+    load_affiliation(env, doctor, hospital)
+        .filter(|affiliation| affiliation.status == AffiliationStatus::Active)
 }
 
 #[contract]
@@ -27,20 +292,30 @@ pub struct DoctorRegistry;
 
 #[contractimpl]
 impl DoctorRegistry {
-    /// Create a new doctor profile with basic information and institution association
+    /// Initialize the contract with an admin and the hospital registry that
+    /// affiliations are checked against
+    pub fn initialize(env: Env, admin: Address, hospital_registry: Address) {
+        admin.require_auth();
+
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Contract already initialized");
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::HospitalRegistry, &hospital_registry);
+    }
+
+    /// Create a new doctor profile with basic information
+    ///
+    /// Institutions are linked through `request_affiliation`.
     ///
     /// # Arguments
     /// * `wallet` - The wallet address of the doctor
     /// * `name` - The name of the doctor
     /// * `specialization` - The area of specialization
-    /// * `institution_wallet` - The wallet address of the associated hospital/clinic
-    pub fn create_doctor_profile(
-        env: Env,
-        wallet: Address,
-        name: String,
-        specialization: String,
-        institution_wallet: Address,
-    ) {
+    pub fn create_doctor_profile(env: Env, wallet: Address, name: String, specialization: String) {
         wallet.require_auth();
 
         let key = DataKey::Doctor(wallet.clone());
@@ -48,11 +323,15 @@ impl DoctorRegistry {
             panic!("Doctor profile already exists");
         }
 
+        index_push(&env, &IndexKey::All, &wallet);
+        index_push(&env, &IndexKey::Specialty(specialization.clone()), &wallet);
+
         let doctor_profile = DoctorProfileData {
             name,
             specialization,
-            institution_wallet,
             metadata: String::from_str(&env, ""),
+            accepting_new_patients: false,
+            telehealth: false,
         };
 
         env.storage().persistent().set(&key, &doctor_profile);
@@ -82,6 +361,16 @@ impl DoctorRegistry {
             .get(&key)
             .expect("Doctor profile not found");
 
+        // Keep the specialty index in step with the profile
+        if doctor_profile.specialization != specialization {
+            index_remove(
+                &env,
+                &IndexKey::Specialty(doctor_profile.specialization.clone()),
+                &wallet,
+            );
+            index_push(&env, &IndexKey::Specialty(specialization.clone()), &wallet);
+        }
+
         doctor_profile.specialization = specialization;
         doctor_profile.metadata = metadata;
         env.storage().persistent().set(&key, &doctor_profile);
@@ -90,6 +379,90 @@ impl DoctorRegistry {
             .publish((symbol_short!("upd_doc"), wallet), symbol_short!("success"));
     }
 
+    /// Update the availability flags shown in directory searches
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the doctor
+    /// * `accepting_new_patients` - Whether the doctor takes new patients
+    /// * `telehealth` - Whether the doctor offers telemedicine visits
+    pub fn set_availability(
+        env: Env,
+        wallet: Address,
+        accepting_new_patients: bool,
+        telehealth: bool,
+    ) {
+        wallet.require_auth();
+
+        let key = DataKey::Doctor(wallet.clone());
+        let mut doctor_profile: DoctorProfileData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Doctor profile not found");
+
+        doctor_profile.accepting_new_patients = accepting_new_patients;
+        doctor_profile.telehealth = telehealth;
+        env.storage().persistent().set(&key, &doctor_profile);
+
+        env.events().publish(
+            (symbol_short!("avail_doc"), wallet),
+            (accepting_new_patients, telehealth),
+        );
+    }
+
+    /// Search the provider directory
+    ///
+    /// Walks the most selective index for the filter: the institution's
+    /// doctors, then the specialty index, then every profile. A page covers
+    /// `limit` index entries, so it may hold fewer matches than `limit`
+    /// while `next_cursor` is still set.
+    ///
+    /// # Arguments
+    /// * `filter` - Criteria every returned doctor matches
+    /// * `cursor` - Index position to resume from, 0 for the first page
+    /// * `limit` - Number of index entries to scan, at most `MAX_PAGE_SIZE`
+    pub fn search_doctors(
+        env: Env,
+        filter: DoctorSearchFilter,
+        cursor: u32,
+        limit: u32,
+    ) -> DoctorSearchPage {
+        let index = match (&filter.institution, &filter.specialization) {
+            (Some(institution), _) => IndexKey::Hospital(institution.clone()),
+            (None, Some(specialization)) => IndexKey::Specialty(specialization.clone()),
+            (None, None) => IndexKey::All,
+        };
+        let (wallets, next_cursor) = index_page(&env, &index, cursor, limit);
+
+        let mut doctors = Vec::new(&env);
+        for wallet in wallets.iter() {
+            let profile: DoctorProfileData = match env
+                .storage()
+                .persistent()
+                .get(&DataKey::Doctor(wallet.clone()))
+            {
+                Some(profile) => profile,
+                None => continue,
+            };
+
+            if filter
+                .specialization
+                .as_ref()
+                .is_some_and(|specialization| profile.specialization != *specialization)
+                || (filter.accepting_new_patients && !profile.accepting_new_patients)
+                || (filter.telehealth && !profile.telehealth)
+            {
+                continue;
+            }
+            doctors.push_back(DoctorSearchResult { wallet, profile });
+        }
+
+        DoctorSearchPage {
+            doctors,
+            next_cursor,
+        }
+    }
+
     /// Retrieve doctor profile data by wallet address
     ///
     /// # Arguments
@@ -104,6 +477,353 @@ impl DoctorRegistry {
             .get(&key)
             .expect("Doctor profile not found")
     }
+
+    /// Check whether a wallet has a doctor profile
+    pub fn has_doctor_profile(env: Env, wallet: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Doctor(wallet))
+    }
+
+    /// Ask a hospital to confirm an affiliation
+    ///
+    /// The affiliation only takes effect once the hospital confirms it with
+    /// `confirm_affiliation`. An ended affiliation can be requested again.
+    ///
+    /// # Arguments
+    /// * `doctor` - The wallet address of the doctor
+    /// * `hospital` - The wallet address of a hospital in the hospital registry
+    /// * `role` - The doctor's role at the hospital
+    /// * `privileges` - The clinical privileges the doctor asks for
+    pub fn request_affiliation(
+        env: Env,
+        doctor: Address,
+        hospital: Address,
+        role: AffiliationRole,
+        privileges: Vec<Privilege>,
+    ) {
+        doctor.require_auth();
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Doctor(doctor.clone()))
+        {
+            panic!("Doctor profile not found");
+        }
+
+        let hospital_registry: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::HospitalRegistry)
+            .expect("Contract not initialized");
+        if !HospitalRegistryClient::new(&env, &hospital_registry).is_hospital_registered(&hospital)
+        {
+            panic!("Hospital not registered");
+        }
+
+        if let Some(existing) = load_affiliation(&env, &doctor, &hospital) {
+            if existing.status != AffiliationStatus::Ended {
+                panic!("Affiliation already exists");
+            }
+        }
+
+        let affiliation = Affiliation {
+            doctor: doctor.clone(),
+            hospital: hospital.clone(),
+            role,
+            privileges,
+            status: AffiliationStatus::Pending,
+            requested_at: env.ledger().timestamp(),
+            confirmed_at: 0,
+            ended_at: 0,
+        };
+        save_affiliation(&env, &affiliation);
+        add_to_list(
+            &env,
+            &DataKey::DoctorAffiliations(doctor.clone()),
+            &hospital,
+        );
+
+        env.events()
+            .publish((symbol_short!("aff_req"), doctor, hospital), role);
+    }
+
+    /// Confirm a doctor's pending affiliation request
+    ///
+    /// # Arguments
+    /// * `hospital` - The wallet address of the hospital
+    /// * `doctor` - The wallet address of the requesting doctor
+    pub fn confirm_affiliation(env: Env, hospital: Address, doctor: Address) {
+        hospital.require_auth();
+
+        let mut affiliation =
+            load_affiliation(&env, &doctor, &hospital).expect("Affiliation not found");
+        if affiliation.status != AffiliationStatus::Pending {
+            panic!("Affiliation is not pending");
+        }
+
+        affiliation.status = AffiliationStatus::Active;
+        affiliation.confirmed_at = env.ledger().timestamp();
+        save_affiliation(&env, &affiliation);
+        index_push(&env, &IndexKey::Hospital(hospital.clone()), &doctor);
+
+        env.events().publish(
+            (symbol_short!("aff_conf"), doctor, hospital),
+            affiliation.role,
+        );
+    }
+
+    /// End a pending or active affiliation; either side may call it
+    ///
+    /// # Arguments
+    /// * `caller` - The doctor or the hospital
+    /// * `doctor` - The wallet address of the doctor
+    /// * `hospital` - The wallet address of the hospital
+    pub fn end_affiliation(env: Env, caller: Address, doctor: Address, hospital: Address) {
+        caller.require_auth();
+
+        if caller != doctor && caller != hospital {
+            panic!("Not a party to this affiliation");
+        }
+        let mut affiliation =
+            load_affiliation(&env, &doctor, &hospital).expect("Affiliation not found");
+        if affiliation.status == AffiliationStatus::Ended {
+            panic!("Affiliation already ended");
+        }
+
+        affiliation.status = AffiliationStatus::Ended;
+        affiliation.ended_at = env.ledger().timestamp();
+        save_affiliation(&env, &affiliation);
+        index_remove(&env, &IndexKey::Hospital(hospital.clone()), &doctor);
+
+        env.events()
+            .publish((symbol_short!("aff_end"), doctor, hospital), caller);
+    }
+
+    /// Retrieve the affiliation between a doctor and a hospital
+    pub fn get_affiliation(env: Env, doctor: Address, hospital: Address) -> Affiliation {
+        load_affiliation(&env, &doctor, &hospital).expect("Affiliation not found")
+    }
+
+    /// Retrieve every affiliation a doctor requested, including ended ones
+    pub fn get_affiliations(env: Env, doctor: Address) -> Vec<Affiliation> {
+        let mut affiliations = Vec::new(&env);
+        for hospital in load_list(&env, &DataKey::DoctorAffiliations(doctor.clone())).iter() {
+            if let Some(affiliation) = load_affiliation(&env, &doctor, &hospital) {
+                affiliations.push_back(affiliation);
+            }
+        }
+        affiliations
+    }
+
+    /// Retrieve a page of the doctors with an active affiliation at a
+    /// hospital
+    ///
+    /// # Arguments
+    /// * `hospital` - The wallet address of the hospital
+    /// * `cursor` - Index position to resume from, 0 for the first page
+    /// * `limit` - Number of doctors to return, at most `MAX_PAGE_SIZE`
+    pub fn get_hospital_doctors(
+        env: Env,
+        hospital: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Vec<Address> {
+        index_page(&env, &IndexKey::Hospital(hospital), cursor, limit).0
+    }
+
+    /// Check whether a doctor holds an active affiliation with a hospital
+    pub fn is_affiliated(env: Env, doctor: Address, hospital: Address) -> bool {
+        active_affiliation(&env, &doctor, &hospital).is_some()
+    }
+
+    /// Check whether a doctor's active affiliation with a hospital grants a
+    /// privilege
+    pub fn has_privilege(
+        env: Env,
+        doctor: Address,
+        hospital: Address,
+        privilege: Privilege,
+    ) -> bool {
+        active_affiliation(&env, &doctor, &hospital)
+            .is_some_and(|affiliation| affiliation.privileges.contains(privilege))
+    }
+
+    /// Allow a licensing board to record and revoke licenses in the given
+    /// jurisdictions, replacing any jurisdictions it covered before
+    pub fn add_license_verifier(
+        env: Env,
+        admin: Address,
+        verifier: Address,
+        jurisdictions: Vec<String>,
+    ) {
+        require_admin(&env, &admin);
+        env.storage()
+            .persistent()
+            .set(&DataKey::LicenseVerifier(verifier.clone()), &jurisdictions);
+
+        env.events()
+            .publish((symbol_short!("lic_ver"), verifier), true);
+    }
+
+    pub fn remove_license_verifier(env: Env, admin: Address, verifier: Address) {
+        require_admin(&env, &admin);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::LicenseVerifier(verifier.clone()));
+
+        env.events()
+            .publish((symbol_short!("lic_ver"), verifier), false);
+    }
+
+    pub fn is_license_verifier(env: Env, verifier: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::LicenseVerifier(verifier))
+    }
+
+    /// The jurisdictions a licensing board may record licenses in
+    pub fn get_verifier_jurisdictions(env: Env, verifier: Address) -> Vec<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LicenseVerifier(verifier))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Set the jurisdictions participating in a licensure compact
+    ///
+    /// # Arguments
+    /// * `admin` - The contract admin
+    /// * `compact` - The compact being configured
+    /// * `jurisdictions` - Every member jurisdiction, replacing the current list
+    /// * `grants_privilege` - Whether a member license carries a privilege to
+    ///   practice across members, as opposed to an expedited licensing pathway
+    pub fn set_compact_jurisdictions(
+        env: Env,
+        admin: Address,
+        compact: Compact,
+        jurisdictions: Vec<String>,
+        grants_privilege: bool,
+    ) {
+        require_admin(&env, &admin);
+        let membership = CompactMembership {
+            jurisdictions,
+            grants_privilege,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::CompactMembership(compact), &membership);
+
+        env.events()
+            .publish((symbol_short!("compact"),), (compact, membership));
+    }
+
+    pub fn get_compact_membership(env: Env, compact: Compact) -> CompactMembership {
+        compact_membership(&env, compact)
+    }
+
+    /// Record a doctor's license in a jurisdiction, replacing any earlier
+    /// license there
+    ///
+    /// # Arguments
+    /// * `verifier` - The licensing board vouching for the license
+    /// * `doctor` - The wallet address of the doctor
+    /// * `request` - The license details
+    pub fn record_license(env: Env, verifier: Address, doctor: Address, request: LicenseRequest) {
+        require_license_verifier(&env, &verifier, &request.jurisdiction);
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Doctor(doctor.clone()))
+        {
+            panic!("Doctor profile not found");
+        }
+        if request.expires_at <= request.issued_at {
+            panic!("Invalid license period");
+        }
+
+        let license = License {
+            jurisdiction: request.jurisdiction.clone(),
+            license_number_hash: request.license_number_hash,
+            issued_at: request.issued_at,
+            expires_at: request.expires_at,
+            compacts: request.compacts,
+            verified_by: verifier,
+            revoked_at: None,
+        };
+
+        let mut licenses = load_licenses(&env, &doctor);
+        match licenses
+            .iter()
+            .position(|existing| existing.jurisdiction == license.jurisdiction)
+        {
+            Some(index) => licenses.set(index as u32, license),
+            None => licenses.push_back(license),
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Licenses(doctor.clone()), &licenses);
+
+        env.events().publish(
+            (symbol_short!("lic_rec"), doctor),
+            (request.jurisdiction, request.expires_at),
+        );
+    }
+
+    /// Revoke a doctor's license in a jurisdiction
+    pub fn revoke_license(env: Env, verifier: Address, doctor: Address, jurisdiction: String) {
+        require_license_verifier(&env, &verifier, &jurisdiction);
+
+        let mut licenses = load_licenses(&env, &doctor);
+        let index = licenses
+            .iter()
+            .position(|license| license.jurisdiction == jurisdiction)
+            .expect("License not found") as u32;
+
+        let mut license = licenses.get_unchecked(index);
+        license.revoked_at = Some(env.ledger().timestamp());
+        licenses.set(index, license);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Licenses(doctor.clone()), &licenses);
+
+        env.events()
+            .publish((symbol_short!("lic_rev"), doctor), (jurisdiction, verifier));
+    }
+
+    /// Retrieve every license recorded for a doctor, including revoked and
+    /// expired ones
+    pub fn get_licenses(env: Env, doctor: Address) -> Vec<License> {
+        load_licenses(&env, &doctor)
+    }
+
+    /// Check whether a doctor may practice on a patient located in
+    /// `jurisdiction` at time `at`
+    ///
+    /// A license valid at `at` qualifies if it was issued by that
+    /// jurisdiction, or if it belongs to a privilege-granting compact that
+    /// both its own jurisdiction and `jurisdiction` are members of.
+    pub fn can_practice_in(env: Env, doctor: Address, jurisdiction: String, at: u64) -> bool {
+        for license in load_licenses(&env, &doctor).iter() {
+            if license.revoked_at.is_some() || at < license.issued_at || at >= license.expires_at {
+                continue;
+            }
+            if license.jurisdiction == jurisdiction {
+                return true;
+            }
+            for compact in license.compacts.iter() {
+                let membership = compact_membership(&env, compact);
+                let members = membership.jurisdictions;
+                if membership.grants_privilege
+                    && members.contains(&license.jurisdiction)
+                    && members.contains(&jurisdiction)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
 }
 
 mod test;
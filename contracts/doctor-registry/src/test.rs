@@ -1,6 +1,6 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String};
 
 #[test]
 fn test_create_doctor_profile() {
@@ -9,7 +9,6 @@ fn test_create_doctor_profile() {
     let client = DoctorRegistryClient::new(&env, &contract_id);
 
     let doctor_wallet = Address::generate(&env);
-    let institution_wallet = Address::generate(&env);
 
     env.mock_all_auths();
 
@@ -17,14 +16,12 @@ fn test_create_doctor_profile() {
         &doctor_wallet,
         &String::from_str(&env, "Dr. John Smith"),
         &String::from_str(&env, "Cardiology"),
-        &institution_wallet,
     );
 
     let profile = client.get_doctor_profile(&doctor_wallet);
 
     assert_eq!(profile.name, String::from_str(&env, "Dr. John Smith"));
     assert_eq!(profile.specialization, String::from_str(&env, "Cardiology"));
-    assert_eq!(profile.institution_wallet, institution_wallet);
     assert_eq!(profile.metadata, String::from_str(&env, ""));
 }
 
@@ -35,7 +32,6 @@ fn test_update_doctor_profile() {
     let client = DoctorRegistryClient::new(&env, &contract_id);
 
     let doctor_wallet = Address::generate(&env);
-    let institution_wallet = Address::generate(&env);
 
     env.mock_all_auths();
 
@@ -43,7 +39,6 @@ fn test_update_doctor_profile() {
         &doctor_wallet,
         &String::from_str(&env, "Dr. Jane Doe"),
         &String::from_str(&env, "Neurology"),
-        &institution_wallet,
     );
 
     client.update_doctor_profile(
@@ -73,7 +68,6 @@ fn test_duplicate_profile_creation() {
     let client = DoctorRegistryClient::new(&env, &contract_id);
 
     let doctor_wallet = Address::generate(&env);
-    let institution_wallet = Address::generate(&env);
 
     env.mock_all_auths();
 
@@ -81,7 +75,6 @@ fn test_duplicate_profile_creation() {
         &doctor_wallet,
         &String::from_str(&env, "Dr. Test"),
         &String::from_str(&env, "General Medicine"),
-        &institution_wallet,
     );
 
     // Attempt to create again
@@ -89,7 +82,6 @@ fn test_duplicate_profile_creation() {
         &doctor_wallet,
         &String::from_str(&env, "Dr. Test"),
         &String::from_str(&env, "General Medicine"),
-        &institution_wallet,
     );
 }
 
@@ -131,7 +123,6 @@ fn test_multiple_doctors() {
 
     let doctor1_wallet = Address::generate(&env);
     let doctor2_wallet = Address::generate(&env);
-    let institution_wallet = Address::generate(&env);
 
     env.mock_all_auths();
 
@@ -139,14 +130,12 @@ fn test_multiple_doctors() {
         &doctor1_wallet,
         &String::from_str(&env, "Dr. Alice"),
         &String::from_str(&env, "Oncology"),
-        &institution_wallet,
     );
 
     client.create_doctor_profile(
         &doctor2_wallet,
         &String::from_str(&env, "Dr. Bob"),
         &String::from_str(&env, "Orthopedics"),
-        &institution_wallet,
     );
 
     let profile1 = client.get_doctor_profile(&doctor1_wallet);
@@ -161,3 +150,455 @@ fn test_multiple_doctors() {
         String::from_str(&env, "Orthopedics")
     );
 }
+
+fn setup_affiliation_test(env: &Env) -> (DoctorRegistryClient<'_>, Address, Address) {
+    let hospital_registry_id = env.register_contract(None, hospital_registry::HospitalRegistry);
+    let hospital_registry =
+        hospital_registry::HospitalRegistryClient::new(env, &hospital_registry_id);
+    let contract_id = env.register_contract(None, DoctorRegistry);
+    let client = DoctorRegistryClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let doctor_wallet = Address::generate(env);
+    let hospital_wallet = Address::generate(env);
+
+    env.mock_all_auths();
+
+    hospital_registry.register_hospital(
+        &hospital_wallet,
+        &String::from_str(env, "General Hospital"),
+        &String::from_str(env, "123 Main St"),
+        &String::from_str(env, "Services: ER, Surgery"),
+    );
+    client.initialize(&admin, &hospital_registry_id);
+    client.create_doctor_profile(
+        &doctor_wallet,
+        &String::from_str(env, "Dr. Alice"),
+        &String::from_str(env, "Cardiology"),
+    );
+
+    (client, doctor_wallet, hospital_wallet)
+}
+
+#[test]
+fn test_affiliation_handshake() {
+    let env = Env::default();
+    let (client, doctor_wallet, hospital_wallet) = setup_affiliation_test(&env);
+
+    client.request_affiliation(
+        &doctor_wallet,
+        &hospital_wallet,
+        &AffiliationRole::Attending,
+        &vec![&env, Privilege::Admit, Privilege::Prescribe],
+    );
+
+    // Nothing is granted until the hospital confirms
+    let affiliation = client.get_affiliation(&doctor_wallet, &hospital_wallet);
+    assert_eq!(affiliation.status, AffiliationStatus::Pending);
+    assert!(!client.is_affiliated(&doctor_wallet, &hospital_wallet));
+    assert!(!client.has_privilege(&doctor_wallet, &hospital_wallet, &Privilege::Prescribe));
+
+    client.confirm_affiliation(&hospital_wallet, &doctor_wallet);
+
+    assert!(client.is_affiliated(&doctor_wallet, &hospital_wallet));
+    assert!(client.has_privilege(&doctor_wallet, &hospital_wallet, &Privilege::Prescribe));
+    assert!(!client.has_privilege(&doctor_wallet, &hospital_wallet, &Privilege::Surgery));
+    assert_eq!(
+        client.get_hospital_doctors(&hospital_wallet, &0, &MAX_PAGE_SIZE),
+        vec![&env, doctor_wallet.clone()]
+    );
+    assert_eq!(client.get_affiliations(&doctor_wallet).len(), 1);
+}
+
+#[test]
+fn test_ending_affiliation_keeps_hospital_index_consistent() {
+    let env = Env::default();
+    let (client, doctor_wallet, hospital_wallet) = setup_affiliation_test(&env);
+
+    let mut doctors = vec![&env, doctor_wallet];
+    for name in ["Dr. Bob", "Dr. Carol"] {
+        let wallet = Address::generate(&env);
+        client.create_doctor_profile(
+            &wallet,
+            &String::from_str(&env, name),
+            &String::from_str(&env, "Cardiology"),
+        );
+        doctors.push_back(wallet);
+    }
+    for doctor in doctors.iter() {
+        client.request_affiliation(
+            &doctor,
+            &hospital_wallet,
+            &AffiliationRole::Attending,
+            &vec![&env, Privilege::Prescribe],
+        );
+        client.confirm_affiliation(&hospital_wallet, &doctor);
+    }
+
+    // The last doctor takes the vacated position
+    let first = doctors.get(0).unwrap();
+    client.end_affiliation(&first, &first, &hospital_wallet);
+    assert_eq!(
+        client.get_hospital_doctors(&hospital_wallet, &0, &MAX_PAGE_SIZE),
+        vec![&env, doctors.get(2).unwrap(), doctors.get(1).unwrap()]
+    );
+    assert_eq!(
+        client.get_hospital_doctors(&hospital_wallet, &1, &1),
+        vec![&env, doctors.get(1).unwrap()]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Hospital not registered")]
+fn test_affiliation_with_unregistered_hospital() {
+    let env = Env::default();
+    let (client, doctor_wallet, _) = setup_affiliation_test(&env);
+
+    client.request_affiliation(
+        &doctor_wallet,
+        &Address::generate(&env),
+        &AffiliationRole::Visiting,
+        &vec![&env, Privilege::Telehealth],
+    );
+}
+
+#[test]
+fn test_end_affiliation() {
+    let env = Env::default();
+    let (client, doctor_wallet, hospital_wallet) = setup_affiliation_test(&env);
+
+    client.request_affiliation(
+        &doctor_wallet,
+        &hospital_wallet,
+        &AffiliationRole::Resident,
+        &vec![&env, Privilege::OrderTests],
+    );
+    client.confirm_affiliation(&hospital_wallet, &doctor_wallet);
+    client.end_affiliation(&hospital_wallet, &doctor_wallet, &hospital_wallet);
+
+    assert!(!client.is_affiliated(&doctor_wallet, &hospital_wallet));
+    assert_eq!(
+        client
+            .get_hospital_doctors(&hospital_wallet, &0, &MAX_PAGE_SIZE)
+            .len(),
+        0
+    );
+    assert_eq!(
+        client
+            .get_affiliation(&doctor_wallet, &hospital_wallet)
+            .status,
+        AffiliationStatus::Ended
+    );
+
+    // An ended affiliation can be requested again
+    client.request_affiliation(
+        &doctor_wallet,
+        &hospital_wallet,
+        &AffiliationRole::Attending,
+        &vec![&env, Privilege::OrderTests],
+    );
+    assert_eq!(client.get_affiliations(&doctor_wallet).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Not a party to this affiliation")]
+fn test_stranger_cannot_end_affiliation() {
+    let env = Env::default();
+    let (client, doctor_wallet, hospital_wallet) = setup_affiliation_test(&env);
+
+    client.request_affiliation(
+        &doctor_wallet,
+        &hospital_wallet,
+        &AffiliationRole::Consultant,
+        &vec![&env, Privilege::Prescribe],
+    );
+
+    client.end_affiliation(&Address::generate(&env), &doctor_wallet, &hospital_wallet);
+}
+
+#[test]
+#[should_panic(expected = "Affiliation already exists")]
+fn test_duplicate_affiliation_request() {
+    let env = Env::default();
+    let (client, doctor_wallet, hospital_wallet) = setup_affiliation_test(&env);
+
+    let privileges = vec![&env, Privilege::Prescribe];
+    client.request_affiliation(
+        &doctor_wallet,
+        &hospital_wallet,
+        &AffiliationRole::Attending,
+        &privileges,
+    );
+    client.request_affiliation(
+        &doctor_wallet,
+        &hospital_wallet,
+        &AffiliationRole::Attending,
+        &privileges,
+    );
+}
+
+fn add_doctor(env: &Env, client: &DoctorRegistryClient, specialization: &str) -> Address {
+    let wallet = Address::generate(env);
+    client.create_doctor_profile(
+        &wallet,
+        &String::from_str(env, "Dr. Test"),
+        &String::from_str(env, specialization),
+    );
+    wallet
+}
+
+fn search_filter(env: &Env, specialization: &str) -> DoctorSearchFilter {
+    DoctorSearchFilter {
+        specialization: Some(String::from_str(env, specialization)),
+        institution: None,
+        accepting_new_patients: false,
+        telehealth: false,
+    }
+}
+
+#[test]
+fn test_search_doctors_by_specialty_institution_and_flags() {
+    let env = Env::default();
+    let (client, cardiologist, hospital_wallet) = setup_affiliation_test(&env);
+    let remote_cardiologist = add_doctor(&env, &client, "Cardiology");
+    let neurologist = add_doctor(&env, &client, "Neurology");
+
+    for doctor in [&cardiologist, &remote_cardiologist, &neurologist] {
+        client.request_affiliation(
+            doctor,
+            &hospital_wallet,
+            &AffiliationRole::Attending,
+            &vec![&env, Privilege::Telehealth],
+        );
+        client.confirm_affiliation(&hospital_wallet, doctor);
+    }
+    client.set_availability(&cardiologist, &true, &false);
+    client.set_availability(&remote_cardiologist, &true, &true);
+    client.set_availability(&neurologist, &true, &true);
+
+    // Cardiologists at the hospital who offer telemedicine
+    let mut filter = search_filter(&env, "Cardiology");
+    filter.institution = Some(hospital_wallet.clone());
+    filter.telehealth = true;
+    let page = client.search_doctors(&filter, &0, &MAX_PAGE_SIZE);
+    assert_eq!(page.next_cursor, None);
+    assert_eq!(page.doctors.len(), 1);
+    let result = page.doctors.get(0).unwrap();
+    assert_eq!(result.wallet, remote_cardiologist);
+    assert!(result.profile.telehealth);
+
+    // Every cardiologist, wherever they practice
+    let page = client.search_doctors(&search_filter(&env, "Cardiology"), &0, &MAX_PAGE_SIZE);
+    assert_eq!(page.doctors.len(), 2);
+
+    // Doctors elsewhere are excluded by the institution filter
+    let mut filter = search_filter(&env, "Cardiology");
+    filter.institution = Some(Address::generate(&env));
+    let page = client.search_doctors(&filter, &0, &MAX_PAGE_SIZE);
+    assert_eq!(page.doctors.len(), 0);
+}
+
+#[test]
+fn test_specialty_index_follows_profile_update() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, DoctorRegistry);
+    let client = DoctorRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let doctor_wallet = add_doctor(&env, &client, "Neurology");
+    client.update_doctor_profile(
+        &doctor_wallet,
+        &String::from_str(&env, "Pediatric Neurology"),
+        &String::from_str(&env, "Board Certified"),
+    );
+
+    let old = client.search_doctors(&search_filter(&env, "Neurology"), &0, &MAX_PAGE_SIZE);
+    assert_eq!(old.doctors.len(), 0);
+
+    let new = client.search_doctors(
+        &search_filter(&env, "Pediatric Neurology"),
+        &0,
+        &MAX_PAGE_SIZE,
+    );
+    assert_eq!(new.doctors.len(), 1);
+    assert_eq!(new.doctors.get(0).unwrap().wallet, doctor_wallet);
+}
+
+#[test]
+fn test_search_doctors_is_paginated() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, DoctorRegistry);
+    let client = DoctorRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    for _ in 0..3 {
+        add_doctor(&env, &client, "Oncology");
+    }
+    let filter = DoctorSearchFilter {
+        specialization: None,
+        institution: None,
+        accepting_new_patients: false,
+        telehealth: false,
+    };
+
+    let first = client.search_doctors(&filter, &0, &2);
+    assert_eq!(first.doctors.len(), 2);
+    assert_eq!(first.next_cursor, Some(2));
+
+    let second = client.search_doctors(&filter, &2, &2);
+    assert_eq!(second.doctors.len(), 1);
+    assert_eq!(second.next_cursor, None);
+}
+
+fn setup_license_test(env: &Env) -> (DoctorRegistryClient<'_>, Address, Address, Address) {
+    let contract_id = env.register_contract(None, DoctorRegistry);
+    let client = DoctorRegistryClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let board = Address::generate(env);
+
+    env.mock_all_auths();
+
+    client.initialize(&admin, &Address::generate(env));
+    client.add_license_verifier(
+        &admin,
+        &board,
+        &vec![
+            env,
+            String::from_str(env, "NY"),
+            String::from_str(env, "CO"),
+        ],
+    );
+    let doctor_wallet = add_doctor(env, &client, "Psychiatry");
+
+    (client, admin, board, doctor_wallet)
+}
+
+fn license(env: &Env, jurisdiction: &str, compacts: Vec<Compact>) -> LicenseRequest {
+    LicenseRequest {
+        jurisdiction: String::from_str(env, jurisdiction),
+        license_number_hash: BytesN::from_array(env, &[7; 32]),
+        issued_at: 100,
+        expires_at: 1_000,
+        compacts,
+    }
+}
+
+/// --------------------
+/// Licensure Tests
+/// --------------------
+#[test]
+fn test_can_practice_in_licensed_jurisdiction() {
+    let env = Env::default();
+    let (client, _admin, board, doctor_wallet) = setup_license_test(&env);
+    let ny = String::from_str(&env, "NY");
+
+    client.record_license(&board, &doctor_wallet, &license(&env, "NY", vec![&env]));
+
+    assert!(client.can_practice_in(&doctor_wallet, &ny, &500));
+    assert!(!client.can_practice_in(&doctor_wallet, &ny, &50));
+    assert!(!client.can_practice_in(&doctor_wallet, &ny, &1_000));
+    assert!(!client.can_practice_in(&doctor_wallet, &String::from_str(&env, "CA"), &500));
+
+    let licenses = client.get_licenses(&doctor_wallet);
+    assert_eq!(licenses.len(), 1);
+    assert_eq!(licenses.get(0).unwrap().verified_by, board);
+}
+
+#[test]
+fn test_can_practice_in_through_compact() {
+    let env = Env::default();
+    let (client, admin, board, doctor_wallet) = setup_license_test(&env);
+    let members = vec![
+        &env,
+        String::from_str(&env, "CO"),
+        String::from_str(&env, "WI"),
+    ];
+
+    client.set_compact_jurisdictions(&admin, &Compact::PsyPact, &members, &true);
+    client.record_license(
+        &board,
+        &doctor_wallet,
+        &license(&env, "CO", vec![&env, Compact::PsyPact]),
+    );
+
+    assert!(client.can_practice_in(&doctor_wallet, &String::from_str(&env, "WI"), &500));
+    assert!(!client.can_practice_in(&doctor_wallet, &String::from_str(&env, "NY"), &500));
+}
+
+#[test]
+fn test_licensing_pathway_compact_grants_no_privilege() {
+    let env = Env::default();
+    let (client, admin, board, doctor_wallet) = setup_license_test(&env);
+    let members = vec![
+        &env,
+        String::from_str(&env, "CO"),
+        String::from_str(&env, "WI"),
+    ];
+
+    // The IMLC only expedites licensing in WI; it does not license the doctor there
+    client.set_compact_jurisdictions(&admin, &Compact::Imlc, &members, &false);
+    client.record_license(
+        &board,
+        &doctor_wallet,
+        &license(&env, "CO", vec![&env, Compact::Imlc]),
+    );
+
+    assert!(
+        !client
+            .get_compact_membership(&Compact::Imlc)
+            .grants_privilege
+    );
+    assert!(client.can_practice_in(&doctor_wallet, &String::from_str(&env, "CO"), &500));
+    assert!(!client.can_practice_in(&doctor_wallet, &String::from_str(&env, "WI"), &500));
+}
+
+#[test]
+fn test_revoked_license_no_longer_qualifies() {
+    let env = Env::default();
+    let (client, _admin, board, doctor_wallet) = setup_license_test(&env);
+    let ny = String::from_str(&env, "NY");
+
+    client.record_license(&board, &doctor_wallet, &license(&env, "NY", vec![&env]));
+    client.revoke_license(&board, &doctor_wallet, &ny);
+
+    assert!(!client.can_practice_in(&doctor_wallet, &ny, &500));
+
+    // Re-recording the license replaces the revoked entry
+    client.record_license(&board, &doctor_wallet, &license(&env, "NY", vec![&env]));
+    assert_eq!(client.get_licenses(&doctor_wallet).len(), 1);
+    assert!(client.can_practice_in(&doctor_wallet, &ny, &500));
+}
+
+#[test]
+#[should_panic(expected = "Not a license verifier")]
+fn test_record_license_requires_verifier() {
+    let env = Env::default();
+    let (client, _admin, _board, doctor_wallet) = setup_license_test(&env);
+
+    let stranger = Address::generate(&env);
+    client.record_license(&stranger, &doctor_wallet, &license(&env, "NY", vec![&env]));
+}
+
+#[test]
+fn test_verifier_limited_to_its_jurisdictions() {
+    let env = Env::default();
+    let (client, admin, board, doctor_wallet) = setup_license_test(&env);
+    let ny = String::from_str(&env, "NY");
+
+    assert!(client
+        .try_record_license(&board, &doctor_wallet, &license(&env, "CA", vec![&env]))
+        .is_err());
+
+    // Another state's board can neither record nor revoke a NY license
+    let ca_board = Address::generate(&env);
+    client.add_license_verifier(&admin, &ca_board, &vec![&env, String::from_str(&env, "CA")]);
+    client.record_license(&ca_board, &doctor_wallet, &license(&env, "CA", vec![&env]));
+    client.record_license(&board, &doctor_wallet, &license(&env, "NY", vec![&env]));
+    assert!(client
+        .try_revoke_license(&ca_board, &doctor_wallet, &ny)
+        .is_err());
+    assert!(client.can_practice_in(&doctor_wallet, &ny, &500));
+}
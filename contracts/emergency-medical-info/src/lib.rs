@@ -1,5 +1,6 @@
 #![no_std]
 
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
 use soroban_sdk::{
     contract, contractimpl, contracttype, Address, BytesN, Env, String, Symbol, Vec,
 };
@@ -68,6 +69,36 @@ pub enum DataKey {
     EmergencyAccessLog(Address),
     DNROrder(Address),
     EmergencyNotifications(Address),
+    AccessPolicy(Address), // patient -> AccessControl contract
+}
+
+/// Ask the patient's AccessControl contract whether `requester` holds `scope`
+/// on their emergency record. Returns `None` when the patient has not set a policy.
+fn policy_allows(
+    env: &Env,
+    patient_id: &Address,
+    requester: &Address,
+    scope: Scope,
+) -> Option<bool> {
+    if patient_id == requester {
+        return Some(true);
+    }
+    let policy: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AccessPolicy(patient_id.clone()))?;
+    Some(AccessPolicyClient::new(env, &policy).check_access(
+        requester,
+        &patient_resource(env, patient_id, "emergency"),
+        &scope,
+        &Purpose::Treatment,
+    ))
+}
+
+fn require_policy(env: &Env, patient_id: &Address, requester: &Address, scope: Scope) {
+    if !policy_allows(env, patient_id, requester, scope).unwrap_or(true) {
+        panic!("Access denied by patient's access policy");
+    }
 }
 
 #[contract]
@@ -75,6 +106,25 @@ pub struct EmergencyMedicalInfo;
 
 #[contractimpl]
 impl EmergencyMedicalInfo {
+    /// Delegate access decisions for a patient's emergency record to an
+    /// AccessControl contract, or clear the delegation with `None`. Break-glass
+    /// access through `emergency_access_request` stays open and audited.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        let key = DataKey::AccessPolicy(patient_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Get the AccessControl contract governing a patient's emergency record, if any
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccessPolicy(patient_id))
+    }
+
     /// Set or update emergency profile for a patient
     /// Sub-second access optimized with persistent storage
     #[allow(clippy::too_many_arguments)]
@@ -129,6 +179,7 @@ impl EmergencyMedicalInfo {
         severity: Symbol,
     ) {
         provider_id.require_auth();
+        require_policy(&env, &patient_id, &provider_id, Scope::Write);
 
         let alert = CriticalAlert {
             provider_id,
@@ -226,6 +277,7 @@ impl EmergencyMedicalInfo {
         effective_date: u64,
     ) {
         provider_id.require_auth();
+        require_policy(&env, &patient_id, &provider_id, Scope::Write);
 
         let dnr = DNROrder {
             provider_id: provider_id.clone(),
@@ -256,6 +308,7 @@ impl EmergencyMedicalInfo {
         requester: Address,
     ) -> EmergencyProfile {
         requester.require_auth();
+        require_policy(&env, &patient_id, &requester, Scope::Read);
 
         let key = DataKey::EmergencyProfile(patient_id.clone());
         env.storage()
@@ -1,7 +1,8 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Env};
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest};
+use soroban_sdk::{testutils::Address as _, vec, Env};
 
 fn create_test_emergency_contacts(env: &Env) -> Vec<EmergencyContact> {
     let mut contacts = Vec::new(env);
@@ -456,3 +457,72 @@ fn test_comprehensive_emergency_scenario() {
     let logs = client.get_emergency_access_logs(&patient);
     assert_eq!(logs.len(), 1);
 }
+
+#[test]
+fn test_access_policy_gates_emergency_record() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, EmergencyMedicalInfo);
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    let doctor = Address::generate(&env);
+    let responder = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.set_emergency_profile(
+        &patient,
+        &Symbol::new(&env, "O_POS"),
+        &String::from_str(&env, "Penicillin"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &create_test_emergency_contacts(&env),
+        &None,
+    );
+
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &doctor, EntityType::Doctor);
+    client.set_access_policy(&patient, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient), Some(policy_id));
+
+    let alert = |client: &EmergencyMedicalInfoClient| {
+        client.try_add_critical_alert(
+            &patient,
+            &doctor,
+            &Symbol::new(&env, "ALLERGY"),
+            &String::from_str(&env, "Anaphylaxis risk"),
+            &Symbol::new(&env, "HIGH"),
+        )
+    };
+    assert!(client.try_get_emergency_info(&patient, &doctor).is_err());
+    assert!(alert(&client).is_err());
+    // The patient reads their own record without a grant
+    client.get_emergency_info(&patient, &patient);
+
+    let resource_id = patient_resource(&env, &patient, "emergency");
+    policy.grant_access(
+        &patient,
+        &doctor,
+        &resource_id,
+        &vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    assert!(client.try_get_emergency_info(&patient, &doctor).is_ok());
+    assert!(alert(&client).is_ok());
+
+    // Break-glass stays available to responders without a grant
+    client.emergency_access_request(
+        &responder,
+        &patient,
+        &Symbol::new(&env, "CARDIAC"),
+        &String::from_str(&env, "Unresponsive patient"),
+        &String::from_str(&env, "ER"),
+    );
+    assert!(client.try_get_emergency_info(&patient, &responder).is_err());
+
+    policy.revoke_access(&patient, &doctor, &resource_id);
+    assert!(client.try_get_emergency_info(&patient, &doctor).is_err());
+}
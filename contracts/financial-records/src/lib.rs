@@ -1,4 +1,5 @@
 #![no_std]
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
 use soroban_sdk::{contract, contractimpl, contracttype, vec, Address, Env, String, Vec};
 
 #[contracttype]
@@ -26,6 +27,7 @@ pub enum DataKey {
     Record(Address, u32),     // (Owner, Index) -> FinancialRecord
     RecordCount(Address),     // Owner -> Number of records
     Access(Address, Address), // (Owner, Authorized) -> bool
+    AccessPolicy(Address),    // Owner -> AccessControl contract
 }
 
 #[contract]
@@ -167,16 +169,45 @@ impl FinancialRecordContract {
             .remove(&DataKey::Access(owner, authorized));
     }
 
+    /// Delegates access decisions to an AccessControl contract, or clears the
+    /// delegation with `None`. While set, callers need a `Payment` grant on
+    /// `patient/<owner>/financial` and local grants are ignored.
+    pub fn set_access_policy(e: Env, owner: Address, policy: Option<Address>) {
+        owner.require_auth();
+        let key = DataKey::AccessPolicy(owner);
+        match policy {
+            Some(policy) => e.storage().persistent().set(&key, &policy),
+            None => e.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Returns the AccessControl contract governing an owner's records, if any.
+    pub fn get_access_policy(e: Env, owner: Address) -> Option<Address> {
+        e.storage().persistent().get(&DataKey::AccessPolicy(owner))
+    }
+
     /// Internal helper to check access.
     fn check_access(e: &Env, caller: &Address, owner: &Address) {
         if caller == owner {
             return;
         }
-        let is_authorized: bool = e
+        let policy: Option<Address> = e
             .storage()
             .persistent()
-            .get(&DataKey::Access(owner.clone(), caller.clone()))
-            .unwrap_or(false);
+            .get(&DataKey::AccessPolicy(owner.clone()));
+        let is_authorized = match policy {
+            Some(policy) => AccessPolicyClient::new(e, &policy).check_access(
+                caller,
+                &patient_resource(e, owner, "financial"),
+                &Scope::Read,
+                &Purpose::Payment,
+            ),
+            None => e
+                .storage()
+                .persistent()
+                .get(&DataKey::Access(owner.clone(), caller.clone()))
+                .unwrap_or(false),
+        };
 
         if !is_authorized {
             panic!("Access denied");
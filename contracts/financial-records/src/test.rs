@@ -1,7 +1,8 @@
 #![cfg(test)]
 use super::*;
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest};
 use soroban_sdk::testutils::{Address as _, Ledger};
-use soroban_sdk::Env;
+use soroban_sdk::{BytesN, Env};
 
 #[test]
 fn test_add_and_get_records() {
@@ -143,3 +144,46 @@ fn test_filtering() {
     assert_eq!(range_records.len(), 1);
     assert_eq!(range_records.get(0).unwrap().timestamp, 200);
 }
+
+#[test]
+fn test_access_policy_contract() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, ());
+    let client = FinancialRecordContractClient::new(&e, &contract_id);
+
+    let owner = Address::generate(&e);
+    let insurer = Address::generate(&e);
+
+    // Deploy access-control with the owner and a verified insurer registered
+    let (policy_id, policy) = setup_access_policy(&e, &owner, &insurer, EntityType::Insurer);
+
+    client.add_financial_record(
+        &owner,
+        &RecordType::Invoice,
+        &String::from_str(&e, "h"),
+        &String::from_str(&e, "d"),
+    );
+    client.set_access_policy(&owner, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&owner), Some(policy_id));
+
+    let resource_id = patient_resource(&e, &owner, "financial");
+    policy.grant_access(
+        &owner,
+        &insurer,
+        &resource_id,
+        &vec![&e, access_control::Scope::Read],
+        &GrantRequest {
+            purpose: access_control::Purpose::Payment,
+            consent_hash: BytesN::from_array(&e, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    assert_eq!(client.get_financial_records(&insurer, &owner).len(), 1);
+
+    // Revoking the grant in access-control blocks the downstream read
+    policy.revoke_access(&owner, &insurer, &resource_id);
+    assert!(client.try_get_financial_records(&insurer, &owner).is_err());
+}
@@ -1,5 +1,6 @@
 #![no_std]
 
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map,
     String, Symbol, Vec,
@@ -252,6 +253,27 @@ pub enum DataKey {
     ReferralCompletion(u64),
     CareSummary(u64, Symbol),
     CareSummaryRequest(u64),
+    AccessPolicy(Address), // patient -> AccessControl contract
+}
+
+/// Ask the patient's AccessControl contract whether `provider` holds `scope`
+/// on their referrals. Returns `None` when the patient has not set a policy.
+fn policy_allows(
+    env: &Env,
+    patient_id: &Address,
+    provider: &Address,
+    scope: Scope,
+) -> Option<bool> {
+    let policy: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AccessPolicy(patient_id.clone()))?;
+    Some(AccessPolicyClient::new(env, &policy).check_access(
+        provider,
+        &patient_resource(env, patient_id, "referrals"),
+        &scope,
+        &Purpose::Treatment,
+    ))
 }
 
 #[contract]
@@ -761,6 +783,26 @@ impl HealthcareAnalytics {
 
     // --- Referral workflow ---
 
+    /// Delegate access decisions for a patient's referrals to an AccessControl
+    /// contract, or clear the delegation with `None`. While set, referring
+    /// providers need a write grant and providers sharing a care summary need
+    /// a read grant on `patient/<patient>/referrals`.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        let key = DataKey::AccessPolicy(patient_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Get the AccessControl contract governing a patient's referrals, if any.
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccessPolicy(patient_id))
+    }
+
     /// Create a new referral from referring provider to receiving provider.
     pub fn create_referral(
         env: Env,
@@ -774,6 +816,9 @@ impl HealthcareAnalytics {
         requested_services: Vec<Symbol>,
     ) -> Result<u64, ReferralError> {
         referring_provider.require_auth();
+        if !policy_allows(&env, &patient_id, &referring_provider, Scope::Write).unwrap_or(true) {
+            return Err(ReferralError::Unauthorized);
+        }
 
         let next_id_key = DataKey::NextReferralId;
         let next_id: u64 = env
@@ -1022,6 +1067,9 @@ impl HealthcareAnalytics {
         if !is_participant {
             return Err(ReferralError::Unauthorized);
         }
+        if !policy_allows(&env, &referral.patient_id, &from_provider, Scope::Read).unwrap_or(true) {
+            return Err(ReferralError::Unauthorized);
+        }
         if referral.status == ReferralStatus::Declined || referral.status == ReferralStatus::Cancelled
         {
             return Err(ReferralError::InvalidState);
@@ -1,9 +1,10 @@
 #![cfg(test)]
 
 use super::*;
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest};
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
-    symbol_short, Address, BytesN, Env, String, Symbol, Vec,
+    symbol_short, vec, Address, BytesN, Env, String, Symbol, Vec,
 };
 
 #[test]
@@ -911,6 +912,60 @@ fn test_share_care_summary() {
     );
 }
 
+#[test]
+fn test_access_policy_gates_referrals() {
+    let env = Env::default();
+    let contract_id = env.register(HealthcareAnalytics, ());
+    let client = HealthcareAnalyticsClient::new(&env, &contract_id);
+    let (referring, receiving, patient) = setup_referral_parties(&env);
+
+    env.mock_all_auths();
+
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &referring, EntityType::Doctor);
+    client.set_access_policy(&patient, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient), Some(policy_id));
+
+    let create = |client: &HealthcareAnalyticsClient| {
+        client.try_create_referral(
+            &referring,
+            &patient,
+            &receiving,
+            &symbol_short!("cardio"),
+            &String::from_str(&env, "Eval"),
+            &symbol_short!("routine"),
+            &BytesN::from_array(&env, &[0u8; 32]),
+            &Vec::new(&env),
+        )
+    };
+    assert_eq!(create(&client), Err(Ok(ReferralError::Unauthorized)));
+
+    let resource_id = patient_resource(&env, &patient, "referrals");
+    policy.grant_access(
+        &patient,
+        &referring,
+        &resource_id,
+        &vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    let id = create(&client).unwrap().unwrap();
+
+    // The receiving provider holds no grant of its own
+    let summary_hash = BytesN::from_array(&env, &[10u8; 32]);
+    assert_eq!(
+        client.try_share_care_summary(&id, &receiving, &symbol_short!("consult"), &summary_hash),
+        Err(Ok(ReferralError::Unauthorized))
+    );
+    client.share_care_summary(&id, &referring, &symbol_short!("consult"), &summary_hash);
+
+    policy.revoke_access(&patient, &referring, &resource_id);
+    assert_eq!(create(&client), Err(Ok(ReferralError::Unauthorized)));
+}
+
 #[test]
 fn test_request_care_summary() {
     let env = Env::default();
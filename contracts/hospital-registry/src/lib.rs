@@ -213,6 +213,15 @@ impl HospitalRegistry {
             .expect("Hospital not found")
     }
 
+    /// Check whether a wallet belongs to a registered hospital
+    ///
+    /// # Arguments
+    /// * `wallet` - The wallet address of the hospital
+    pub fn is_hospital_registered(env: Env, wallet: Address) -> bool {
+        let key = DataKey::Hospital(wallet);
+        env.storage().persistent().has(&key)
+    }
+
     /// Set full hospital configuration in one call
     pub fn set_hospital_config(env: Env, wallet: Address, config: HospitalConfig) {
         wallet.require_auth();
@@ -63,6 +63,28 @@ fn test_update_hospital() {
     assert_eq!(hospital.name, String::from_str(&env, "City Hospital"));
 }
 
+#[test]
+fn test_is_hospital_registered() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, HospitalRegistry);
+    let client = HospitalRegistryClient::new(&env, &contract_id);
+
+    let hospital_wallet = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    assert!(!client.is_hospital_registered(&hospital_wallet));
+
+    client.register_hospital(
+        &hospital_wallet,
+        &String::from_str(&env, "City Hospital"),
+        &String::from_str(&env, "456 Oak Ave"),
+        &String::from_str(&env, "General Services"),
+    );
+
+    assert!(client.is_hospital_registered(&hospital_wallet));
+}
+
 #[test]
 #[should_panic(expected = "Hospital already registered")]
 fn test_duplicate_registration() {
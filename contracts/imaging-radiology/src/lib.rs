@@ -1,5 +1,6 @@
 #![no_std]
 
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, String, Symbol, Vec,
 };
@@ -89,6 +90,7 @@ pub enum DataKey {
     PeerReview(u64),
     PatientOrders(Address),
     ProviderOrders(Address),
+    AccessPolicy(Address), // patient -> AccessControl contract
 }
 
 /// --------------------
@@ -109,11 +111,52 @@ pub enum Error {
     PeerReviewExists = 8,
 }
 
+/// Fails unless the patient's AccessControl contract, if they set one,
+/// grants `writer` write access to their imaging.
+fn require_policy_write(env: &Env, patient_id: &Address, writer: &Address) -> Result<(), Error> {
+    let Some(policy) = env
+        .storage()
+        .persistent()
+        .get::<_, Address>(&DataKey::AccessPolicy(patient_id.clone()))
+    else {
+        return Ok(());
+    };
+    if !AccessPolicyClient::new(env, &policy).check_access(
+        writer,
+        &patient_resource(env, patient_id, "imaging"),
+        &Scope::Write,
+        &Purpose::Treatment,
+    ) {
+        return Err(Error::UnauthorizedAccess);
+    }
+    Ok(())
+}
+
 #[contract]
 pub struct ImagingRadiology;
 
 #[contractimpl]
 impl ImagingRadiology {
+    /// Delegate access decisions for a patient's imaging to an AccessControl
+    /// contract, or clear the delegation with `None`. While set, ordering
+    /// providers, imaging centers and radiologists need a write grant on
+    /// `patient/<patient>/imaging`.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        let key = DataKey::AccessPolicy(patient_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Get the AccessControl contract governing a patient's imaging, if any
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccessPolicy(patient_id))
+    }
+
     /// Order a new imaging study
     #[allow(clippy::too_many_arguments)]
     pub fn order_imaging_study(
@@ -127,6 +170,7 @@ impl ImagingRadiology {
         priority: Symbol,
     ) -> Result<u64, Error> {
         provider_id.require_auth();
+        require_policy_write(&env, &patient_id, &provider_id)?;
 
         // Get next order ID
         let counter_key = DataKey::OrderCounter;
@@ -195,6 +239,7 @@ impl ImagingRadiology {
             .persistent()
             .get(&order_key)
             .ok_or(Error::OrderNotFound)?;
+        require_policy_write(&env, &order.patient_id, &imaging_center)?;
 
         // Check if already scheduled
         let schedule_key = DataKey::ImagingSchedule(order_id);
@@ -238,6 +283,7 @@ impl ImagingRadiology {
             .persistent()
             .get(&order_key)
             .ok_or(Error::OrderNotFound)?;
+        require_policy_write(&env, &order.patient_id, &imaging_center)?;
 
         // Check if images already uploaded
         let images_key = DataKey::DicomImages(order_id);
@@ -276,10 +322,12 @@ impl ImagingRadiology {
 
         // Verify order exists
         let order_key = DataKey::ImagingOrder(order_id);
-        env.storage()
+        let order: ImagingOrder = env
+            .storage()
             .persistent()
-            .get::<_, ImagingOrder>(&order_key)
+            .get(&order_key)
             .ok_or(Error::OrderNotFound)?;
+        require_policy_write(&env, &order.patient_id, &radiologist_id)?;
 
         // Verify images uploaded
         let images_key = DataKey::DicomImages(order_id);
@@ -324,6 +372,7 @@ impl ImagingRadiology {
             .persistent()
             .get(&order_key)
             .ok_or(Error::OrderNotFound)?;
+        require_policy_write(&env, &order.patient_id, &radiologist_id)?;
 
         // Verify images uploaded
         let images_key = DataKey::DicomImages(order_id);
@@ -366,10 +415,12 @@ impl ImagingRadiology {
 
         // Verify order exists
         let order_key = DataKey::ImagingOrder(order_id);
-        env.storage()
+        let order: ImagingOrder = env
+            .storage()
             .persistent()
-            .get::<_, ImagingOrder>(&order_key)
+            .get(&order_key)
             .ok_or(Error::OrderNotFound)?;
+        require_policy_write(&env, &order.patient_id, &requesting_radiologist)?;
 
         // Check if peer review already requested
         let peer_key = DataKey::PeerReview(order_id);
@@ -1,6 +1,7 @@
 #![cfg(test)]
 
 use super::*;
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest};
 use soroban_sdk::{testutils::Address as _, vec as svec, Env};
 
 #[test]
@@ -723,3 +724,60 @@ fn test_urgent_findings_notification() {
     let order = client.get_imaging_order(&order_id).unwrap();
     assert_eq!(order.priority, Symbol::new(&env, "STAT"));
 }
+
+#[test]
+fn test_access_policy_gates_imaging_writes() {
+    let env = Env::default();
+    let contract_id = env.register(ImagingRadiology, ());
+    let client = ImagingRadiologyClient::new(&env, &contract_id);
+
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let imaging_center = Address::generate(&env);
+    env.mock_all_auths();
+
+    let order = |client: &ImagingRadiologyClient| {
+        client.try_order_imaging_study(
+            &provider,
+            &patient,
+            &Symbol::new(&env, "MRI"),
+            &String::from_str(&env, "Brain"),
+            &false,
+            &String::from_str(&env, "Headaches"),
+            &Symbol::new(&env, "ROUTINE"),
+        )
+    };
+    let order_id = order(&client).unwrap().unwrap();
+
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &provider, EntityType::Doctor);
+    client.set_access_policy(&patient, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient), Some(policy_id));
+
+    // Neither the ordering provider nor the imaging center holds a grant yet
+    assert_eq!(order(&client), Err(Ok(Error::UnauthorizedAccess)));
+    let prep_hash = BytesN::from_array(&env, &[1u8; 32]);
+    let scheduled_time = env.ledger().timestamp() + 86400;
+    assert_eq!(
+        client.try_schedule_imaging(&order_id, &imaging_center, &scheduled_time, &prep_hash),
+        Err(Ok(Error::UnauthorizedAccess))
+    );
+
+    let resource_id = patient_resource(&env, &patient, "imaging");
+    let request = GrantRequest {
+        purpose: Purpose::Treatment,
+        consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+        can_delegate: false,
+        expires_at: 0,
+    };
+    policy.grant_access(
+        &patient,
+        &provider,
+        &resource_id,
+        &svec![&env, Scope::Write],
+        &request,
+    );
+    assert!(order(&client).is_ok());
+
+    policy.revoke_access(&patient, &provider, &resource_id);
+    assert_eq!(order(&client), Err(Ok(Error::UnauthorizedAccess)));
+}
@@ -3,19 +3,64 @@
 mod types;
 mod test;
 
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
 use soroban_sdk::{contract, contractimpl, Env, Address, String, Symbol, Vec, BytesN};
 use types::{DataKey, Error, VaccineRecord, AdverseEvent, VaccineSeries};
 
+/// Ask the patient's AccessControl contract whether `requester` holds `scope`
+/// on their immunizations. Returns `None` when the patient has not set a policy.
+fn policy_allows(
+    env: &Env,
+    patient_id: &Address,
+    requester: &Address,
+    scope: Scope,
+) -> Option<bool> {
+    if patient_id == requester {
+        return Some(true);
+    }
+    let policy: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AccessPolicy(patient_id.clone()))?;
+    Some(AccessPolicyClient::new(env, &policy).check_access(
+        requester,
+        &patient_resource(env, patient_id, "immunizations"),
+        &scope,
+        &Purpose::Treatment,
+    ))
+}
+
 #[contract]
 pub struct ImmunizationRegistry;
 
 #[contractimpl]
 impl ImmunizationRegistry {
+    /// Delegate access decisions for a patient's immunizations to an
+    /// AccessControl contract, or clear the delegation with `None`. While set,
+    /// providers need a grant on `patient/<patient>/immunizations` to record or
+    /// read them.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        let key = DataKey::AccessPolicy(patient_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::AccessPolicy(patient_id))
+    }
+
     pub fn record_immunization(
         env: Env,
         record: VaccineRecord,
     ) -> Result<u64, Error> {
         record.provider_id.require_auth();
+        let allowed = policy_allows(&env, &record.patient_id, &record.provider_id, Scope::Write);
+        if allowed == Some(false) {
+            return Err(Error::NotAuthorized);
+        }
 
         let count: u64 = env
             .storage()
@@ -50,8 +95,13 @@ impl ImmunizationRegistry {
     ) -> Result<(), Error> {
         reporter.require_auth();
 
-        if !env.storage().persistent().has(&DataKey::ImmunizationRecord(immunization_id)) {
-            return Err(Error::RecordNotFound);
+        let record: VaccineRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ImmunizationRecord(immunization_id))
+            .ok_or(Error::RecordNotFound)?;
+        if policy_allows(&env, &record.patient_id, &reporter, Scope::Write) == Some(false) {
+            return Err(Error::NotAuthorized);
         }
 
         let event = AdverseEvent {
@@ -80,6 +130,9 @@ impl ImmunizationRegistry {
         requester: Address,
     ) -> Result<Vec<VaccineRecord>, Error> {
         requester.require_auth();
+        if policy_allows(&env, &patient_id, &requester, Scope::Read) == Some(false) {
+            return Err(Error::NotAuthorized);
+        }
 
         let record_ids: Vec<u64> = env
             .storage()
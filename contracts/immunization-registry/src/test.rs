@@ -1,6 +1,7 @@
 #![cfg(test)]
 
 use super::*;
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest};
 use soroban_sdk::{testutils::Address as _, Address, Env, String, Symbol, BytesN};
 
 #[test]
@@ -162,3 +163,55 @@ fn test_vaccine_series_and_due() {
     let due3 = client.check_due_vaccines(&patient_id, &1700000000);
     assert_eq!(due3.len(), 0);
 }
+
+#[test]
+fn test_access_policy_governs_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ImmunizationRegistry, ());
+    let client = ImmunizationRegistryClient::new(&env, &contract_id);
+
+    let patient_id = Address::generate(&env);
+    let provider_id = Address::generate(&env);
+    let (policy_id, policy) =
+        setup_access_policy(&env, &patient_id, &provider_id, EntityType::Doctor);
+    client.set_access_policy(&patient_id, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient_id), Some(policy_id));
+
+    let record = VaccineRecord {
+        patient_id: patient_id.clone(),
+        provider_id: provider_id.clone(),
+        vaccine_name: String::from_str(&env, "Hepatitis B"),
+        cvx_code: String::from_str(&env, "CVX_43"),
+        lot_number: String::from_str(&env, "LOT_12345"),
+        manufacturer: String::from_str(&env, "SANOFI"),
+        administration_date: 1690000000,
+        expiration_date: 1790000000,
+        dose_number: 1,
+        route: Symbol::new(&env, "IM"),
+        site: Symbol::new(&env, "DELTOID"),
+    };
+    assert!(client.try_record_immunization(&record).is_err());
+
+    let resource_id = patient_resource(&env, &patient_id, "immunizations");
+    policy.grant_access(
+        &patient_id,
+        &provider_id,
+        &resource_id,
+        &soroban_sdk::vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    client.record_immunization(&record);
+    assert_eq!(client.get_immunization_history(&patient_id, &provider_id).len(), 1);
+
+    // Revoking the grant in access-control blocks the downstream read
+    policy.revoke_access(&patient_id, &provider_id, &resource_id);
+    assert!(client.try_get_immunization_history(&patient_id, &provider_id).is_err());
+    assert_eq!(client.get_immunization_history(&patient_id, &patient_id).len(), 1);
+}
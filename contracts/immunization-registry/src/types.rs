@@ -10,6 +10,7 @@ pub enum DataKey {
     ImmunizationRecord(u64),
     AdverseEvents(u64), // List of AdverseEvent
     PatientVaccineSeries(Address), // List of VaccineSeries
+    AccessPolicy(Address), // patient -> AccessControl contract
 }
 
 #[contracterror]
@@ -1,5 +1,6 @@
 #![no_std]
 
+use access_control_interface::{AccessPolicyClient, Purpose, Scope, patient_resource};
 use soroban_sdk::{
     Address, BytesN, Env, String, Symbol, Vec, contract, contracterror, contractimpl, contracttype,
     panic_with_error,
@@ -46,11 +47,54 @@ pub struct OrderRequest {
     pub collection_date: Option<u64>,
 }
 
+#[contracttype]
+pub enum DataKey {
+    AccessPolicy(Address), // patient -> AccessControl contract
+}
+
+/// Panics unless the patient's AccessControl contract, if they set one,
+/// grants `writer` write access to their labs.
+fn require_policy_write(env: &Env, patient_id: &Address, writer: &Address) {
+    let Some(policy) = env
+        .storage()
+        .persistent()
+        .get::<_, Address>(&DataKey::AccessPolicy(patient_id.clone()))
+    else {
+        return;
+    };
+    if !AccessPolicyClient::new(env, &policy).check_access(
+        writer,
+        &patient_resource(env, patient_id, "labs"),
+        &Scope::Write,
+        &Purpose::Treatment,
+    ) {
+        panic_with_error!(env, Error::Unauthorized);
+    }
+}
+
 #[contract]
 pub struct LabManagementContract;
 
 #[contractimpl]
 impl LabManagementContract {
+    /// Delegate access decisions for a patient's labs to an AccessControl
+    /// contract, or clear the delegation with `None`. While set, ordering
+    /// providers and reporting labs need a write grant on `patient/<patient>/labs`.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        let key = DataKey::AccessPolicy(patient_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccessPolicy(patient_id))
+    }
+
     pub fn order_lab_test(
         env: Env,
         provider_id: Address,
@@ -58,6 +102,7 @@ impl LabManagementContract {
         req: OrderRequest,
     ) -> u64 {
         provider_id.require_auth();
+        require_policy_write(&env, &patient_id, &provider_id);
         let id = env
             .storage()
             .instance()
@@ -102,6 +147,7 @@ impl LabManagementContract {
     ) {
         lab_id.require_auth();
         let mut order: LabOrder = env.storage().persistent().get(&order_id).expect("No Order");
+        require_policy_write(&env, &order.patient_id, &lab_id);
 
         if !qc_passed {
             panic_with_error!(&env, Error::QCFieldFailed);
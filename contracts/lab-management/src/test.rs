@@ -1,5 +1,6 @@
 #![cfg(test)]
 use super::*;
+use access_control::{EntityType, GrantRequest, testutils::setup_access_policy};
 use soroban_sdk::{Address, Env, String, testutils::Address as _, vec};
 
 #[test]
@@ -107,3 +108,53 @@ fn test_fail_assign_nonexistent_order() {
     let lab = Address::generate(&env);
     client.assign_lab(&999, &lab, &0);
 }
+
+#[test]
+fn test_access_policy_governs_orders() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(LabManagementContract, ());
+    let client = LabManagementContractClient::new(&env, &contract_id);
+
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &provider, EntityType::Doctor);
+    client.set_access_policy(&patient, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient), Some(policy_id));
+
+    let req = OrderRequest {
+        test_panel: vec![&env, String::from_str(&env, "2345-7")],
+        priority: Symbol::new(&env, "Routine"),
+        clinical_info_hash: BytesN::from_array(&env, &[0u8; 32]),
+        fasting_required: false,
+        collection_date: None,
+    };
+    assert!(
+        client
+            .try_order_lab_test(&provider, &patient, &req)
+            .is_err()
+    );
+
+    let resource_id = patient_resource(&env, &patient, "labs");
+    policy.grant_access(
+        &patient,
+        &provider,
+        &resource_id,
+        &vec![&env, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    client.order_lab_test(&provider, &patient, &req);
+
+    // Revoking the grant in access-control blocks the next order
+    policy.revoke_access(&patient, &provider, &resource_id);
+    assert!(
+        client
+            .try_order_lab_test(&provider, &patient, &req)
+            .is_err()
+    );
+}
@@ -3,15 +3,46 @@
 mod types;
 mod test;
 
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
 use soroban_sdk::{contract, contractimpl, Env, Address, String, Vec, BytesN};
 use types::{DataKey, Error, ServiceLine, ClaimStatus, ClaimRecord, DenialInfo};
 
+/// Ask the patient's AccessControl contract whether `provider` may bill
+/// against their records. Returns `None` when the patient has not set a policy.
+fn policy_allows(env: &Env, patient_id: &Address, provider: &Address) -> Option<bool> {
+    let policy: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AccessPolicy(patient_id.clone()))?;
+    Some(AccessPolicyClient::new(env, &policy).check_access(
+        provider,
+        &patient_resource(env, patient_id, "claims"),
+        &Scope::Write,
+        &Purpose::Payment,
+    ))
+}
+
 #[contract]
 pub struct MedicalClaimsSystem;
 
 #[contractimpl]
 impl MedicalClaimsSystem {
-    
+    /// Delegate access decisions for a patient's claims to an AccessControl
+    /// contract, or clear the delegation with `None`. While set, providers need
+    /// a write grant for payment on `patient/<patient>/claims` to submit claims.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        let key = DataKey::AccessPolicy(patient_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::AccessPolicy(patient_id))
+    }
+
     pub fn submit_claim(
         env: Env,
         provider_id: Address,
@@ -24,6 +55,9 @@ impl MedicalClaimsSystem {
         total_amount: i128,
     ) -> Result<u64, Error> {
         provider_id.require_auth();
+        if !policy_allows(&env, &patient_id, &provider_id).unwrap_or(true) {
+            return Err(Error::NotAuthorized);
+        }
 
         let count: u64 = env
             .storage()
@@ -1,7 +1,8 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String, BytesN, Vec};
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String, BytesN, Vec};
 
 #[test]
 fn test_full_claim_lifecycle() {
@@ -181,3 +182,54 @@ fn test_appeal_workflow() {
         &BytesN::from_array(&env, &[4; 32]),
     );
 }
+
+#[test]
+fn test_access_policy_gates_claim_submission() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, MedicalClaimsSystem);
+    let client = MedicalClaimsSystemClient::new(&env, &contract_id);
+
+    let provider_id = Address::generate(&env);
+    let patient_id = Address::generate(&env);
+
+    let (policy_id, policy) =
+        setup_access_policy(&env, &patient_id, &provider_id, EntityType::Doctor);
+    client.set_access_policy(&patient_id, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient_id), Some(policy_id));
+
+    let submit = |client: &MedicalClaimsSystemClient| {
+        client.try_submit_claim(
+            &provider_id,
+            &patient_id,
+            &12345,
+            &1690000000,
+            &Vec::new(&env),
+            &Vec::new(&env),
+            &BytesN::from_array(&env, &[0; 32]),
+            &15000,
+        )
+    };
+    assert_eq!(submit(&client), Err(Ok(Error::NotAuthorized)));
+
+    let resource_id = patient_resource(&env, &patient_id, "claims");
+    let mut request = GrantRequest {
+        purpose: Purpose::Treatment,
+        consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+        can_delegate: false,
+        expires_at: 0,
+    };
+    // A treatment grant does not cover billing
+    let scopes = vec![&env, Scope::Write];
+    policy.grant_access(&patient_id, &provider_id, &resource_id, &scopes, &request);
+    assert_eq!(submit(&client), Err(Ok(Error::NotAuthorized)));
+    policy.revoke_access(&patient_id, &provider_id, &resource_id);
+
+    request.purpose = Purpose::Payment;
+    policy.grant_access(&patient_id, &provider_id, &resource_id, &scopes, &request);
+    assert!(submit(&client).is_ok());
+
+    policy.revoke_access(&patient_id, &provider_id, &resource_id);
+    assert_eq!(submit(&client), Err(Ok(Error::NotAuthorized)));
+}
@@ -70,4 +70,5 @@ pub enum DataKey {
     PatientClaims(Address),  // patient_id -> Vec<u64>
     ClaimPayment(u64), // claim_id -> (u64, String) // payment_date, payment_reference
     PatientPayment(u64), // claim_id -> (u64, i128) // payment_date, payment_amount
+    AccessPolicy(Address), // patient_id -> AccessControl contract
 }
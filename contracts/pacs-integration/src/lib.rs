@@ -6,12 +6,30 @@ mod types;
 #[cfg(test)]
 mod test;
 
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
 use soroban_sdk::{
     contract, contractimpl, symbol_short, Address, BytesN, Env, String, Symbol, Vec,
 };
 use storage::*;
 use types::*;
 
+/// Ask the patient's AccessControl contract whether `entity` holds `scope` on
+/// their imaging. Returns `None` when the patient has not set a policy.
+fn policy_allows(
+    env: &Env,
+    patient_id: &Address,
+    entity: &Address,
+    scope: Scope,
+) -> Option<bool> {
+    let policy = load_access_policy(env, patient_id)?;
+    Some(AccessPolicyClient::new(env, &policy).check_access(
+        entity,
+        &patient_resource(env, patient_id, "imaging"),
+        &scope,
+        &Purpose::Treatment,
+    ))
+}
+
 #[contract]
 pub struct PacsContract;
 
@@ -34,6 +52,10 @@ impl PacsContract {
     ) -> Result<u64, Error> {
         ordering_provider.require_auth();
 
+        if policy_allows(&env, &patient_id, &ordering_provider, Scope::Write) == Some(false) {
+            return Err(Error::Unauthorized);
+        }
+
         if study_uid.len() == 0 || body_part.len() == 0 {
             return Err(Error::InvalidInput);
         }
@@ -122,6 +144,10 @@ impl PacsContract {
 
         let mut study = load_study(&env, study_id).ok_or(Error::NotFound)?;
 
+        if policy_allows(&env, &study.patient_id, &radiologist_id, Scope::Write) == Some(false) {
+            return Err(Error::Unauthorized);
+        }
+
         // Only "addendum" may be added once a report already exists.
         if study.has_report && report_type != Symbol::new(&env, "addendum") {
             return Err(Error::ReportAlreadyExists);
@@ -232,6 +258,26 @@ impl PacsContract {
         Ok(())
     }
 
+    /// Patient delegates access decisions for their imaging to an AccessControl
+    /// contract (grants on `patient/<patient_id>/imaging`), or clears it with
+    /// `None`. While set, per-study grants are ignored.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+
+        match &policy {
+            Some(policy) => save_access_policy(&env, &patient_id, policy),
+            None => remove_access_policy(&env, &patient_id),
+        }
+
+        env.events()
+            .publish((symbol_short!("acc_pol"), patient_id), policy);
+    }
+
+    /// Return the AccessControl contract governing a patient's imaging, if any.
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        load_access_policy(&env, &patient_id)
+    }
+
     /// Bundle multiple studies into a portable CD record; returns cd_id.
     pub fn create_imaging_cd(
         env: Env,
@@ -357,22 +403,30 @@ impl PacsContract {
             study.patient_id == viewer_id || study.ordering_provider == viewer_id;
 
         if !is_owner {
-            let now = env.ledger().timestamp();
-            let grants = load_access_list(&env, study_id);
-            let mut allowed = false;
-            for grant in grants.iter() {
-                if grant.viewer_id == viewer_id {
-                    if let Some(exp) = grant.expires_at {
-                        if now > exp {
-                            return Err(Error::AccessExpired);
+            if let Some(allowed) =
+                policy_allows(&env, &study.patient_id, &viewer_id, Scope::Read)
+            {
+                if !allowed {
+                    return Err(Error::Unauthorized);
+                }
+            } else {
+                let now = env.ledger().timestamp();
+                let grants = load_access_list(&env, study_id);
+                let mut allowed = false;
+                for grant in grants.iter() {
+                    if grant.viewer_id == viewer_id {
+                        if let Some(exp) = grant.expires_at {
+                            if now > exp {
+                                return Err(Error::AccessExpired);
+                            }
                         }
+                        allowed = true;
+                        break;
                     }
-                    allowed = true;
-                    break;
                 }
-            }
-            if !allowed {
-                return Err(Error::Unauthorized);
+                if !allowed {
+                    return Err(Error::Unauthorized);
+                }
             }
         }
 
@@ -406,14 +460,21 @@ impl PacsContract {
         let study_ids = load_patient_studies(&env, &patient_id);
         let mut results: Vec<ImagingStudy> = Vec::new(&env);
 
+        // A policy decision covers every study of the patient, so ask once.
+        let policy_access = if requester == patient_id {
+            None
+        } else {
+            policy_allows(&env, &patient_id, &requester, Scope::Read)
+        };
+
         for sid in study_ids.iter() {
             if let Some(study) = load_study(&env, sid) {
                 // --- access check ---
                 let is_owner =
                     study.patient_id == requester || study.ordering_provider == requester;
-                let mut allowed = is_owner;
+                let mut allowed = is_owner || policy_access == Some(true);
 
-                if !allowed {
+                if !allowed && policy_access.is_none() {
                     let grants = load_access_list(&env, sid);
                     for grant in grants.iter() {
                         if grant.viewer_id == requester {
@@ -127,6 +127,26 @@ pub fn save_anonymized_uid(env: &Env, study_id: u64, uid: &String) {
         .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
 }
 
+pub fn save_access_policy(env: &Env, patient_id: &Address, policy: &Address) {
+    let key = DataKey::AccessPolicy(patient_id.clone());
+    env.storage().persistent().set(&key, policy);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+}
+
+pub fn remove_access_policy(env: &Env, patient_id: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::AccessPolicy(patient_id.clone()));
+}
+
+pub fn load_access_policy(env: &Env, patient_id: &Address) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AccessPolicy(patient_id.clone()))
+}
+
 pub fn save_cd_record(env: &Env, record: &CdRecord) {
     let key = DataKey::CdRecord(record.cd_id);
     env.storage().persistent().set(&key, record);
@@ -1,9 +1,11 @@
 #![cfg(test)]
 
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Symbol, Vec};
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest, Purpose, Scope};
+use access_control_interface::patient_resource;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String, Symbol, Vec};
 
 use crate::{PacsContract, PacsContractClient};
-use crate::types::{ComparisonCriteria, ImagingFilters};
+use crate::types::{ComparisonCriteria, Error, ImagingFilters};
 
 // ─── helpers ────────────────────────────────────────────────────────────────
 
@@ -369,3 +371,77 @@ fn search_studies_critical_findings_filter() {
     let results = client.search_imaging_studies(&patient, &patient, &filters);
     assert_eq!(results.len(), 1);
 }
+
+// ─── access policy ──────────────────────────────────────────────────────────
+
+#[test]
+fn access_policy_governs_views() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, patient, provider) = setup(&env);
+    let sid = register_ct_chest(&env, &client, &patient, &provider);
+    let viewer = Address::generate(&env);
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &viewer, EntityType::Doctor);
+
+    client.set_access_policy(&patient, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient), Some(policy_id));
+
+    // Per-study grants no longer apply once the patient opts into the policy
+    client.grant_imaging_access(&sid, &patient, &viewer, &Symbol::new(&env, "view_only"), &None);
+    let result = client.try_track_study_views(&sid, &viewer, &0_u64, &0_u32);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    let resource_id = patient_resource(&env, &patient, "imaging");
+    policy.grant_access(
+        &patient,
+        &viewer,
+        &resource_id,
+        &vec![&env, Scope::Read],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: dummy_hash(&env),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+
+    let filters = ImagingFilters {
+        modality: None,
+        body_part: None,
+        start_date: None,
+        end_date: None,
+        has_critical_findings: None,
+    };
+    client.track_study_views(&sid, &viewer, &1_700_001_000_u64, &30_u32);
+    assert_eq!(client.search_imaging_studies(&patient, &viewer, &filters).len(), 1);
+
+    // Revoking the grant in access-control blocks the downstream reads
+    policy.revoke_access(&patient, &viewer, &resource_id);
+    let result = client.try_track_study_views(&sid, &viewer, &0_u64, &0_u32);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    assert_eq!(client.search_imaging_studies(&patient, &viewer, &filters).len(), 0);
+}
+
+#[test]
+fn access_policy_requires_write_grant_to_register() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, patient, provider) = setup(&env);
+    let (policy_id, _) = setup_access_policy(&env, &patient, &provider, EntityType::Doctor);
+
+    client.set_access_policy(&patient, &Some(policy_id));
+
+    let result = client.try_register_imaging_study(
+        &patient,
+        &provider,
+        &String::from_str(&env, "1.2.840.10008.5.1.4.1.1.2"),
+        &Symbol::new(&env, "CT"),
+        &String::from_str(&env, "Chest"),
+        &1_700_000_000_u64,
+        &String::from_str(&env, "CT Chest w contrast"),
+        &2_u32,
+        &40_u32,
+        &dummy_hash(&env),
+    );
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
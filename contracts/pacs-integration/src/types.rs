@@ -25,6 +25,7 @@ pub enum DataKey {
     QcReview(u64),
     AnonymizedStudy(u64),
     CdRecord(u64),
+    AccessPolicy(Address),
 }
 
 #[contracttype]
@@ -0,0 +1,14 @@
+#![no_std]
+
+//! Page size limits shared by the contracts that expose cursor-based listings.
+
+/// Upper bound on the number of entries covered by a single page.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Clamps a caller supplied page size to `1..=MAX_PAGE_SIZE`.
+pub fn page_limit(limit: u32) -> u32 {
+    if limit == 0 {
+        panic!("Page limit must be positive");
+    }
+    limit.min(MAX_PAGE_SIZE)
+}
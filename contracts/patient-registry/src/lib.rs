@@ -1,9 +1,15 @@
 #![no_std]
 
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
+use doctor_registry_interface::DoctorDirectoryClient;
+use pagination::page_limit;
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Bytes, Env, Map, String, Vec,
+    contract, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env, IntoVal, Map,
+    String, TryFromVal, Val, Vec,
 };
 
+pub use pagination::MAX_PAGE_SIZE;
+
 /// --------------------
 /// Patient Structures
 /// --------------------
@@ -24,7 +30,29 @@ pub struct DoctorData {
     pub name: String,
     pub specialization: String,
     pub certificate_hash: Bytes,
-    pub verified: bool,
+    pub status: DoctorStatus,
+    pub verified_by: Option<Address>, // Institution behind the latest verification
+    pub license_expires_at: u64,      // 0 until verified
+    pub suspended_at: u64,            // 0 unless suspended
+    pub suspended_by: Option<Address>, // Institution behind the current suspension
+    pub revoked_at: u64,              // 0 unless revoked
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DoctorStatus {
+    Pending,
+    Verified,
+    Suspended,
+    Revoked, // Final
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DoctorStatusChange {
+    pub institution: Address,
+    pub status: DoctorStatus,
+    pub timestamp: u64,
 }
 
 /// --------------------
@@ -32,20 +60,517 @@ pub struct DoctorData {
 /// --------------------
 #[contracttype]
 pub enum DataKey {
+    Admin,
     Patient(Address),
     Doctor(Address),
     Institution(Address),
-    MedicalRecords(Address),
-    AuthorizedDoctors(Address),
+    DoctorHistory(Address),  // Doctor -> Vec<DoctorStatusChange>
+    DoctorRegistry,          // DoctorRegistry contract holding doctor profiles
+    MedicalRecords(Address), // Patient -> Vec<u64> of record ids, all versions
+    Record(u64),             // Record id -> MedicalRecord
+    RecordCounter,
+    AccessLogCount(Address),      // Patient -> number of logged reads
+    AccessLogEntry(Address, u32), // (Patient, Index) -> AccessLogEntry
+    AuthorizedDoctors(Address),   // Patient -> Map<Address, DoctorGrant>
+    AccessPolicy(Address),        // Patient -> AccessControl contract
+    MergedInto(Address),          // Merged or recovered wallet -> wallet now holding its data
+    Merge(u64),                   // Merge id -> MergeRecord
+    MergeCounter,
+    PatientMerges(Address), // Wallet -> Vec<u64> of merges it took part in
+    Guardians(Address),     // Patient -> GuardianConfig
+    Recovery(Address),      // Patient -> RecoveryRequest
+    RecoveredTo(Address),   // Recovered wallet -> wallet that replaced it
+}
+
+/// --------------------
+/// Medical Records
+/// --------------------
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecordType {
+    Note,
+    Diagnosis,
+    Procedure,
+    Encounter,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct MedicalRecord {
+    pub id: u64,
+    pub record_type: RecordType,
     pub doctor: Address,
     pub record_hash: Bytes,
     pub description: String,
     pub timestamp: u64,
+    pub version: u32,                     // 1 for the original entry
+    pub supersedes: Option<u64>,          // Record this one amends
+    pub superseded_by: Option<u64>,       // Set once the record is amended
+    pub amendment_reason: Option<String>, // None for the original entry
+}
+
+/// A doctor's authorization on a patient's records.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DoctorGrant {
+    pub record_types: Vec<RecordType>, // Empty for every record type
+    pub granted_at: u64,
+    pub expires_at: u64, // 0 for no expiry
+}
+
+/// Narrows `get_medical_records`. An empty `record_types` or a `None` bound
+/// matches everything; date bounds are inclusive.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordFilter {
+    pub record_types: Vec<RecordType>,
+    pub start: Option<u64>,
+    pub end: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordPage {
+    pub records: Vec<MedicalRecord>,
+    pub next_cursor: Option<u32>, // None once the history is exhausted
+}
+
+/// --------------------
+/// Access Log
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReadKind {
+    Records,
+    History,
+    Record(u64),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessLogEntry {
+    pub reader: Address,
+    pub kind: ReadKind,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessLogPage {
+    pub entries: Vec<AccessLogEntry>,
+    pub next_cursor: Option<u32>,
+}
+
+/// --------------------
+/// Identity Merges
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MergeStatus {
+    Active,
+    Reverted,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MergeRecord {
+    pub id: u64,
+    pub institution: Address,
+    pub from: Address, // Wallet redirected to `into`
+    pub into: Address, // Canonical wallet
+    pub evidence_hash: BytesN<32>,
+    pub moved_records: Vec<u64>,     // Record ids
+    pub added_doctors: Vec<Address>, // Doctors authorized on `from` but not on `into`
+    pub merged_at: u64,
+    pub grace_ends_at: u64,
+    pub status: MergeStatus,
+    pub reverted_by: Option<Address>,
+    pub reverted_at: u64, // 0 while active
+}
+
+/// --------------------
+/// Social Recovery
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianConfig {
+    pub guardians: Vec<Address>,
+    pub threshold: u32, // Guardian approvals required to rotate the wallet
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryRequest {
+    pub new_wallet: Address,
+    pub approvals: Vec<Address>,
+    pub initiated_at: u64,
+    pub executable_at: u64, // The old wallet can veto until then
+}
+
+/// Delay between starting a recovery and completing it, in seconds.
+const RECOVERY_DELAY: u64 = 7 * 24 * 60 * 60;
+
+/// How long a merge can be reverted, in seconds.
+const MERGE_GRACE_PERIOD: u64 = 30 * 24 * 60 * 60;
+
+/// Follows merge redirects to the wallet that currently holds a patient's data.
+fn resolve_patient(env: &Env, wallet: Address) -> Address {
+    let mut current = wallet;
+    while let Some(next) = env
+        .storage()
+        .persistent()
+        .get::<_, Address>(&DataKey::MergedInto(current.clone()))
+    {
+        current = next;
+    }
+    current
+}
+
+/// Follows recovery redirects only, so a merge can find the wallet that
+/// now stands in for its canonical patient.
+fn resolve_recovered(env: &Env, wallet: Address) -> Address {
+    let mut current = wallet;
+    while let Some(next) = env
+        .storage()
+        .persistent()
+        .get::<_, Address>(&DataKey::RecoveredTo(current.clone()))
+    {
+        current = next;
+    }
+    current
+}
+
+/// Only the institution behind a doctor's latest verification, or one the
+/// doctor registry lists as affiliated with them, may act on their status.
+fn require_doctor_overseer(
+    env: &Env,
+    wallet: &Address,
+    doctor: &DoctorData,
+    institution: &Address,
+) {
+    if doctor.verified_by.as_ref() == Some(institution) {
+        return;
+    }
+    if let Some(registry) = doctor_registry(env) {
+        if DoctorDirectoryClient::new(env, &registry).is_affiliated(wallet, institution) {
+            return;
+        }
+    }
+    panic!("Institution does not oversee this doctor");
+}
+
+fn load_admin(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .expect("Contract not initialized")
+}
+
+fn require_not_merged(env: &Env, wallet: &Address) {
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::MergedInto(wallet.clone()))
+    {
+        panic!("Patient identity has been merged");
+    }
+}
+
+fn require_institution(env: &Env, institution: &Address) {
+    if !env
+        .storage()
+        .persistent()
+        .has(&DataKey::Institution(institution.clone()))
+    {
+        panic!("Unauthorized institution");
+    }
+}
+
+fn doctor_registry(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::DoctorRegistry)
+}
+
+fn load_doctor(env: &Env, wallet: &Address) -> DoctorData {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Doctor(wallet.clone()))
+        .expect("Doctor not found")
+}
+
+/// Stores a doctor's new status and appends it to the doctor's trail.
+fn save_doctor_status(env: &Env, wallet: &Address, doctor: &DoctorData, institution: &Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Doctor(wallet.clone()), doctor);
+
+    let key = DataKey::DoctorHistory(wallet.clone());
+    let mut history: Vec<DoctorStatusChange> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    history.push_back(DoctorStatusChange {
+        institution: institution.clone(),
+        status: doctor.status.clone(),
+        timestamp: env.ledger().timestamp(),
+    });
+    env.storage().persistent().set(&key, &history);
+}
+
+/// Panics unless the doctor is verified, not suspended and licensed.
+fn require_active_doctor(env: &Env, wallet: &Address) {
+    let doctor = load_doctor(env, wallet);
+    match doctor.status {
+        DoctorStatus::Verified => {}
+        DoctorStatus::Pending => panic!("Doctor is not verified"),
+        DoctorStatus::Suspended => panic!("Doctor is suspended"),
+        DoctorStatus::Revoked => panic!("Doctor verification has been revoked"),
+    }
+    if env.ledger().timestamp() >= doctor.license_expires_at {
+        panic!("Doctor license has expired");
+    }
+}
+
+fn load_records(env: &Env, patient: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MedicalRecords(patient.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn save_records(env: &Env, patient: &Address, records: &Vec<u64>) {
+    let key = DataKey::MedicalRecords(patient.clone());
+    if records.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, records);
+    }
+}
+
+fn load_record(env: &Env, record_id: u64) -> MedicalRecord {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Record(record_id))
+        .expect("Record not found")
+}
+
+/// Stores a new record under the next id and appends it to the patient's
+/// history.
+fn push_record(env: &Env, patient: &Address, mut record: MedicalRecord) -> u64 {
+    let record_id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::RecordCounter)
+        .unwrap_or(0u64)
+        + 1;
+    env.storage()
+        .persistent()
+        .set(&DataKey::RecordCounter, &record_id);
+
+    record.id = record_id;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Record(record_id), &record);
+
+    let mut records = load_records(env, patient);
+    records.push_back(record_id);
+    save_records(env, patient, &records);
+
+    record_id
+}
+
+/// Whether `record_types` includes `record_type`; an empty list includes all.
+fn covers(record_types: &Vec<RecordType>, record_type: RecordType) -> bool {
+    record_types.is_empty() || record_types.contains(record_type)
+}
+
+const RECORD_TYPES: [RecordType; 4] = [
+    RecordType::Note,
+    RecordType::Diagnosis,
+    RecordType::Procedure,
+    RecordType::Encounter,
+];
+
+/// The access-control path `patient/<patient>/records/<type>` guarding one
+/// record type. A grant on `patient/<patient>/records/*` covers every type.
+fn record_resource(env: &Env, patient: &Address, record_type: RecordType) -> String {
+    let category = match record_type {
+        RecordType::Note => "records/note",
+        RecordType::Diagnosis => "records/diagnosis",
+        RecordType::Procedure => "records/procedure",
+        RecordType::Encounter => "records/encounter",
+    };
+    patient_resource(env, patient, category)
+}
+
+fn is_live(env: &Env, grant: &DoctorGrant) -> bool {
+    grant.expires_at == 0 || env.ledger().timestamp() < grant.expires_at
+}
+
+/// The doctor's local grant on a patient's records, unless it has expired.
+fn live_grant(env: &Env, patient: &Address, doctor: &Address) -> Option<DoctorGrant> {
+    load_authorized_doctors(env, patient)
+        .get(doctor.clone())
+        .filter(|grant| is_live(env, grant))
+}
+
+fn set_grant(env: &Env, patient: &Address, doctor: Address, grant: DoctorGrant) {
+    let mut doctors = load_authorized_doctors(env, patient);
+    doctors.set(doctor, grant);
+    save_authorized_doctors(env, patient, &doctors);
+}
+
+/// Whether a doctor may write a record of the given type, through the
+/// patient's access policy if one is set or the local grants otherwise.
+fn can_write_records(
+    env: &Env,
+    patient: &Address,
+    doctor: &Address,
+    record_type: RecordType,
+) -> bool {
+    let policy_key = DataKey::AccessPolicy(patient.clone());
+    match env.storage().persistent().get::<_, Address>(&policy_key) {
+        Some(policy) => AccessPolicyClient::new(env, &policy).check_access(
+            doctor,
+            &record_resource(env, patient, record_type),
+            &Scope::Write,
+            &Purpose::Treatment,
+        ),
+        None => live_grant(env, patient, doctor)
+            .is_some_and(|grant| covers(&grant.record_types, record_type)),
+    }
+}
+
+fn load_authorized_doctors(env: &Env, patient: &Address) -> Map<Address, DoctorGrant> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AuthorizedDoctors(patient.clone()))
+        .unwrap_or(Map::new(env))
+}
+
+fn save_authorized_doctors(env: &Env, patient: &Address, doctors: &Map<Address, DoctorGrant>) {
+    let key = DataKey::AuthorizedDoctors(patient.clone());
+    if doctors.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, doctors);
+    }
+}
+
+fn load_guardians(env: &Env, patient: &Address) -> GuardianConfig {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Guardians(patient.clone()))
+        .expect("No guardians configured")
+}
+
+fn load_recovery(env: &Env, patient: &Address) -> RecoveryRequest {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Recovery(patient.clone()))
+        .expect("No recovery in progress")
+}
+
+/// Moves a persistent entry from one key to another, if present.
+fn move_entry<V>(env: &Env, from: &DataKey, to: &DataKey)
+where
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    if let Some(value) = env.storage().persistent().get::<_, V>(from) {
+        env.storage().persistent().set(to, &value);
+        env.storage().persistent().remove(from);
+    }
+}
+
+/// Checks that `reader` may read a patient's records: the patient itself, or
+/// a doctor allowed by the patient's access policy or local grants. Returns
+/// the record types the reader may see, empty for all of them.
+fn reader_scope(env: &Env, patient: &Address, reader: &Address) -> Vec<RecordType> {
+    if reader == patient {
+        return Vec::new(env);
+    }
+    let policy_key = DataKey::AccessPolicy(patient.clone());
+    let scope = match env.storage().persistent().get::<_, Address>(&policy_key) {
+        Some(policy) => {
+            let policy = AccessPolicyClient::new(env, &policy);
+            let mut readable = Vec::new(env);
+            for record_type in RECORD_TYPES {
+                if policy.check_access(
+                    reader,
+                    &record_resource(env, patient, record_type),
+                    &Scope::Read,
+                    &Purpose::Treatment,
+                ) {
+                    readable.push_back(record_type);
+                }
+            }
+            match readable.len() {
+                0 => None,
+                n if n as usize == RECORD_TYPES.len() => Some(Vec::new(env)),
+                _ => Some(readable),
+            }
+        }
+        None => live_grant(env, patient, reader).map(|grant| grant.record_types),
+    };
+    scope.expect("Not authorized to read records")
+}
+
+fn log_read(env: &Env, patient: &Address, reader: &Address, kind: ReadKind) {
+    let count_key = DataKey::AccessLogCount(patient.clone());
+    let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+    let entry = AccessLogEntry {
+        reader: reader.clone(),
+        kind,
+        timestamp: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::AccessLogEntry(patient.clone(), count), &entry);
+    env.storage().persistent().set(&count_key, &(count + 1));
+}
+
+/// Loads the records at positions `cursor..cursor + limit` of a patient's
+/// history and keeps those matching `keep`. A page may therefore hold fewer
+/// records than `limit` while `next_cursor` is still set.
+fn record_page(
+    env: &Env,
+    patient: &Address,
+    cursor: u32,
+    limit: u32,
+    keep: impl Fn(&MedicalRecord) -> bool,
+) -> RecordPage {
+    let record_ids = load_records(env, patient);
+    let end = cursor
+        .saturating_add(page_limit(limit))
+        .min(record_ids.len());
+
+    let mut records = Vec::new(env);
+    for position in cursor..end {
+        let record = load_record(env, record_ids.get_unchecked(position));
+        if keep(&record) {
+            records.push_back(record);
+        }
+    }
+
+    RecordPage {
+        records,
+        next_cursor: if end < record_ids.len() {
+            Some(end)
+        } else {
+            None
+        },
+    }
+}
+
+fn push_patient_merge(env: &Env, wallet: &Address, merge_id: u64) {
+    let key = DataKey::PatientMerges(wallet.clone());
+    let mut merges: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    merges.push_back(merge_id);
+    env.storage().persistent().set(&key, &merges);
 }
 
 #[contract]
@@ -53,6 +578,16 @@ pub struct MedicalRegistry;
 
 #[contractimpl]
 impl MedicalRegistry {
+    pub fn initialize(env: Env, admin: Address) {
+        admin.require_auth();
+
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Contract already initialized");
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+    }
+
     // =====================================================
     //                    PATIENT LOGIC
     // =====================================================
@@ -123,12 +658,22 @@ impl MedicalRegistry {
         if env.storage().persistent().has(&key) {
             panic!("Doctor already registered");
         }
+        if let Some(registry) = doctor_registry(&env) {
+            if !DoctorDirectoryClient::new(&env, &registry).has_doctor_profile(&wallet) {
+                panic!("Doctor profile not found");
+            }
+        }
 
         let doctor = DoctorData {
             name,
             specialization,
             certificate_hash,
-            verified: false,
+            status: DoctorStatus::Pending,
+            verified_by: None,
+            license_expires_at: 0,
+            suspended_at: 0,
+            suspended_by: None,
+            revoked_at: 0,
         };
 
         env.storage().persistent().set(&key, &doctor);
@@ -137,43 +682,179 @@ impl MedicalRegistry {
             .publish((symbol_short!("reg_doc"), wallet), symbol_short!("success"));
     }
 
-    pub fn verify_doctor(env: Env, wallet: Address, institution_wallet: Address) {
+    /// Verify a doctor until `license_expires_at`. Also renews the license
+    /// of a verified doctor, and lifts a suspension when called by the
+    /// institution that imposed it.
+    pub fn verify_doctor(
+        env: Env,
+        wallet: Address,
+        institution_wallet: Address,
+        license_expires_at: u64,
+    ) {
         institution_wallet.require_auth();
+        require_institution(&env, &institution_wallet);
 
-        let inst_key = DataKey::Institution(institution_wallet);
-        if !env.storage().persistent().has(&inst_key) {
-            panic!("Unauthorized institution");
+        let mut doctor = load_doctor(&env, &wallet);
+        if doctor.status == DoctorStatus::Revoked {
+            panic!("Doctor verification has been revoked");
+        }
+        if doctor.status == DoctorStatus::Suspended
+            && doctor.suspended_by.as_ref() != Some(&institution_wallet)
+        {
+            panic!("Only the suspending institution can lift the suspension");
+        }
+        if let Some(registry) = doctor_registry(&env) {
+            if !DoctorDirectoryClient::new(&env, &registry)
+                .is_affiliated(&wallet, &institution_wallet)
+            {
+                panic!("Institution is not affiliated with doctor");
+            }
+        }
+        if license_expires_at <= env.ledger().timestamp() {
+            panic!("License already expired");
         }
 
-        let doc_key = DataKey::Doctor(wallet.clone());
-        let mut doctor: DoctorData = env
-            .storage()
-            .persistent()
-            .get(&doc_key)
-            .expect("Doctor not found");
-
-        doctor.verified = true;
-        env.storage().persistent().set(&doc_key, &doctor);
+        doctor.status = DoctorStatus::Verified;
+        doctor.verified_by = Some(institution_wallet.clone());
+        doctor.license_expires_at = license_expires_at;
+        doctor.suspended_at = 0;
+        doctor.suspended_by = None;
+        save_doctor_status(&env, &wallet, &doctor, &institution_wallet);
 
         env.events().publish(
             (symbol_short!("ver_doc"), wallet),
-            symbol_short!("verified"),
+            (institution_wallet, license_expires_at),
         );
     }
 
+    /// Temporarily stop a verified doctor from writing records. Only the
+    /// verifying institution or an affiliated one may do this.
+    pub fn suspend_doctor(env: Env, wallet: Address, institution_wallet: Address) {
+        institution_wallet.require_auth();
+        require_institution(&env, &institution_wallet);
+
+        let mut doctor = load_doctor(&env, &wallet);
+        if doctor.status != DoctorStatus::Verified {
+            panic!("Doctor is not verified");
+        }
+        require_doctor_overseer(&env, &wallet, &doctor, &institution_wallet);
+
+        doctor.status = DoctorStatus::Suspended;
+        doctor.suspended_at = env.ledger().timestamp();
+        doctor.suspended_by = Some(institution_wallet.clone());
+        save_doctor_status(&env, &wallet, &doctor, &institution_wallet);
+
+        env.events()
+            .publish((symbol_short!("sus_doc"), wallet), institution_wallet);
+    }
+
+    /// Lift a suspension on the admin's authority, for when the suspending
+    /// institution can no longer act. The existing license expiry is kept.
+    pub fn reinstate_doctor(env: Env, wallet: Address) {
+        let admin = load_admin(&env);
+        admin.require_auth();
+
+        let mut doctor = load_doctor(&env, &wallet);
+        if doctor.status != DoctorStatus::Suspended {
+            panic!("Doctor is not suspended");
+        }
+
+        doctor.status = DoctorStatus::Verified;
+        doctor.suspended_at = 0;
+        doctor.suspended_by = None;
+        save_doctor_status(&env, &wallet, &doctor, &admin);
+
+        env.events()
+            .publish((symbol_short!("rst_doc"), wallet), admin);
+    }
+
+    /// Permanently withdraw a doctor's verification. Only the verifying
+    /// institution or an affiliated one may do this.
+    pub fn revoke_doctor(env: Env, wallet: Address, institution_wallet: Address) {
+        institution_wallet.require_auth();
+        require_institution(&env, &institution_wallet);
+
+        let mut doctor = load_doctor(&env, &wallet);
+        if doctor.status == DoctorStatus::Revoked {
+            panic!("Doctor verification has been revoked");
+        }
+        require_doctor_overseer(&env, &wallet, &doctor, &institution_wallet);
+
+        doctor.status = DoctorStatus::Revoked;
+        doctor.revoked_at = env.ledger().timestamp();
+        save_doctor_status(&env, &wallet, &doctor, &institution_wallet);
+
+        env.events()
+            .publish((symbol_short!("rev_doc"), wallet), institution_wallet);
+    }
+
+    /// Name and specialization come from the doctor registry when one is
+    /// set.
     pub fn get_doctor(env: Env, wallet: Address) -> DoctorData {
-        let key = DataKey::Doctor(wallet);
+        let mut doctor = load_doctor(&env, &wallet);
+        if let Some(registry) = doctor_registry(&env) {
+            let profile = DoctorDirectoryClient::new(&env, &registry).get_doctor_profile(&wallet);
+            doctor.name = profile.name;
+            doctor.specialization = profile.specialization;
+        }
+        doctor
+    }
+
+    /// Make a `DoctorRegistry` contract the source of truth for doctor
+    /// profiles and institution affiliations. Set once by the admin at
+    /// deployment.
+    ///
+    /// Afterwards doctors need a registry profile to register here, and only
+    /// institutions the doctor is affiliated with can verify them.
+    pub fn set_doctor_registry(env: Env, registry: Address) {
+        load_admin(&env).require_auth();
+
+        if env.storage().instance().has(&DataKey::DoctorRegistry) {
+            panic!("Doctor registry already set");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::DoctorRegistry, &registry);
+
+        env.events().publish((symbol_short!("doc_reg"),), registry);
+    }
+
+    pub fn get_doctor_registry(env: Env) -> Option<Address> {
+        doctor_registry(&env)
+    }
+
+    /// Every status change of a doctor and the institution behind it,
+    /// oldest first.
+    pub fn get_doctor_history(env: Env, wallet: Address) -> Vec<DoctorStatusChange> {
         env.storage()
             .persistent()
-            .get(&key)
-            .expect("Doctor not found")
+            .get(&DataKey::DoctorHistory(wallet))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Whether the doctor is verified, not suspended and licensed.
+    pub fn is_doctor_active(env: Env, wallet: Address) -> bool {
+        match env
+            .storage()
+            .persistent()
+            .get::<_, DoctorData>(&DataKey::Doctor(wallet))
+        {
+            Some(doctor) => {
+                doctor.status == DoctorStatus::Verified
+                    && env.ledger().timestamp() < doctor.license_expires_at
+            }
+            None => false,
+        }
     }
 
     // =====================================================
     //              INSTITUTION MANAGEMENT
     // =====================================================
 
+    /// Admit an institution. Institutions verify, suspend and revoke
+    /// doctors and merge patients, so only the admin may add them.
     pub fn register_institution(env: Env, institution_wallet: Address) {
+        load_admin(&env).require_auth();
         institution_wallet.require_auth();
         let key = DataKey::Institution(institution_wallet);
         env.storage().persistent().set(&key, &true);
@@ -185,89 +866,698 @@ impl MedicalRegistry {
 
     pub fn grant_access(env: Env, patient: Address, doctor: Address) {
         patient.require_auth();
+        require_not_merged(&env, &patient);
 
-        let key = DataKey::AuthorizedDoctors(patient.clone());
-        let mut map: Map<Address, bool> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Map::new(&env));
+        let grant = DoctorGrant {
+            record_types: Vec::new(&env),
+            granted_at: env.ledger().timestamp(),
+            expires_at: 0,
+        };
+        set_grant(&env, &patient, doctor, grant);
+    }
 
-        map.set(doctor, true);
-        env.storage().persistent().set(&key, &map);
+    /// Authorize a doctor for some record types only, or until `expires_at`,
+    /// or both. An empty `record_types` covers every type and an `expires_at`
+    /// of 0 never expires. Replaces any earlier grant to the same doctor.
+    pub fn grant_scoped_access(
+        env: Env,
+        patient: Address,
+        doctor: Address,
+        record_types: Vec<RecordType>,
+        expires_at: u64,
+    ) {
+        patient.require_auth();
+        require_not_merged(&env, &patient);
+
+        let now = env.ledger().timestamp();
+        if expires_at != 0 && expires_at <= now {
+            panic!("Expiry must be in the future");
+        }
+
+        let grant = DoctorGrant {
+            record_types: record_types.clone(),
+            granted_at: now,
+            expires_at,
+        };
+        set_grant(&env, &patient, doctor.clone(), grant);
+
+        env.events().publish(
+            (symbol_short!("grant_doc"), patient, doctor),
+            (record_types, expires_at),
+        );
     }
 
     pub fn revoke_access(env: Env, patient: Address, doctor: Address) {
         patient.require_auth();
+        require_not_merged(&env, &patient);
 
-        let key = DataKey::AuthorizedDoctors(patient.clone());
-        let mut map: Map<Address, bool> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Map::new(&env));
-
-        map.remove(doctor);
-        env.storage().persistent().set(&key, &map);
+        let mut doctors = load_authorized_doctors(&env, &patient);
+        doctors.remove(doctor);
+        save_authorized_doctors(&env, &patient, &doctors);
     }
 
+    /// Doctors holding a grant that has not expired.
     pub fn get_authorized_doctors(env: Env, patient: Address) -> Vec<Address> {
-        let key = DataKey::AuthorizedDoctors(patient);
-        let map: Map<Address, bool> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Map::new(&env));
+        let doctors = load_authorized_doctors(&env, &resolve_patient(&env, patient));
 
-        map.keys()
+        let mut live = Vec::new(&env);
+        for (doctor, grant) in doctors.iter() {
+            if is_live(&env, &grant) {
+                live.push_back(doctor);
+            }
+        }
+        live
+    }
+
+    /// The doctor's grant on a patient's records, unless it has expired.
+    pub fn get_doctor_grant(env: Env, patient: Address, doctor: Address) -> Option<DoctorGrant> {
+        live_grant(&env, &resolve_patient(&env, patient), &doctor)
+    }
+
+    /// Delegate access decisions for a patient's records to an AccessControl
+    /// contract, or clear the delegation with `None`.
+    ///
+    /// While set, doctors are checked against grants on
+    /// `patient/<patient>/records/<type>` for each record type instead of
+    /// `AuthorizedDoctors`.
+    pub fn set_access_policy(env: Env, patient: Address, policy: Option<Address>) {
+        patient.require_auth();
+
+        let key = DataKey::AccessPolicy(patient.clone());
+        match &policy {
+            Some(policy) => env.storage().persistent().set(&key, policy),
+            None => env.storage().persistent().remove(&key),
+        }
+
+        env.events()
+            .publish((symbol_short!("set_pol"), patient), policy);
+    }
+
+    pub fn get_access_policy(env: Env, patient: Address) -> Option<Address> {
+        let key = DataKey::AccessPolicy(patient);
+        env.storage().persistent().get(&key)
     }
 
     pub fn add_medical_record(
         env: Env,
         patient: Address,
         doctor: Address,
+        record_type: RecordType,
         record_hash: Bytes,
         description: String,
-    ) {
+    ) -> u64 {
         doctor.require_auth();
 
-        // Check access
-        let access_key = DataKey::AuthorizedDoctors(patient.clone());
-        let access_map: Map<Address, bool> = env
-            .storage()
-            .persistent()
-            .get(&access_key)
-            .unwrap_or(Map::new(&env));
+        // Records of a merged wallet land on the canonical one
+        let patient = resolve_patient(&env, patient);
 
-        if !access_map.contains_key(doctor.clone()) {
+        if !can_write_records(&env, &patient, &doctor, record_type) {
             panic!("Doctor not authorized");
         }
+        require_active_doctor(&env, &doctor);
 
         let record = MedicalRecord {
-            doctor,
+            id: 0,
+            record_type,
+            doctor: doctor.clone(),
             record_hash,
             description,
             timestamp: env.ledger().timestamp(),
+            version: 1,
+            supersedes: None,
+            superseded_by: None,
+            amendment_reason: None,
         };
+        let record_id = push_record(&env, &patient, record);
+
+        env.events()
+            .publish((symbol_short!("add_rec"), patient, doctor), record_id);
+
+        record_id
+    }
+
+    /// Correct a record by superseding it with a new version.
+    ///
+    /// Only the latest version of a record can be amended. The original
+    /// stays in the history, linked to its replacement.
+    pub fn amend_medical_record(
+        env: Env,
+        patient: Address,
+        doctor: Address,
+        record_id: u64,
+        record_hash: Bytes,
+        description: String,
+        reason: String,
+    ) -> u64 {
+        doctor.require_auth();
+
+        let patient = resolve_patient(&env, patient);
+
+        if !load_records(&env, &patient).contains(record_id) {
+            panic!("Record not found");
+        }
+        let mut original = load_record(&env, record_id);
+        if !can_write_records(&env, &patient, &doctor, original.record_type) {
+            panic!("Doctor not authorized");
+        }
+        require_active_doctor(&env, &doctor);
+        if original.superseded_by.is_some() {
+            panic!("Record already superseded");
+        }
+        if reason.is_empty() {
+            panic!("Amendment reason required");
+        }
+
+        let amendment = MedicalRecord {
+            id: 0,
+            record_type: original.record_type,
+            doctor: doctor.clone(),
+            record_hash,
+            description,
+            timestamp: env.ledger().timestamp(),
+            version: original.version + 1,
+            supersedes: Some(record_id),
+            superseded_by: None,
+            amendment_reason: Some(reason),
+        };
+        let amendment_id = push_record(&env, &patient, amendment);
+
+        original.superseded_by = Some(amendment_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Record(record_id), &original);
 
-        let records_key = DataKey::MedicalRecords(patient.clone());
-        let mut records: Vec<MedicalRecord> = env
+        env.events().publish(
+            (symbol_short!("amend_rec"), patient, doctor),
+            (record_id, amendment_id),
+        );
+
+        amendment_id
+    }
+
+    /// Latest version of each of the patient's records, oldest first.
+    ///
+    /// Readable by the patient and by doctors with read access. Pages walk
+    /// the full history, so pass `next_cursor` back until it is `None`.
+    /// Every read is recorded in the patient's access log.
+    pub fn get_medical_records(
+        env: Env,
+        reader: Address,
+        patient: Address,
+        filter: RecordFilter,
+        cursor: u32,
+        limit: u32,
+    ) -> RecordPage {
+        reader.require_auth();
+
+        let patient = resolve_patient(&env, patient);
+        let scope = reader_scope(&env, &patient, &reader);
+        log_read(&env, &patient, &reader, ReadKind::Records);
+
+        record_page(&env, &patient, cursor, limit, |record| {
+            record.superseded_by.is_none()
+                && covers(&scope, record.record_type)
+                && covers(&filter.record_types, record.record_type)
+                && filter.start.is_none_or(|start| record.timestamp >= start)
+                && filter.end.is_none_or(|end| record.timestamp <= end)
+        })
+    }
+
+    /// Every version of every record, including superseded ones, in the
+    /// order they were written.
+    pub fn get_medical_record_history(
+        env: Env,
+        reader: Address,
+        patient: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> RecordPage {
+        reader.require_auth();
+
+        let patient = resolve_patient(&env, patient);
+        let scope = reader_scope(&env, &patient, &reader);
+        log_read(&env, &patient, &reader, ReadKind::History);
+
+        record_page(&env, &patient, cursor, limit, |record| {
+            covers(&scope, record.record_type)
+        })
+    }
+
+    pub fn get_medical_record(
+        env: Env,
+        reader: Address,
+        patient: Address,
+        record_id: u64,
+    ) -> MedicalRecord {
+        reader.require_auth();
+
+        let patient = resolve_patient(&env, patient);
+        let scope = reader_scope(&env, &patient, &reader);
+        if !load_records(&env, &patient).contains(record_id) {
+            panic!("Record not found");
+        }
+        let record = load_record(&env, record_id);
+        if !covers(&scope, record.record_type) {
+            panic!("Not authorized to read records");
+        }
+        log_read(&env, &patient, &reader, ReadKind::Record(record_id));
+
+        record
+    }
+
+    /// Reads of the patient's records, oldest first. Only the wallet holding
+    /// the patient's data can list them, including the log of a wallet that
+    /// was merged or recovered into it.
+    pub fn get_access_log(env: Env, patient: Address, cursor: u32, limit: u32) -> AccessLogPage {
+        resolve_patient(&env, patient.clone()).require_auth();
+
+        let count: u32 = env
             .storage()
             .persistent()
-            .get(&records_key)
-            .unwrap_or(Vec::new(&env));
+            .get(&DataKey::AccessLogCount(patient.clone()))
+            .unwrap_or(0);
+        let end = cursor.saturating_add(page_limit(limit)).min(count);
+
+        let mut entries = Vec::new(&env);
+        for index in cursor..end {
+            if let Some(entry) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::AccessLogEntry(patient.clone(), index))
+            {
+                entries.push_back(entry);
+            }
+        }
 
-        records.push_back(record);
-        env.storage().persistent().set(&records_key, &records);
+        AccessLogPage {
+            entries,
+            next_cursor: if end < count { Some(end) } else { None },
+        }
     }
 
-    pub fn get_medical_records(env: Env, patient: Address) -> Vec<MedicalRecord> {
-        let key = DataKey::MedicalRecords(patient);
+    // =====================================================
+    //                 IDENTITY LINKING
+    // =====================================================
+
+    /// Merge two wallets of the same person on an institution's attestation.
+    ///
+    /// Records and authorized doctors of `from` move to `into`, and `from`
+    /// is redirected to `into`. Both wallets co-sign, since institution
+    /// registration is open and an institution alone must not be able to
+    /// fold someone else's identity into a wallet it controls. A wallet whose
+    /// keys are lost is moved with guardian recovery instead. The merge can be
+    /// reverted with `revert_merge` during the grace period.
+    pub fn merge_patients(
+        env: Env,
+        institution: Address,
+        from: Address,
+        into: Address,
+        evidence_hash: BytesN<32>,
+    ) -> u64 {
+        institution.require_auth();
+        from.require_auth();
+        into.require_auth();
+
+        require_institution(&env, &institution);
+        if from == into {
+            panic!("Cannot merge a patient into itself");
+        }
+        for wallet in [&from, &into] {
+            if !env
+                .storage()
+                .persistent()
+                .has(&DataKey::Patient(wallet.clone()))
+            {
+                panic!("Patient not found");
+            }
+            require_not_merged(&env, wallet);
+        }
+
+        // Move records
+        let moved_records = load_records(&env, &from);
+        let mut records = load_records(&env, &into);
+        records.append(&moved_records);
+        save_records(&env, &into, &records);
+        save_records(&env, &from, &Vec::new(&env));
+
+        // Move authorized doctors, remembering which ones are new to `into`
+        let mut doctors = load_authorized_doctors(&env, &into);
+        let mut added_doctors = Vec::new(&env);
+        for (doctor, grant) in load_authorized_doctors(&env, &from).iter() {
+            if !doctors.contains_key(doctor.clone()) {
+                doctors.set(doctor.clone(), grant);
+                added_doctors.push_back(doctor);
+            }
+        }
+        save_authorized_doctors(&env, &into, &doctors);
+        save_authorized_doctors(&env, &from, &Map::new(&env));
+
+        let merge_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MergeCounter)
+            .unwrap_or(0u64)
+            + 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::MergeCounter, &merge_id);
+
+        let merged_at = env.ledger().timestamp();
+        let merge = MergeRecord {
+            id: merge_id,
+            institution: institution.clone(),
+            from: from.clone(),
+            into: into.clone(),
+            evidence_hash,
+            moved_records,
+            added_doctors,
+            merged_at,
+            grace_ends_at: merged_at + MERGE_GRACE_PERIOD,
+            status: MergeStatus::Active,
+            reverted_by: None,
+            reverted_at: 0,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Merge(merge_id), &merge);
         env.storage()
+            .persistent()
+            .set(&DataKey::MergedInto(from.clone()), &into);
+        push_patient_merge(&env, &from, merge_id);
+        push_patient_merge(&env, &into, merge_id);
+
+        env.events().publish(
+            (symbol_short!("merge"), from, into),
+            (merge_id, institution),
+        );
+
+        merge_id
+    }
+
+    /// Undo a merge during its grace period.
+    ///
+    /// Moved records and doctors go back to the merged wallet and the
+    /// redirect is removed. Callable by the attesting institution or either
+    /// patient wallet. If the canonical wallet has since been recovered, the
+    /// wallet that replaced it stands in for it and the lost wallet can no
+    /// longer revert.
+    pub fn revert_merge(env: Env, caller: Address, merge_id: u64) {
+        caller.require_auth();
+
+        let key = DataKey::Merge(merge_id);
+        let mut merge: MergeRecord = env
+            .storage()
             .persistent()
             .get(&key)
+            .expect("Merge not found");
+
+        let into = resolve_recovered(&env, merge.into.clone());
+        if caller != merge.institution && caller != merge.from && caller != into {
+            panic!("Not authorized to revert this merge");
+        }
+        if merge.status != MergeStatus::Active {
+            panic!("Merge already reverted");
+        }
+        let now = env.ledger().timestamp();
+        if now > merge.grace_ends_at {
+            panic!("Grace period has ended");
+        }
+        require_not_merged(&env, &into);
+
+        // Take the moved records back out of the canonical history, along
+        // with any amendments made to them since the merge
+        let mut returning = merge.moved_records.clone();
+        let mut kept = Vec::new(&env);
+        for record_id in load_records(&env, &into).iter() {
+            if returning.contains(record_id) {
+                continue;
+            }
+            match load_record(&env, record_id).supersedes {
+                Some(original) if returning.contains(original) => {
+                    returning.push_back(record_id);
+                }
+                _ => kept.push_back(record_id),
+            }
+        }
+        save_records(&env, &into, &kept);
+        save_records(&env, &merge.from, &returning);
+
+        // Doctors added to the canonical wallet go back; any the canonical
+        // patient revoked since the merge stay revoked
+        let mut doctors = load_authorized_doctors(&env, &into);
+        let mut restored = Map::new(&env);
+        for doctor in merge.added_doctors.iter() {
+            if let Some(grant) = doctors.get(doctor.clone()) {
+                doctors.remove(doctor.clone());
+                restored.set(doctor, grant);
+            }
+        }
+        save_authorized_doctors(&env, &into, &doctors);
+        save_authorized_doctors(&env, &merge.from, &restored);
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::MergedInto(merge.from.clone()));
+
+        merge.status = MergeStatus::Reverted;
+        merge.reverted_by = Some(caller.clone());
+        merge.reverted_at = now;
+        env.storage().persistent().set(&key, &merge);
+
+        env.events().publish(
+            (symbol_short!("unmerge"), merge.from, merge.into),
+            (merge_id, caller),
+        );
+    }
+
+    pub fn get_merge(env: Env, merge_id: u64) -> MergeRecord {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Merge(merge_id))
+            .expect("Merge not found")
+    }
+
+    /// Ids of every merge a wallet took part in, oldest first.
+    pub fn get_patient_merges(env: Env, wallet: Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PatientMerges(wallet))
             .unwrap_or(Vec::new(&env))
     }
+
+    /// The wallet that currently holds a patient's data.
+    pub fn get_canonical_patient(env: Env, wallet: Address) -> Address {
+        resolve_patient(&env, wallet)
+    }
+
+    // =====================================================
+    //                 SOCIAL RECOVERY
+    // =====================================================
+
+    /// Nominate the guardians who can rotate the patient's wallet, `threshold`
+    /// of which must approve a recovery. Replaces any earlier nomination.
+    pub fn set_guardians(env: Env, patient: Address, guardians: Vec<Address>, threshold: u32) {
+        patient.require_auth();
+        require_not_merged(&env, &patient);
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Patient(patient.clone()))
+        {
+            panic!("Patient not found");
+        }
+        if threshold == 0 || threshold > guardians.len() {
+            panic!("Invalid guardian threshold");
+        }
+        for (i, guardian) in guardians.iter().enumerate() {
+            if guardian == patient {
+                panic!("Patient cannot be their own guardian");
+            }
+            if guardians.first_index_of(&guardian) != Some(i as u32) {
+                panic!("Duplicate guardian");
+            }
+        }
+
+        let config = GuardianConfig {
+            guardians,
+            threshold,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Guardians(patient.clone()), &config);
+
+        env.events()
+            .publish((symbol_short!("guardians"), patient), threshold);
+    }
+
+    pub fn get_guardians(env: Env, patient: Address) -> GuardianConfig {
+        load_guardians(&env, &patient)
+    }
+
+    /// A guardian starts moving the patient's data to `new_wallet`.
+    pub fn initiate_recovery(env: Env, guardian: Address, patient: Address, new_wallet: Address) {
+        guardian.require_auth();
+        require_not_merged(&env, &patient);
+
+        let config = load_guardians(&env, &patient);
+        if !config.guardians.contains(&guardian) {
+            panic!("Not a guardian");
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Recovery(patient.clone()))
+        {
+            panic!("Recovery already in progress");
+        }
+        if new_wallet == patient
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::Patient(new_wallet.clone()))
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::MergedInto(new_wallet.clone()))
+        {
+            panic!("New wallet is already in use");
+        }
+
+        let initiated_at = env.ledger().timestamp();
+        let request = RecoveryRequest {
+            new_wallet: new_wallet.clone(),
+            approvals: Vec::from_array(&env, [guardian.clone()]),
+            initiated_at,
+            executable_at: initiated_at + RECOVERY_DELAY,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Recovery(patient.clone()), &request);
+
+        env.events()
+            .publish((symbol_short!("rec_init"), patient), (guardian, new_wallet));
+    }
+
+    pub fn approve_recovery(env: Env, guardian: Address, patient: Address) {
+        guardian.require_auth();
+
+        let config = load_guardians(&env, &patient);
+        if !config.guardians.contains(&guardian) {
+            panic!("Not a guardian");
+        }
+
+        let mut request = load_recovery(&env, &patient);
+        if request.approvals.contains(&guardian) {
+            panic!("Guardian already approved");
+        }
+        request.approvals.push_back(guardian.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Recovery(patient.clone()), &request);
+
+        env.events()
+            .publish((symbol_short!("rec_appr"), patient), guardian);
+    }
+
+    /// The current wallet cancels a recovery it did not ask for.
+    pub fn veto_recovery(env: Env, patient: Address) {
+        patient.require_auth();
+
+        let request = load_recovery(&env, &patient);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Recovery(patient.clone()));
+
+        env.events()
+            .publish((symbol_short!("rec_veto"), patient), request.new_wallet);
+    }
+
+    /// Move the patient's data, grants and record history to the new wallet
+    /// once enough guardians approved and the veto window has passed. The
+    /// new wallet signs to prove it is controlled by the patient.
+    pub fn complete_recovery(env: Env, patient: Address) {
+        let request = load_recovery(&env, &patient);
+        let new_wallet = request.new_wallet;
+        new_wallet.require_auth();
+
+        let config = load_guardians(&env, &patient);
+        let approvals = request
+            .approvals
+            .iter()
+            .filter(|guardian| config.guardians.contains(guardian))
+            .count() as u32;
+        if approvals < config.threshold {
+            panic!("Not enough guardian approvals");
+        }
+        if env.ledger().timestamp() < request.executable_at {
+            panic!("Recovery is time-locked");
+        }
+
+        move_entry::<PatientData>(
+            &env,
+            &DataKey::Patient(patient.clone()),
+            &DataKey::Patient(new_wallet.clone()),
+        );
+        move_entry::<Map<Address, DoctorGrant>>(
+            &env,
+            &DataKey::AuthorizedDoctors(patient.clone()),
+            &DataKey::AuthorizedDoctors(new_wallet.clone()),
+        );
+        move_entry::<Vec<u64>>(
+            &env,
+            &DataKey::MedicalRecords(patient.clone()),
+            &DataKey::MedicalRecords(new_wallet.clone()),
+        );
+        move_entry::<Address>(
+            &env,
+            &DataKey::AccessPolicy(patient.clone()),
+            &DataKey::AccessPolicy(new_wallet.clone()),
+        );
+        move_entry::<GuardianConfig>(
+            &env,
+            &DataKey::Guardians(patient.clone()),
+            &DataKey::Guardians(new_wallet.clone()),
+        );
+        move_entry::<Vec<u64>>(
+            &env,
+            &DataKey::PatientMerges(patient.clone()),
+            &DataKey::PatientMerges(new_wallet.clone()),
+        );
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Recovery(patient.clone()));
+        env.storage()
+            .persistent()
+            .set(&DataKey::MergedInto(patient.clone()), &new_wallet);
+        env.storage()
+            .persistent()
+            .set(&DataKey::RecoveredTo(patient.clone()), &new_wallet);
+
+        // Let the access policy re-key grants on the patient's paths. A
+        // policy that rejects the call must not block the recovery itself.
+        let policy: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AccessPolicy(new_wallet.clone()));
+        if let Some(policy) = policy {
+            let rekeyed = AccessPolicyClient::new(&env, &policy)
+                .try_record_recovery(&patient, &new_wallet)
+                .is_ok();
+            env.events().publish(
+                (symbol_short!("rec_pol"), new_wallet.clone()),
+                (policy, rekeyed),
+            );
+        }
+
+        env.events()
+            .publish((symbol_short!("recovered"), patient), new_wallet);
+    }
+
+    pub fn get_recovery(env: Env, patient: Address) -> Option<RecoveryRequest> {
+        env.storage().persistent().get(&DataKey::Recovery(patient))
+    }
 }
 #[cfg(test)]
 mod test;
@@ -1,7 +1,28 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Bytes, Env, String};
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest};
+use soroban_sdk::{
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    vec, Address, Bytes, BytesN, Env, String,
+};
+
+const LICENSE_EXPIRY: u64 = 365 * 24 * 60 * 60;
+
+/// Registers a doctor and has a fresh institution verify them.
+fn verified_doctor(env: &Env, client: &MedicalRegistryClient) -> Address {
+    let doctor = Address::generate(env);
+    let institution = Address::generate(env);
+    client.register_doctor(
+        &doctor,
+        &String::from_str(env, "Dr. Alice"),
+        &String::from_str(env, "General Practice"),
+        &Bytes::from_array(env, &[1, 2, 3, 4]),
+    );
+    client.register_institution(&institution);
+    client.verify_doctor(&doctor, &institution, &LICENSE_EXPIRY);
+    doctor
+}
 
 /// ------------------------------------------------
 /// PATIENT TESTS
@@ -98,7 +119,7 @@ fn test_register_and_get_doctor() {
     assert_eq!(doctor.name, name);
     assert_eq!(doctor.specialization, specialization);
     assert_eq!(doctor.certificate_hash, cert_hash);
-    assert_eq!(doctor.verified, false);
+    assert_eq!(doctor.status, DoctorStatus::Pending);
 }
 
 #[test]
@@ -115,6 +136,7 @@ fn test_register_institution_and_verify_doctor() {
     let cert_hash = Bytes::from_array(&env, &[9, 9, 9]);
 
     env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
 
     // Register doctor
     client.register_doctor(&doctor_wallet, &name, &specialization, &cert_hash);
@@ -123,10 +145,13 @@ fn test_register_institution_and_verify_doctor() {
     client.register_institution(&institution_wallet);
 
     // Verify doctor
-    client.verify_doctor(&doctor_wallet, &institution_wallet);
+    client.verify_doctor(&doctor_wallet, &institution_wallet, &LICENSE_EXPIRY);
 
     let doctor = client.get_doctor(&doctor_wallet);
-    assert_eq!(doctor.verified, true);
+    assert_eq!(doctor.status, DoctorStatus::Verified);
+    assert_eq!(doctor.verified_by, Some(institution_wallet));
+    assert_eq!(doctor.license_expires_at, LICENSE_EXPIRY);
+    assert!(client.is_doctor_active(&doctor_wallet));
 }
 
 #[test]
@@ -148,7 +173,30 @@ fn test_verify_doctor_by_unregistered_institution_should_fail() {
     client.register_doctor(&doctor_wallet, &name, &specialization, &cert_hash);
 
     // This should panic
-    client.verify_doctor(&doctor_wallet, &fake_institution);
+    client.verify_doctor(&doctor_wallet, &fake_institution, &LICENSE_EXPIRY);
+}
+
+fn all_records(env: &Env) -> RecordFilter {
+    RecordFilter {
+        record_types: Vec::new(env),
+        start: None,
+        end: None,
+    }
+}
+
+/// Reads every current record as the wallet holding the patient's data.
+fn records_of(env: &Env, client: &MedicalRegistryClient, patient: &Address) -> Vec<MedicalRecord> {
+    let reader = client.get_canonical_patient(patient);
+    client
+        .get_medical_records(&reader, patient, &all_records(env), &0, &MAX_PAGE_SIZE)
+        .records
+}
+
+fn history_of(client: &MedicalRegistryClient, patient: &Address) -> Vec<MedicalRecord> {
+    let reader = client.get_canonical_patient(patient);
+    let page = client.get_medical_record_history(&reader, patient, &0, &MAX_PAGE_SIZE);
+    assert!(page.next_cursor.is_none());
+    page.records
 }
 
 #[test]
@@ -157,18 +205,19 @@ fn test_grant_access_and_add_medical_record() {
     let contract_id = env.register(MedicalRegistry, ());
     let client = MedicalRegistryClient::new(&env, &contract_id);
 
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
     let patient = Address::generate(&env);
-    let doctor = Address::generate(&env);
+    let doctor = verified_doctor(&env, &client);
 
     let hash = Bytes::from_array(&env, &[1, 2, 3]);
     let desc = String::from_str(&env, "Blood test results");
 
-    env.mock_all_auths();
-
     client.grant_access(&patient, &doctor);
-    client.add_medical_record(&patient, &doctor, &hash, &desc);
+    client.add_medical_record(&patient, &doctor, &RecordType::Note, &hash, &desc);
 
-    let records = client.get_medical_records(&patient);
+    let records = records_of(&env, &client, &patient);
     assert_eq!(records.len(), 1);
 
     let record = records.get(0).unwrap();
@@ -191,7 +240,7 @@ fn test_unauthorized_doctor_cannot_add_record() {
 
     env.mock_all_auths();
 
-    client.add_medical_record(&patient, &doctor, &hash, &desc);
+    client.add_medical_record(&patient, &doctor, &RecordType::Note, &hash, &desc);
 }
 
 #[test]
@@ -211,3 +260,1411 @@ fn test_revoke_access() {
     let doctors = client.get_authorized_doctors(&patient);
     assert_eq!(doctors.len(), 0);
 }
+
+/// ------------------------------------------------
+/// ACCESS POLICY TESTS
+/// ------------------------------------------------
+
+#[test]
+fn test_access_policy_governs_record_writes() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let patient = Address::generate(&env);
+    let doctor = verified_doctor(&env, &client);
+
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &doctor, EntityType::Doctor);
+    client.set_access_policy(&patient, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient), Some(policy_id));
+
+    // A wildcard grant covers the records of every clinical contract
+    let resource_id = patient_resource(&env, &patient, "*");
+    policy.grant_access(
+        &patient,
+        &doctor,
+        &resource_id,
+        &vec![
+            &env,
+            access_control::Scope::Read,
+            access_control::Scope::Write,
+        ],
+        &GrantRequest {
+            purpose: access_control::Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+
+    let hash = Bytes::from_array(&env, &[1, 2, 3]);
+    let desc = String::from_str(&env, "Blood test results");
+    client.add_medical_record(&patient, &doctor, &RecordType::Note, &hash, &desc);
+    assert_eq!(records_of(&env, &client, &patient).len(), 1);
+    let history = client.get_medical_record_history(&doctor, &patient, &0, &MAX_PAGE_SIZE);
+    assert_eq!(history.records.len(), 1);
+
+    // Revoking the grant in access-control blocks further writes and reads
+    policy.revoke_access(&patient, &doctor, &resource_id);
+    assert!(client
+        .try_add_medical_record(&patient, &doctor, &RecordType::Note, &hash, &desc)
+        .is_err());
+    assert!(client
+        .try_get_medical_record_history(&doctor, &patient, &0, &MAX_PAGE_SIZE)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "Doctor not authorized")]
+fn test_access_policy_ignores_local_grants() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    let doctor = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let (policy_id, _) = setup_access_policy(&env, &patient, &doctor, EntityType::Doctor);
+    client.grant_access(&patient, &doctor);
+    client.set_access_policy(&patient, &Some(policy_id));
+
+    client.add_medical_record(
+        &patient,
+        &doctor,
+        &RecordType::Note,
+        &Bytes::from_array(&env, &[1, 2, 3]),
+        &String::from_str(&env, "Blood test results"),
+    );
+}
+
+#[test]
+fn test_access_policy_scopes_grants_by_record_type() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let patient = Address::generate(&env);
+    let doctor = verified_doctor(&env, &client);
+
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &doctor, EntityType::Doctor);
+    client.set_access_policy(&patient, &Some(policy_id));
+
+    let request = GrantRequest {
+        purpose: access_control::Purpose::Treatment,
+        consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+        can_delegate: false,
+        expires_at: 0,
+    };
+    let scopes = vec![
+        &env,
+        access_control::Scope::Read,
+        access_control::Scope::Write,
+    ];
+    let hash = Bytes::from_array(&env, &[1, 2, 3]);
+    let desc = String::from_str(&env, "Visit");
+
+    // A grant on records/* covers every record type
+    let all_types = patient_resource(&env, &patient, "records/*");
+    policy.grant_access(&patient, &doctor, &all_types, &scopes, &request);
+    client.add_medical_record(&patient, &doctor, &RecordType::Note, &hash, &desc);
+    client.add_medical_record(&patient, &doctor, &RecordType::Diagnosis, &hash, &desc);
+    policy.revoke_access(&patient, &doctor, &all_types);
+
+    // A grant on one record type leaves the others out of reach
+    let diagnoses = patient_resource(&env, &patient, "records/diagnosis");
+    policy.grant_access(&patient, &doctor, &diagnoses, &scopes, &request);
+    client.add_medical_record(&patient, &doctor, &RecordType::Diagnosis, &hash, &desc);
+    assert!(client
+        .try_add_medical_record(&patient, &doctor, &RecordType::Note, &hash, &desc)
+        .is_err());
+    assert!(client
+        .try_add_medical_record(&patient, &doctor, &RecordType::Procedure, &hash, &desc)
+        .is_err());
+
+    let visible = client
+        .get_medical_records(&doctor, &patient, &all_records(&env), &0, &MAX_PAGE_SIZE)
+        .records;
+    assert_eq!(visible.len(), 2);
+    assert!(visible
+        .iter()
+        .all(|record| record.record_type == RecordType::Diagnosis));
+
+    let note = records_of(&env, &client, &patient)
+        .iter()
+        .find(|record| record.record_type == RecordType::Note)
+        .unwrap();
+    assert!(client
+        .try_get_medical_record(&doctor, &patient, &note.id)
+        .is_err());
+}
+
+fn setup_duplicate_patient(
+    env: &Env,
+    client: &MedicalRegistryClient,
+) -> (Address, Address, Address, Address) {
+    let old_wallet = Address::generate(env);
+    let new_wallet = Address::generate(env);
+    let institution = Address::generate(env);
+    let doctor = verified_doctor(env, client);
+
+    for wallet in [&old_wallet, &new_wallet] {
+        client.register_patient(
+            wallet,
+            &String::from_str(env, "John Doe"),
+            &631152000,
+            &String::from_str(env, "ipfs://data"),
+        );
+    }
+    client.register_institution(&institution);
+
+    client.grant_access(&old_wallet, &doctor);
+    client.add_medical_record(
+        &old_wallet,
+        &doctor,
+        &RecordType::Note,
+        &Bytes::from_array(env, &[1, 2, 3]),
+        &String::from_str(env, "Blood test results"),
+    );
+
+    (old_wallet, new_wallet, institution, doctor)
+}
+
+/// ------------------------------------------------
+/// IDENTITY LINKING TESTS
+/// ------------------------------------------------
+
+#[test]
+fn test_merge_patients_consolidates_records() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (old_wallet, new_wallet, institution, doctor) = setup_duplicate_patient(&env, &client);
+
+    let merge_id = client.merge_patients(
+        &institution,
+        &old_wallet,
+        &new_wallet,
+        &BytesN::from_array(&env, &[5u8; 32]),
+    );
+
+    assert_eq!(client.get_canonical_patient(&old_wallet), new_wallet);
+    assert_eq!(records_of(&env, &client, &new_wallet).len(), 1);
+    assert_eq!(records_of(&env, &client, &old_wallet).len(), 1);
+    assert_eq!(
+        client.get_authorized_doctors(&new_wallet),
+        vec![&env, doctor.clone()]
+    );
+
+    // Writes addressed to the old wallet land on the canonical one
+    client.add_medical_record(
+        &old_wallet,
+        &doctor,
+        &RecordType::Note,
+        &Bytes::from_array(&env, &[4, 5, 6]),
+        &String::from_str(&env, "Follow-up"),
+    );
+    assert_eq!(records_of(&env, &client, &new_wallet).len(), 2);
+
+    let merge = client.get_merge(&merge_id);
+    assert_eq!(merge.status, MergeStatus::Active);
+    assert_eq!(merge.moved_records.len(), 1);
+    assert_eq!(client.get_patient_merges(&old_wallet), vec![&env, merge_id]);
+    assert_eq!(client.get_patient_merges(&new_wallet), vec![&env, merge_id]);
+}
+
+#[test]
+fn test_revert_merge_within_grace_period() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (old_wallet, new_wallet, institution, doctor) = setup_duplicate_patient(&env, &client);
+    let merge_id = client.merge_patients(
+        &institution,
+        &old_wallet,
+        &new_wallet,
+        &BytesN::from_array(&env, &[5u8; 32]),
+    );
+
+    client.revert_merge(&new_wallet, &merge_id);
+
+    assert_eq!(client.get_canonical_patient(&old_wallet), old_wallet);
+    assert_eq!(records_of(&env, &client, &old_wallet).len(), 1);
+    assert_eq!(records_of(&env, &client, &new_wallet).len(), 0);
+    assert_eq!(
+        client.get_authorized_doctors(&old_wallet),
+        vec![&env, doctor]
+    );
+    assert_eq!(client.get_authorized_doctors(&new_wallet).len(), 0);
+
+    let merge = client.get_merge(&merge_id);
+    assert_eq!(merge.status, MergeStatus::Reverted);
+    assert_eq!(merge.reverted_by, Some(new_wallet));
+}
+
+#[test]
+#[should_panic(expected = "Grace period has ended")]
+fn test_revert_merge_after_grace_period() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (old_wallet, new_wallet, institution, _) = setup_duplicate_patient(&env, &client);
+    let merge_id = client.merge_patients(
+        &institution,
+        &old_wallet,
+        &new_wallet,
+        &BytesN::from_array(&env, &[5u8; 32]),
+    );
+
+    let grace_ends_at = client.get_merge(&merge_id).grace_ends_at;
+    env.ledger().with_mut(|li| li.timestamp = grace_ends_at + 1);
+
+    client.revert_merge(&institution, &merge_id);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized institution")]
+fn test_merge_by_unregistered_institution_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (old_wallet, new_wallet, _, _) = setup_duplicate_patient(&env, &client);
+
+    client.merge_patients(
+        &Address::generate(&env),
+        &old_wallet,
+        &new_wallet,
+        &BytesN::from_array(&env, &[5u8; 32]),
+    );
+}
+
+#[test]
+fn test_hostile_institution_cannot_merge_victim() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    // A registered institution controlling a patient wallet of its own
+    let (victim, _, _, _) = setup_duplicate_patient(&env, &client);
+    let institution = Address::generate(&env);
+    let own_wallet = Address::generate(&env);
+    client.register_institution(&institution);
+    client.register_patient(
+        &own_wallet,
+        &String::from_str(&env, "Mallory"),
+        &631152000,
+        &String::from_str(&env, "ipfs://data"),
+    );
+
+    // Without the victim's signature the merge is rejected
+    let evidence = BytesN::from_array(&env, &[5u8; 32]);
+    let invoke = MockAuthInvoke {
+        contract: &contract_id,
+        fn_name: "merge_patients",
+        args: (&institution, &victim, &own_wallet, &evidence).into_val(&env),
+        sub_invokes: &[],
+    };
+    let result = client
+        .mock_auths(&[
+            MockAuth {
+                address: &institution,
+                invoke: &invoke,
+            },
+            MockAuth {
+                address: &own_wallet,
+                invoke: &invoke,
+            },
+        ])
+        .try_merge_patients(&institution, &victim, &own_wallet, &evidence);
+    assert!(result.is_err());
+    assert_eq!(client.get_canonical_patient(&victim), victim);
+    assert_eq!(records_of(&env, &client, &victim).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Patient identity has been merged")]
+fn test_merged_wallet_cannot_grant_access() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (old_wallet, new_wallet, institution, _) = setup_duplicate_patient(&env, &client);
+    client.merge_patients(
+        &institution,
+        &old_wallet,
+        &new_wallet,
+        &BytesN::from_array(&env, &[5u8; 32]),
+    );
+
+    client.grant_access(&old_wallet, &Address::generate(&env));
+}
+
+fn setup_guardians(
+    env: &Env,
+    client: &MedicalRegistryClient,
+) -> (Address, Address, Address, Address, Address) {
+    let patient = Address::generate(env);
+    let doctor = verified_doctor(env, client);
+    let guardian_a = Address::generate(env);
+    let guardian_b = Address::generate(env);
+    let new_wallet = Address::generate(env);
+
+    client.register_patient(
+        &patient,
+        &String::from_str(env, "John Doe"),
+        &631152000,
+        &String::from_str(env, "ipfs://data"),
+    );
+    client.grant_access(&patient, &doctor);
+    client.add_medical_record(
+        &patient,
+        &doctor,
+        &RecordType::Note,
+        &Bytes::from_array(env, &[1, 2, 3]),
+        &String::from_str(env, "Blood test results"),
+    );
+
+    let guardians = vec![env, guardian_a.clone(), guardian_b.clone()];
+    client.set_guardians(&patient, &guardians, &2);
+
+    (patient, doctor, guardian_a, guardian_b, new_wallet)
+}
+
+/// ------------------------------------------------
+/// SOCIAL RECOVERY TESTS
+/// ------------------------------------------------
+
+#[test]
+fn test_guardian_recovery_moves_patient_data() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, doctor, guardian_a, guardian_b, new_wallet) = setup_guardians(&env, &client);
+
+    client.initiate_recovery(&guardian_a, &patient, &new_wallet);
+    client.approve_recovery(&guardian_b, &patient);
+
+    let request = client.get_recovery(&patient).unwrap();
+    assert_eq!(request.approvals.len(), 2);
+    env.ledger()
+        .with_mut(|li| li.timestamp = request.executable_at);
+
+    client.complete_recovery(&patient);
+
+    assert!(client.get_recovery(&patient).is_none());
+    assert!(!client.is_patient_registered(&patient));
+    assert_eq!(
+        client.get_patient(&new_wallet).name,
+        String::from_str(&env, "John Doe")
+    );
+    assert_eq!(records_of(&env, &client, &new_wallet).len(), 1);
+    assert_eq!(
+        client.get_authorized_doctors(&new_wallet),
+        vec![&env, doctor]
+    );
+    assert_eq!(client.get_guardians(&new_wallet).threshold, 2);
+    assert_eq!(client.get_canonical_patient(&patient), new_wallet);
+}
+
+#[test]
+fn test_recovery_rekeys_access_policy_grants() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, doctor, guardian_a, guardian_b, new_wallet) = setup_guardians(&env, &client);
+
+    // The access policy trusts this registry to report recoveries
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &doctor, EntityType::Doctor);
+    let admin = policy.get_council().members.get(0).unwrap();
+    let proposal = policy.propose_admin_action(
+        &admin,
+        &access_control::AdminAction::SetIdentityRegistry(contract_id.clone()),
+    );
+    policy.execute_proposal(&admin, &proposal);
+    client.set_access_policy(&patient, &Some(policy_id));
+
+    policy.grant_access(
+        &patient,
+        &doctor,
+        &patient_resource(&env, &patient, "records/*"),
+        &vec![&env, access_control::Scope::Read],
+        &GrantRequest {
+            purpose: access_control::Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+
+    client.initiate_recovery(&guardian_a, &patient, &new_wallet);
+    client.approve_recovery(&guardian_b, &patient);
+    let request = client.get_recovery(&patient).unwrap();
+    env.ledger()
+        .with_mut(|li| li.timestamp = request.executable_at);
+    client.complete_recovery(&patient);
+    while policy.migrate_recovered_grants(&patient, &MAX_PAGE_SIZE) > 0 {}
+
+    // The doctor keeps reading the records under the new wallet
+    let history = client.get_medical_record_history(&doctor, &new_wallet, &0, &MAX_PAGE_SIZE);
+    assert_eq!(history.records.len(), 1);
+}
+
+#[test]
+fn test_revert_merge_after_canonical_wallet_recovered() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (old_wallet, new_wallet, institution, doctor) = setup_duplicate_patient(&env, &client);
+    let merge_id = client.merge_patients(
+        &institution,
+        &old_wallet,
+        &new_wallet,
+        &BytesN::from_array(&env, &[5u8; 32]),
+    );
+
+    // The canonical wallet is lost and recovered before the merge is undone
+    let guardian_a = Address::generate(&env);
+    let guardian_b = Address::generate(&env);
+    let recovered = Address::generate(&env);
+    client.set_guardians(
+        &new_wallet,
+        &vec![&env, guardian_a.clone(), guardian_b.clone()],
+        &2,
+    );
+    client.initiate_recovery(&guardian_a, &new_wallet, &recovered);
+    client.approve_recovery(&guardian_b, &new_wallet);
+    let request = client.get_recovery(&new_wallet).unwrap();
+    env.ledger()
+        .with_mut(|li| li.timestamp = request.executable_at);
+    client.complete_recovery(&new_wallet);
+    assert_eq!(client.get_patient_merges(&recovered), vec![&env, merge_id]);
+
+    // The lost wallet no longer speaks for the merge
+    assert!(client.try_revert_merge(&new_wallet, &merge_id).is_err());
+
+    client.revert_merge(&recovered, &merge_id);
+
+    assert_eq!(client.get_canonical_patient(&old_wallet), old_wallet);
+    assert_eq!(records_of(&env, &client, &old_wallet).len(), 1);
+    assert_eq!(records_of(&env, &client, &recovered).len(), 0);
+    assert_eq!(
+        client.get_authorized_doctors(&old_wallet),
+        vec![&env, doctor]
+    );
+    assert_eq!(client.get_authorized_doctors(&recovered).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Recovery is time-locked")]
+fn test_recovery_before_delay_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, _, guardian_a, guardian_b, new_wallet) = setup_guardians(&env, &client);
+
+    client.initiate_recovery(&guardian_a, &patient, &new_wallet);
+    client.approve_recovery(&guardian_b, &patient);
+    client.complete_recovery(&patient);
+}
+
+#[test]
+#[should_panic(expected = "Not enough guardian approvals")]
+fn test_recovery_without_quorum_should_fail() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, _, guardian_a, _, new_wallet) = setup_guardians(&env, &client);
+
+    client.initiate_recovery(&guardian_a, &patient, &new_wallet);
+    let executable_at = client.get_recovery(&patient).unwrap().executable_at;
+    env.ledger().with_mut(|li| li.timestamp = executable_at);
+
+    client.complete_recovery(&patient);
+}
+
+#[test]
+#[should_panic(expected = "No recovery in progress")]
+fn test_old_wallet_can_veto_recovery() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, _, guardian_a, guardian_b, new_wallet) = setup_guardians(&env, &client);
+
+    client.initiate_recovery(&guardian_a, &patient, &new_wallet);
+    client.veto_recovery(&patient);
+    assert!(client.get_recovery(&patient).is_none());
+
+    client.approve_recovery(&guardian_b, &patient);
+}
+
+#[test]
+#[should_panic(expected = "Not a guardian")]
+fn test_non_guardian_cannot_initiate_recovery() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, _, _, _, new_wallet) = setup_guardians(&env, &client);
+
+    client.initiate_recovery(&Address::generate(&env), &patient, &new_wallet);
+}
+
+fn setup_record(env: &Env, client: &MedicalRegistryClient) -> (Address, Address, u64) {
+    let patient = Address::generate(env);
+    let doctor = verified_doctor(env, client);
+
+    client.grant_access(&patient, &doctor);
+    let record_id = client.add_medical_record(
+        &patient,
+        &doctor,
+        &RecordType::Diagnosis,
+        &Bytes::from_array(env, &[1, 2, 3]),
+        &String::from_str(env, "Type 1 diabetes"),
+    );
+
+    (patient, doctor, record_id)
+}
+
+/// ------------------------------------------------
+/// MEDICAL RECORD VERSIONING TESTS
+/// ------------------------------------------------
+#[test]
+fn test_amend_medical_record_keeps_history() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, doctor, record_id) = setup_record(&env, &client);
+
+    let amendment_id = client.amend_medical_record(
+        &patient,
+        &doctor,
+        &record_id,
+        &Bytes::from_array(&env, &[4, 5, 6]),
+        &String::from_str(&env, "Type 2 diabetes"),
+        &String::from_str(&env, "Misdiagnosed type"),
+    );
+    assert_ne!(amendment_id, record_id);
+
+    // Only the amendment is current
+    let records = records_of(&env, &client, &patient);
+    assert_eq!(records.len(), 1);
+    let latest = records.get(0).unwrap();
+    assert_eq!(latest.id, amendment_id);
+    assert_eq!(latest.record_type, RecordType::Diagnosis);
+    assert_eq!(latest.version, 2);
+    assert_eq!(latest.supersedes, Some(record_id));
+    assert_eq!(
+        latest.amendment_reason,
+        Some(String::from_str(&env, "Misdiagnosed type"))
+    );
+
+    // The original stays in the history, linked to its replacement
+    let history = history_of(&client, &patient);
+    assert_eq!(history.len(), 2);
+    let original = client.get_medical_record(&patient, &patient, &record_id);
+    assert_eq!(original, history.get(0).unwrap());
+    assert_eq!(original.superseded_by, Some(amendment_id));
+    assert_eq!(
+        original.description,
+        String::from_str(&env, "Type 1 diabetes")
+    );
+}
+
+#[test]
+fn test_get_medical_records_filters_by_type_and_date() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let (patient, doctor, _) = setup_record(&env, &client);
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    client.add_medical_record(
+        &patient,
+        &doctor,
+        &RecordType::Encounter,
+        &Bytes::from_array(&env, &[7, 8, 9]),
+        &String::from_str(&env, "Annual checkup"),
+    );
+
+    let diagnoses = client
+        .get_medical_records(
+            &patient,
+            &patient,
+            &RecordFilter {
+                record_types: vec![&env, RecordType::Diagnosis],
+                start: None,
+                end: None,
+            },
+            &0,
+            &MAX_PAGE_SIZE,
+        )
+        .records;
+    assert_eq!(diagnoses.len(), 1);
+    assert_eq!(diagnoses.get(0).unwrap().record_type, RecordType::Diagnosis);
+
+    let recent = client
+        .get_medical_records(
+            &patient,
+            &patient,
+            &RecordFilter {
+                record_types: Vec::new(&env),
+                start: Some(1_500),
+                end: Some(2_000),
+            },
+            &0,
+            &MAX_PAGE_SIZE,
+        )
+        .records;
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent.get(0).unwrap().record_type, RecordType::Encounter);
+
+    let none = client
+        .get_medical_records(
+            &patient,
+            &patient,
+            &RecordFilter {
+                record_types: vec![&env, RecordType::Procedure],
+                start: None,
+                end: None,
+            },
+            &0,
+            &MAX_PAGE_SIZE,
+        )
+        .records;
+    assert_eq!(none.len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Record already superseded")]
+fn test_amend_superseded_record_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, doctor, record_id) = setup_record(&env, &client);
+
+    let hash = Bytes::from_array(&env, &[4, 5, 6]);
+    let desc = String::from_str(&env, "Type 2 diabetes");
+    let reason = String::from_str(&env, "Misdiagnosed type");
+    client.amend_medical_record(&patient, &doctor, &record_id, &hash, &desc, &reason);
+
+    // Amendments must target the latest version
+    client.amend_medical_record(&patient, &doctor, &record_id, &hash, &desc, &reason);
+}
+
+#[test]
+#[should_panic(expected = "Doctor not authorized")]
+fn test_unauthorized_doctor_cannot_amend_record() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, doctor, record_id) = setup_record(&env, &client);
+    client.revoke_access(&patient, &doctor);
+
+    client.amend_medical_record(
+        &patient,
+        &doctor,
+        &record_id,
+        &Bytes::from_array(&env, &[4, 5, 6]),
+        &String::from_str(&env, "Type 2 diabetes"),
+        &String::from_str(&env, "Misdiagnosed type"),
+    );
+}
+
+#[test]
+fn test_revert_merge_returns_amendments() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (old_wallet, new_wallet, institution, doctor) = setup_duplicate_patient(&env, &client);
+    let record_id = records_of(&env, &client, &old_wallet).get(0).unwrap().id;
+
+    let merge_id = client.merge_patients(
+        &institution,
+        &old_wallet,
+        &new_wallet,
+        &BytesN::from_array(&env, &[5u8; 32]),
+    );
+    let amendment_id = client.amend_medical_record(
+        &new_wallet,
+        &doctor,
+        &record_id,
+        &Bytes::from_array(&env, &[4, 5, 6]),
+        &String::from_str(&env, "Blood test results, corrected"),
+        &String::from_str(&env, "Wrong reference range"),
+    );
+
+    client.revert_merge(&institution, &merge_id);
+
+    // The amended record follows its original back to the old wallet
+    let history = history_of(&client, &old_wallet);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap().id, amendment_id);
+    assert_eq!(history_of(&client, &new_wallet).len(), 0);
+}
+
+/// ------------------------------------------------
+/// RECORD ACCESS TESTS
+/// ------------------------------------------------
+#[test]
+#[should_panic(expected = "Not authorized to read records")]
+fn test_unauthorized_reader_cannot_list_records() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, _, _) = setup_record(&env, &client);
+    let stranger = Address::generate(&env);
+
+    client.get_medical_records(&stranger, &patient, &all_records(&env), &0, &MAX_PAGE_SIZE);
+}
+
+#[test]
+#[should_panic(expected = "Not authorized to read records")]
+fn test_revoked_doctor_cannot_read_record() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, doctor, record_id) = setup_record(&env, &client);
+    client.get_medical_record(&doctor, &patient, &record_id);
+
+    client.revoke_access(&patient, &doctor);
+    client.get_medical_record(&doctor, &patient, &record_id);
+}
+
+#[test]
+fn test_medical_records_are_paginated() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, doctor, _) = setup_record(&env, &client);
+    for i in 0..2u8 {
+        client.add_medical_record(
+            &patient,
+            &doctor,
+            &RecordType::Note,
+            &Bytes::from_array(&env, &[i]),
+            &String::from_str(&env, "Progress note"),
+        );
+    }
+
+    let first = client.get_medical_records(&patient, &patient, &all_records(&env), &0, &2);
+    assert_eq!(first.records.len(), 2);
+    assert_eq!(first.next_cursor, Some(2));
+
+    let second = client.get_medical_records(&patient, &patient, &all_records(&env), &2, &2);
+    assert_eq!(second.records.len(), 1);
+    assert_eq!(second.next_cursor, None);
+    assert_eq!(second.records.get(0).unwrap().id, 3);
+}
+
+#[test]
+fn test_record_reads_are_logged() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, doctor, record_id) = setup_record(&env, &client);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    client.get_medical_records(&doctor, &patient, &all_records(&env), &0, &MAX_PAGE_SIZE);
+    client.get_medical_record(&doctor, &patient, &record_id);
+    client.get_medical_record_history(&patient, &patient, &0, &MAX_PAGE_SIZE);
+
+    let log = client.get_access_log(&patient, &0, &MAX_PAGE_SIZE);
+    assert_eq!(log.next_cursor, None);
+    assert_eq!(
+        log.entries,
+        vec![
+            &env,
+            AccessLogEntry {
+                reader: doctor.clone(),
+                kind: ReadKind::Records,
+                timestamp: 500,
+            },
+            AccessLogEntry {
+                reader: doctor,
+                kind: ReadKind::Record(record_id),
+                timestamp: 500,
+            },
+            AccessLogEntry {
+                reader: patient.clone(),
+                kind: ReadKind::History,
+                timestamp: 500,
+            },
+        ]
+    );
+
+    let page = client.get_access_log(&patient, &1, &1);
+    assert_eq!(page.entries.len(), 1);
+    assert_eq!(page.next_cursor, Some(2));
+}
+
+/// ------------------------------------------------
+/// SCOPED AUTHORIZATION TESTS
+/// ------------------------------------------------
+#[test]
+fn test_scoped_grant_limits_record_types() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, _, diagnosis_id) = setup_record(&env, &client);
+    let gp = verified_doctor(&env, &client);
+    let specialist = verified_doctor(&env, &client);
+    client.grant_access(&patient, &gp);
+    let note_id = client.add_medical_record(
+        &patient,
+        &gp,
+        &RecordType::Note,
+        &Bytes::from_array(&env, &[4, 5, 6]),
+        &String::from_str(&env, "Patient history"),
+    );
+
+    client.grant_scoped_access(
+        &patient,
+        &specialist,
+        &vec![&env, RecordType::Diagnosis],
+        &0,
+    );
+    let grant = client.get_doctor_grant(&patient, &specialist).unwrap();
+    assert_eq!(grant.record_types, vec![&env, RecordType::Diagnosis]);
+
+    // Reads only cover the granted record types
+    let records = client
+        .get_medical_records(
+            &specialist,
+            &patient,
+            &all_records(&env),
+            &0,
+            &MAX_PAGE_SIZE,
+        )
+        .records;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records.get(0).unwrap().id, diagnosis_id);
+    assert!(client
+        .try_get_medical_record(&specialist, &patient, &note_id)
+        .is_err());
+
+    // And so do writes
+    let hash = Bytes::from_array(&env, &[7, 8, 9]);
+    let desc = String::from_str(&env, "Referral");
+    client.add_medical_record(&patient, &specialist, &RecordType::Diagnosis, &hash, &desc);
+    assert!(client
+        .try_add_medical_record(&patient, &specialist, &RecordType::Note, &hash, &desc)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "Doctor not authorized")]
+fn test_expired_grant_drops_out() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let patient = Address::generate(&env);
+    let doctor = Address::generate(&env);
+
+    // A 30 day window around a referral
+    let expires_at = 30 * 24 * 60 * 60;
+    client.grant_scoped_access(&patient, &doctor, &Vec::new(&env), &expires_at);
+    assert_eq!(
+        client.get_authorized_doctors(&patient),
+        vec![&env, doctor.clone()]
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = expires_at);
+    assert_eq!(client.get_authorized_doctors(&patient).len(), 0);
+    assert!(client.get_doctor_grant(&patient, &doctor).is_none());
+    assert!(client
+        .try_get_medical_record_history(&doctor, &patient, &0, &MAX_PAGE_SIZE)
+        .is_err());
+
+    client.add_medical_record(
+        &patient,
+        &doctor,
+        &RecordType::Encounter,
+        &Bytes::from_array(&env, &[1, 2, 3]),
+        &String::from_str(&env, "Follow-up visit"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Expiry must be in the future")]
+fn test_grant_with_past_expiry_should_fail() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let patient = Address::generate(&env);
+    let doctor = Address::generate(&env);
+
+    client.grant_scoped_access(&patient, &doctor, &Vec::new(&env), &1_000);
+}
+
+/// ------------------------------------------------
+/// DOCTOR VERIFICATION LIFECYCLE TESTS
+/// ------------------------------------------------
+#[test]
+fn test_suspended_doctor_cannot_add_record() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, doctor, _) = setup_record(&env, &client);
+    let institution = client.get_doctor(&doctor).verified_by.unwrap();
+    let hash = Bytes::from_array(&env, &[4, 5, 6]);
+    let desc = String::from_str(&env, "Follow-up");
+
+    client.suspend_doctor(&doctor, &institution);
+    assert!(!client.is_doctor_active(&doctor));
+    assert!(client
+        .try_add_medical_record(&patient, &doctor, &RecordType::Note, &hash, &desc)
+        .is_err());
+
+    // Re-verification lifts the suspension
+    client.verify_doctor(&doctor, &institution, &LICENSE_EXPIRY);
+    client.add_medical_record(&patient, &doctor, &RecordType::Note, &hash, &desc);
+
+    let history = client.get_doctor_history(&doctor);
+    assert_eq!(history.len(), 3);
+    let suspension = history.get(1).unwrap();
+    assert_eq!(suspension.status, DoctorStatus::Suspended);
+    assert_eq!(suspension.institution, institution);
+}
+
+#[test]
+#[should_panic(expected = "Doctor license has expired")]
+fn test_expired_license_blocks_record_writes() {
+    use soroban_sdk::testutils::Ledger;
+
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let (patient, doctor, _) = setup_record(&env, &client);
+
+    env.ledger().with_mut(|li| li.timestamp = LICENSE_EXPIRY);
+    client.add_medical_record(
+        &patient,
+        &doctor,
+        &RecordType::Note,
+        &Bytes::from_array(&env, &[4, 5, 6]),
+        &String::from_str(&env, "Follow-up"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Doctor is not verified")]
+fn test_unverified_doctor_cannot_add_record() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let patient = Address::generate(&env);
+    let doctor = Address::generate(&env);
+    client.register_doctor(
+        &doctor,
+        &String::from_str(&env, "Dr. Eve"),
+        &String::from_str(&env, "Oncology"),
+        &Bytes::from_array(&env, &[7, 7, 7]),
+    );
+    client.grant_access(&patient, &doctor);
+
+    client.add_medical_record(
+        &patient,
+        &doctor,
+        &RecordType::Note,
+        &Bytes::from_array(&env, &[1, 2, 3]),
+        &String::from_str(&env, "Blood test results"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Doctor verification has been revoked")]
+fn test_revoked_doctor_cannot_be_reverified() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let doctor = verified_doctor(&env, &client);
+    let institution = client.get_doctor(&doctor).verified_by.unwrap();
+
+    client.revoke_doctor(&doctor, &institution);
+    assert_eq!(client.get_doctor(&doctor).status, DoctorStatus::Revoked);
+
+    client.verify_doctor(&doctor, &institution, &LICENSE_EXPIRY);
+}
+
+#[test]
+#[should_panic(expected = "Institution does not oversee this doctor")]
+fn test_unrelated_institution_cannot_suspend_doctor() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let doctor = verified_doctor(&env, &client);
+    let rival = Address::generate(&env);
+    client.register_institution(&rival);
+
+    client.suspend_doctor(&doctor, &rival);
+}
+
+#[test]
+#[should_panic(expected = "Institution does not oversee this doctor")]
+fn test_unrelated_institution_cannot_revoke_doctor() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let doctor = verified_doctor(&env, &client);
+    let rival = Address::generate(&env);
+    client.register_institution(&rival);
+
+    client.revoke_doctor(&doctor, &rival);
+}
+
+#[test]
+fn test_only_suspending_institution_lifts_suspension() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    let doctor = verified_doctor(&env, &client);
+    let institution = client.get_doctor(&doctor).verified_by.unwrap();
+    client.suspend_doctor(&doctor, &institution);
+
+    let rival = Address::generate(&env);
+    client.register_institution(&rival);
+    assert!(client
+        .try_verify_doctor(&doctor, &rival, &LICENSE_EXPIRY)
+        .is_err());
+    assert!(!client.is_doctor_active(&doctor));
+
+    // The admin can still lift it
+    client.reinstate_doctor(&doctor);
+    assert!(client.is_doctor_active(&doctor));
+    assert_eq!(client.get_doctor(&doctor).suspended_by, None);
+}
+
+#[test]
+fn test_register_institution_requires_admin() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.initialize(&Address::generate(&env));
+
+    // An institution cannot admit itself
+    let institution = Address::generate(&env);
+    assert!(client
+        .mock_auths(&[MockAuth {
+            address: &institution,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "register_institution",
+                args: (&institution,).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_register_institution(&institution)
+        .is_err());
+}
+
+/// Deploys a hospital registry and a doctor registry holding one doctor
+/// profile, and points the medical registry at it
+fn setup_doctor_registry(
+    env: &Env,
+    client: &MedicalRegistryClient,
+) -> (
+    doctor_registry::DoctorRegistryClient<'static>,
+    Address,
+    Address,
+) {
+    let hospitals_id = env.register(hospital_registry::HospitalRegistry, ());
+    let hospitals = hospital_registry::HospitalRegistryClient::new(env, &hospitals_id);
+    let registry_id = env.register(doctor_registry::DoctorRegistry, ());
+    let registry = doctor_registry::DoctorRegistryClient::new(env, &registry_id);
+
+    let doctor = Address::generate(env);
+    let hospital = Address::generate(env);
+
+    hospitals.register_hospital(
+        &hospital,
+        &String::from_str(env, "General Hospital"),
+        &String::from_str(env, "123 Main St"),
+        &String::from_str(env, "Services: ER"),
+    );
+    registry.initialize(&Address::generate(env), &hospitals_id);
+    registry.create_doctor_profile(
+        &doctor,
+        &String::from_str(env, "Dr. Alice"),
+        &String::from_str(env, "Cardiology"),
+    );
+    client.initialize(&Address::generate(env));
+    client.set_doctor_registry(&registry_id);
+    client.register_doctor(
+        &doctor,
+        &String::from_str(env, "Alice"),
+        &String::from_str(env, "Cardio"),
+        &Bytes::from_array(env, &[1, 2, 3, 4]),
+    );
+    client.register_institution(&hospital);
+
+    (registry, doctor, hospital)
+}
+
+/// ------------------------------------------------
+/// DOCTOR REGISTRY TESTS
+/// ------------------------------------------------
+#[test]
+fn test_affiliated_institution_verifies_doctor() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let (registry, doctor, hospital) = setup_doctor_registry(&env, &client);
+    registry.request_affiliation(
+        &doctor,
+        &hospital,
+        &doctor_registry::AffiliationRole::Attending,
+        &vec![&env, doctor_registry::Privilege::Prescribe],
+    );
+    registry.confirm_affiliation(&hospital, &doctor);
+
+    client.verify_doctor(&doctor, &hospital, &LICENSE_EXPIRY);
+
+    // Profile data is read from the doctor registry
+    let data = client.get_doctor(&doctor);
+    assert_eq!(data.status, DoctorStatus::Verified);
+    assert_eq!(data.name, String::from_str(&env, "Dr. Alice"));
+    assert_eq!(data.specialization, String::from_str(&env, "Cardiology"));
+}
+
+#[test]
+#[should_panic(expected = "Institution is not affiliated with doctor")]
+fn test_unaffiliated_institution_cannot_verify_doctor() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let (registry, doctor, hospital) = setup_doctor_registry(&env, &client);
+
+    // Requested but never confirmed by the hospital
+    registry.request_affiliation(
+        &doctor,
+        &hospital,
+        &doctor_registry::AffiliationRole::Visiting,
+        &vec![&env, doctor_registry::Privilege::Telehealth],
+    );
+
+    client.verify_doctor(&doctor, &hospital, &LICENSE_EXPIRY);
+}
+
+#[test]
+#[should_panic(expected = "Doctor profile not found")]
+fn test_register_doctor_requires_registry_profile() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    setup_doctor_registry(&env, &client);
+
+    client.register_doctor(
+        &Address::generate(&env),
+        &String::from_str(&env, "Dr. Eve"),
+        &String::from_str(&env, "Oncology"),
+        &Bytes::from_array(&env, &[7, 7, 7]),
+    );
+}
+
+#[test]
+fn test_only_admin_sets_doctor_registry() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let registry = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+
+    // Nobody but the admin can point the contract at a registry
+    let attacker = Address::generate(&env);
+    let invoke = MockAuthInvoke {
+        contract: &contract_id,
+        fn_name: "set_doctor_registry",
+        args: (&registry,).into_val(&env),
+        sub_invokes: &[],
+    };
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &invoke,
+        }])
+        .try_set_doctor_registry(&registry);
+    assert!(result.is_err());
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &invoke,
+        }])
+        .set_doctor_registry(&registry);
+    assert_eq!(client.get_doctor_registry(), Some(registry));
+}
+
+#[test]
+#[should_panic(expected = "Contract not initialized")]
+fn test_set_doctor_registry_requires_initialization() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    client.set_doctor_registry(&Address::generate(&env));
+}
@@ -2,6 +2,7 @@ use crate::types::{
     AlertThresholds, DataKey, DeviceReading, DeviceRegistration, Error, MonitoringParameters,
     Range, VitalAlert, VitalReading, VitalSigns, VitalStatistics,
 };
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
 use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol, Vec};
 
 // Error codes
@@ -9,11 +10,53 @@ use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol, Vec};
 // 2 = Not Found
 // 3 = Invalid Parameter
 
+/// Fails unless `writer` is the patient or, when the patient has set an
+/// AccessControl contract, holds a write grant on their vitals.
+fn require_policy_write(env: &Env, patient_id: &Address, writer: &Address) -> Result<(), Error> {
+    if patient_id == writer {
+        return Ok(());
+    }
+    let Some(policy) = env
+        .storage()
+        .persistent()
+        .get::<_, Address>(&DataKey::AccessPolicy(patient_id.clone()))
+    else {
+        return Ok(());
+    };
+    if !AccessPolicyClient::new(env, &policy).check_access(
+        writer,
+        &patient_resource(env, patient_id, "vitals"),
+        &Scope::Write,
+        &Purpose::Treatment,
+    ) {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
 #[contract]
 pub struct PatientVitalsContract;
 
 #[contractimpl]
 impl PatientVitalsContract {
+    /// Delegate access decisions for a patient's vitals to an AccessControl
+    /// contract, or clear the delegation with `None`. While set, recorders
+    /// other than the patient need a write grant on `patient/<patient>/vitals`.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        let key = DataKey::AccessPolicy(patient_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccessPolicy(patient_id))
+    }
+
     pub fn record_vital_signs(
         env: Env,
         patient_id: Address,
@@ -22,6 +65,7 @@ impl PatientVitalsContract {
         vitals: VitalSigns,
     ) -> Result<u64, Error> {
         recorder.require_auth();
+        require_policy_write(&env, &patient_id, &recorder)?;
 
         // Load existing history or create new
         let key = DataKey::VitalsHistory(patient_id.clone());
@@ -53,6 +97,7 @@ impl PatientVitalsContract {
         monitoring_frequency: u32,
     ) -> Result<(), Error> {
         provider_id.require_auth();
+        require_policy_write(&env, &patient_id, &provider_id)?;
 
         let key = DataKey::MonitoringParams(patient_id, vital_type);
         let params = MonitoringParameters {
@@ -3,7 +3,9 @@
 use super::*;
 use crate::contract::{PatientVitalsContract, PatientVitalsContractClient};
 use crate::types::{AlertThresholds, DeviceReading, Range, VitalSigns, VitalStatistics};
-use soroban_sdk::{testutils::Address as _, Address, Env, String, Symbol, Vec};
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest, Purpose, Scope};
+use access_control_interface::patient_resource;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String, Symbol, Vec};
 
 #[test]
 fn test_record_vital_signs() {
@@ -170,3 +172,59 @@ fn test_calculate_vital_statistics() {
     assert_eq!(stats.max_value, 90);
     assert_eq!(stats.average_value, 85);
 }
+
+#[test]
+fn test_access_policy_governs_recording() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PatientVitalsContract, ());
+    let client = PatientVitalsContractClient::new(&env, &contract_id);
+
+    let patient_id = Address::generate(&env);
+    let provider_id = Address::generate(&env);
+    let (policy_id, policy) =
+        setup_access_policy(&env, &patient_id, &provider_id, EntityType::Doctor);
+    client.set_access_policy(&patient_id, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient_id), Some(policy_id));
+
+    let vitals = VitalSigns {
+        blood_pressure_systolic: Some(120),
+        blood_pressure_diastolic: Some(80),
+        heart_rate: Some(72),
+        temperature: None,
+        respiratory_rate: None,
+        oxygen_saturation: None,
+        blood_glucose: None,
+        weight: None,
+    };
+    assert!(client
+        .try_record_vital_signs(&patient_id, &provider_id, &1672531200, &vitals)
+        .is_err());
+    // Patients can always record their own
+    client.record_vital_signs(&patient_id, &patient_id, &1672531200, &vitals);
+
+    let resource_id = patient_resource(&env, &patient_id, "vitals");
+    policy.grant_access(
+        &patient_id,
+        &provider_id,
+        &resource_id,
+        &vec![&env, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    assert_eq!(
+        client.record_vital_signs(&patient_id, &provider_id, &1672531260, &vitals),
+        2
+    );
+
+    // Revoking the grant in access-control blocks the next reading
+    policy.revoke_access(&patient_id, &provider_id, &resource_id);
+    assert!(client
+        .try_record_vital_signs(&patient_id, &provider_id, &1672531320, &vitals)
+        .is_err());
+}
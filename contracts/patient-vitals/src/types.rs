@@ -54,6 +54,7 @@ pub enum DataKey {
     MonitoringParams(Address, Symbol), // map to MonitoringParameters
     DeviceReg(Address, String),        // map to DeviceRegistration
     VitalsAlerts(Address, Symbol),     // map to Vec<VitalAlert>
+    AccessPolicy(Address),             // patient -> AccessControl contract
 }
 
 #[contracttype]
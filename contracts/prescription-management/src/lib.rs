@@ -1,5 +1,6 @@
 #![no_std]
 
+use access_control_interface::{AccessPolicyClient, Purpose, Scope, patient_resource};
 use soroban_sdk::{
     Address, BytesN, Env, String, Symbol, Vec, contract, contracterror, contractimpl, contracttype,
     panic_with_error,
@@ -74,6 +75,7 @@ pub enum DataKey {
     PatientConditions(Address),
     MedicationContraindications(String),
     InteractionOverride(u64, Address),
+    AccessPolicy(Address), // patient -> AccessControl contract
 }
 
 #[contracttype]
@@ -116,11 +118,50 @@ pub struct IssueRequest {
     pub substitution_allowed: bool,
 }
 
+/// Panics unless the patient's AccessControl contract, if they set one,
+/// grants `writer` write access to their prescriptions.
+fn require_policy_write(env: &Env, patient_id: &Address, writer: &Address) {
+    let Some(policy) = env
+        .storage()
+        .persistent()
+        .get::<_, Address>(&DataKey::AccessPolicy(patient_id.clone()))
+    else {
+        return;
+    };
+    if !AccessPolicyClient::new(env, &policy).check_access(
+        writer,
+        &patient_resource(env, patient_id, "prescriptions"),
+        &Scope::Write,
+        &Purpose::Treatment,
+    ) {
+        panic_with_error!(env, Error::Unauthorized);
+    }
+}
+
 #[contract]
 pub struct PrescriptionContract;
 
 #[contractimpl]
 impl PrescriptionContract {
+    /// Delegate access decisions for a patient's prescriptions to an
+    /// AccessControl contract, or clear the delegation with `None`. While set,
+    /// prescribers and pharmacies need a write grant on
+    /// `patient/<patient>/prescriptions`.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        let key = DataKey::AccessPolicy(patient_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccessPolicy(patient_id))
+    }
+
     pub fn issue_prescription(
         env: Env,
         provider_id: Address,
@@ -128,6 +169,7 @@ impl PrescriptionContract {
         req: IssueRequest,
     ) -> u64 {
         provider_id.require_auth();
+        require_policy_write(&env, &patient_id, &provider_id);
 
         let id = env
             .storage()
@@ -169,6 +211,7 @@ impl PrescriptionContract {
             .persistent()
             .get(&prescription_id)
             .expect("Prescription not found");
+        require_policy_write(&env, &p.patient_id, &pharmacy_id);
 
         if env.ledger().timestamp() > p.valid_until {
             panic_with_error!(&env, Error::Expired);
@@ -189,6 +232,7 @@ impl PrescriptionContract {
         from_pharmacy.require_auth();
 
         let mut p: Prescription = env.storage().persistent().get(&prescription_id).unwrap();
+        require_policy_write(&env, &p.patient_id, &from_pharmacy);
 
         p.current_pharmacy = Some(to_pharmacy);
         p.status = PrescriptionStatus::Transferred;
@@ -414,6 +458,7 @@ impl PrescriptionContract {
         override_reason: String,
     ) -> Result<(), Error> {
         provider_id.require_auth();
+        require_policy_write(&env, &patient_id, &provider_id);
 
         if override_reason == String::from_str(&env, "") {
             return Err(Error::MissingOverrideReason);
@@ -1,6 +1,7 @@
 #![cfg(test)]
 
 use super::*;
+use access_control::{EntityType, GrantRequest, testutils::setup_access_policy};
 // Note the inclusion of 'Ledger' and 'Address' as traits here
 use soroban_sdk::{
     Address, BytesN, Env, String, Symbol,
@@ -294,3 +295,71 @@ fn test_invalid_severity_rejected() {
 
     assert_eq!(result, Err(Ok(Error::InvalidSeverity)));
 }
+
+#[test]
+fn test_access_policy_governs_dispensing() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PrescriptionContract, ());
+    let client = PrescriptionContractClient::new(&env, &contract_id);
+
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let pharmacy = Address::generate(&env);
+
+    let request = IssueRequest {
+        medication_name: String::from_str(&env, "Amoxicillin"),
+        ndc_code: String::from_str(&env, "0501-1234-01"),
+        dosage: String::from_str(&env, "500mg"),
+        quantity: 30,
+        days_supply: 10,
+        refills_allowed: 0,
+        instructions_hash: BytesN::from_array(&env, &[0u8; 32]),
+        is_controlled: false,
+        schedule: None,
+        valid_until: 1000,
+        substitution_allowed: true,
+    };
+    let id = client.issue_prescription(&provider, &patient, &request);
+
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &pharmacy, EntityType::Hospital);
+    client.set_access_policy(&patient, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient), Some(policy_id));
+
+    // Unregistered prescribers and ungranted pharmacies are refused
+    assert!(
+        client
+            .try_issue_prescription(&provider, &patient, &request)
+            .is_err()
+    );
+    let lot = String::from_str(&env, "LOT-1");
+    assert!(
+        client
+            .try_dispense_prescription(&id, &pharmacy, &30, &lot)
+            .is_err()
+    );
+
+    let resource_id = patient_resource(&env, &patient, "prescriptions");
+    policy.grant_access(
+        &patient,
+        &pharmacy,
+        &resource_id,
+        &vec![&env, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    client.dispense_prescription(&id, &pharmacy, &30, &lot);
+
+    // Revoking the grant in access-control blocks the pharmacy again
+    policy.revoke_access(&patient, &pharmacy, &resource_id);
+    assert!(
+        client
+            .try_transfer_prescription(&id, &pharmacy, &Address::generate(&env))
+            .is_err()
+    );
+}
@@ -6,17 +6,54 @@ mod types;
 #[cfg(test)]
 mod test;
 
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
 use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
 use storage::*;
 use types::*;
 
 const MAX_APPEAL_LEVEL: u32 = 3;
 
+/// Ask the patient's AccessControl contract whether `provider` may request
+/// authorizations against their records. Returns `None` when the patient has
+/// not set a policy.
+fn policy_allows(env: &Env, patient_id: &Address, provider: &Address) -> Option<bool> {
+    let policy: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AccessPolicy(patient_id.clone()))?;
+    Some(AccessPolicyClient::new(env, &policy).check_access(
+        provider,
+        &patient_resource(env, patient_id, "authorizations"),
+        &Scope::Write,
+        &Purpose::Payment,
+    ))
+}
+
 #[contract]
 pub struct PriorAuthorizationContract;
 
 #[contractimpl]
 impl PriorAuthorizationContract {
+    /// Delegate access decisions for a patient's authorizations to an
+    /// AccessControl contract, or clear the delegation with `None`. While set,
+    /// providers need a write grant for payment on
+    /// `patient/<patient>/authorizations` to submit or document a request.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        let key = DataKey::AccessPolicy(patient_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Get the AccessControl contract governing a patient's authorizations, if any.
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccessPolicy(patient_id))
+    }
+
     /// Submit a new prior authorization request.
     pub fn submit_prior_authorization(
         env: Env,
@@ -31,6 +68,9 @@ impl PriorAuthorizationContract {
         urgency: Symbol,
     ) -> Result<u64, Error> {
         provider_id.require_auth();
+        if !policy_allows(&env, &patient_id, &provider_id).unwrap_or(true) {
+            return Err(Error::Unauthorized);
+        }
 
         let auth_request_id = next_auth_id(&env);
 
@@ -84,6 +124,9 @@ impl PriorAuthorizationContract {
         if req.provider_id != provider_id {
             return Err(Error::Unauthorized);
         }
+        if !policy_allows(&env, &req.patient_id, &provider_id).unwrap_or(true) {
+            return Err(Error::Unauthorized);
+        }
 
         let doc = SupportingDocument {
             auth_request_id,
@@ -1,7 +1,8 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Symbol, Vec};
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest};
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String, Symbol, Vec};
 
 // -----------------------------------------------------------------------
 // Helpers
@@ -762,4 +763,44 @@ fn test_full_workflow_deny_appeal_three_levels() {
 
     let info = client.get_authorization_status(&id, &provider).unwrap();
     assert!(matches!(info.status, AuthStatus::Appealed));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_access_policy_gates_submission() {
+    let (env, provider, patient) = setup();
+    let client = register_contract(&env);
+    let auth_id = submit(&env, &client, &provider, &patient);
+
+    let (policy_id, policy) = setup_access_policy(&env, &patient, &provider, EntityType::Doctor);
+    client.set_access_policy(&patient, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient), Some(policy_id));
+
+    let doc_hash = BytesN::from_array(&env, &[2u8; 32]);
+    let doc_type = Symbol::new(&env, "lab_result");
+    assert_eq!(
+        client.try_attach_supporting_documentation(&auth_id, &provider, &doc_hash, &doc_type),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    let resource_id = patient_resource(&env, &patient, "authorizations");
+    policy.grant_access(
+        &patient,
+        &provider,
+        &resource_id,
+        &vec![&env, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Payment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    client.attach_supporting_documentation(&auth_id, &provider, &doc_hash, &doc_type);
+    submit(&env, &client, &provider, &patient);
+
+    policy.revoke_access(&patient, &provider, &resource_id);
+    assert_eq!(
+        client.try_attach_supporting_documentation(&auth_id, &provider, &doc_hash, &doc_type),
+        Err(Ok(Error::Unauthorized))
+    );
+}
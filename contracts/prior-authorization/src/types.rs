@@ -168,4 +168,6 @@ pub enum DataKey {
     ProviderAuths(Address),
     /// patient_id -> Vec<u64> (auth request ids)
     PatientAuths(Address),
+    /// patient_id -> AccessControl contract
+    AccessPolicy(Address),
 }
\ No newline at end of file
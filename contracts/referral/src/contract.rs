@@ -1,11 +1,64 @@
 use crate::types::{DataKey, Error, Referral, ReferralStatus};
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
 use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
 
+/// Ask the patient's AccessControl contract whether `provider` holds `scope`
+/// on their referrals. Returns `None` when the patient has not set a policy.
+fn policy_allows(
+    env: &Env,
+    patient_id: &Address,
+    provider: &Address,
+    scope: Scope,
+) -> Option<bool> {
+    let policy: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AccessPolicy(patient_id.clone()))?;
+    Some(AccessPolicyClient::new(env, &policy).check_access(
+        provider,
+        &patient_resource(env, patient_id, "referrals"),
+        &scope,
+        &Purpose::Treatment,
+    ))
+}
+
+fn require_policy(
+    env: &Env,
+    patient_id: &Address,
+    provider: &Address,
+    scope: Scope,
+) -> Result<(), Error> {
+    if policy_allows(env, patient_id, provider, scope).unwrap_or(true) {
+        Ok(())
+    } else {
+        Err(Error::NotAuthorized)
+    }
+}
+
 #[contract]
 pub struct ReferralContract;
 
 #[contractimpl]
 impl ReferralContract {
+    /// Delegate access decisions for a patient's referrals to an AccessControl
+    /// contract, or clear the delegation with `None`. While set, referring
+    /// providers need a write grant and providers sharing a care summary need
+    /// a read grant on `patient/<patient>/referrals`.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        let key = DataKey::AccessPolicy(patient_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccessPolicy(patient_id))
+    }
+
     pub fn create_referral(
         env: Env,
         referring_provider: Address,
@@ -18,6 +71,7 @@ impl ReferralContract {
         requested_services: Vec<String>,
     ) -> Result<u64, Error> {
         referring_provider.require_auth();
+        require_policy(&env, &patient_id, &referring_provider, Scope::Write)?;
 
         let referral_id: u64 = env
             .storage()
@@ -244,6 +298,7 @@ impl ReferralContract {
         {
             return Err(Error::NotAuthorized);
         }
+        require_policy(&env, &referral.patient_id, &from_provider, Scope::Read)?;
 
         env.events().publish(
             (Symbol::new(&env, "care_summary_shared"), referral_id),
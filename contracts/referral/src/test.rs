@@ -2,9 +2,11 @@
 
 use crate::contract::{ReferralContract, ReferralContractClient};
 use crate::types::Error;
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest};
+use access_control_interface::{patient_resource, Purpose, Scope};
 use soroban_sdk::{
     testutils::{Address as _, Events},
-    Address, BytesN, Env, String, Symbol, Vec,
+    vec, Address, BytesN, Env, String, Symbol, Vec,
 };
 
 #[test]
@@ -144,3 +146,68 @@ fn test_auth_failures() {
     let res = client.try_accept_referral(&referral_id, &wrong_provider, &None);
     assert!(res.is_err()); // NotAuthorized
 }
+
+#[test]
+fn test_access_policy_gates_referrals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, ReferralContract);
+    let client = ReferralContractClient::new(&env, &contract_id);
+
+    let referring_provider = Address::generate(&env);
+    let patient_id = Address::generate(&env);
+    let referred_to = Address::generate(&env);
+
+    let (policy_id, policy) =
+        setup_access_policy(&env, &patient_id, &referring_provider, EntityType::Doctor);
+    client.set_access_policy(&patient_id, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient_id), Some(policy_id));
+
+    let create = |client: &ReferralContractClient| {
+        client.try_create_referral(
+            &referring_provider,
+            &patient_id,
+            &referred_to,
+            &Symbol::new(&env, "Cardio"),
+            &String::from_str(&env, "Heart palpitations"),
+            &Symbol::new(&env, "Urgent"),
+            &BytesN::from_array(&env, &[1; 32]),
+            &Vec::new(&env),
+        )
+    };
+    assert_eq!(create(&client), Err(Ok(Error::NotAuthorized)));
+
+    let resource_id = patient_resource(&env, &patient_id, "referrals");
+    policy.grant_access(
+        &patient_id,
+        &referring_provider,
+        &resource_id,
+        &vec![&env, Scope::Read, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    let referral_id = create(&client).unwrap().unwrap();
+
+    // The receiving provider has no grant of its own to share records
+    client.accept_referral(&referral_id, &referred_to, &None);
+    let summary_type = Symbol::new(&env, "LabResults");
+    let summary_hash = BytesN::from_array(&env, &[2; 32]);
+    assert_eq!(
+        client.try_share_care_summary(&referral_id, &referred_to, &summary_type, &summary_hash),
+        Err(Ok(Error::NotAuthorized))
+    );
+    client.share_care_summary(
+        &referral_id,
+        &referring_provider,
+        &summary_type,
+        &summary_hash,
+    );
+
+    policy.revoke_access(&patient_id, &referring_provider, &resource_id);
+    assert_eq!(create(&client), Err(Ok(Error::NotAuthorized)));
+}
@@ -42,4 +42,5 @@ pub struct Referral {
 pub enum DataKey {
     Referral(u64),
     ReferralCount,
+    AccessPolicy(Address), // patient -> AccessControl contract
 }
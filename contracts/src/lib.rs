@@ -1,14 +1,43 @@
+use pagination::page_limit;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, String, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Env, String,
+    Vec,
 };
 
+pub use pagination::MAX_PAGE_SIZE;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InstitutionData {
     pub name: String,
     pub license_id: String,
     pub metadata: String,
-    pub is_verified: bool,
+    pub status: InstitutionStatus,
+    pub status_changed_by: Option<Address>,
+    pub status_changed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InstitutionStatus {
+    Pending,
+    Verified,
+    Suspended,
+    Closed, // Final; the institution deregistered or was closed by the admin
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstitutionSummary {
+    pub wallet: Address,
+    pub data: InstitutionData,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstitutionPage {
+    pub institutions: Vec<InstitutionSummary>,
+    pub next_cursor: Option<u32>, // None once the listing is exhausted
 }
 
 #[contracttype]
@@ -18,7 +47,16 @@ pub struct Appointment {
     pub patient: Address,
     pub doctor: Address,
     pub datetime: u64,
+    pub duration_minutes: u32,
     pub status: AppointmentStatus,
+    pub reschedules: Vec<Reschedule>,
+    pub canceled_by: Option<Address>,
+    pub cancellation_reason: Option<String>,
+    pub checked_in_at: Option<u64>,
+    pub checked_in_by: Option<Address>,
+    pub started_at: Option<u64>,
+    pub completed_at: Option<u64>,
+    pub reminded_lead_times: Vec<u64>, // Lead times already announced
 }
 
 #[contracttype]
@@ -27,12 +65,176 @@ pub enum AppointmentStatus {
     Scheduled,
     Canceled,
     Completed,
+    NoShow,
+    CheckedIn,
+    InProgress,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reschedule {
+    pub previous_datetime: u64,
+    pub rescheduled_by: Address,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaitlistEntry {
+    pub patient: Address,
+    pub earliest: u64, // Preferred window for a backfilled slot
+    pub latest: u64,
+    pub joined_at: u64,
+}
+
+// A waitlist entry linked to its neighbours, oldest first
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaitlistNode {
+    pub entry: WaitlistEntry,
+    pub prev: Option<Address>,
+    pub next: Option<Address>,
+}
+
+// The time a scheduled appointment occupies in its doctor's calendar
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BookedInterval {
+    pub appointment_id: u64,
+    pub start: u64,
+    pub end: u64,
+}
+
+// A freed slot held for one waitlisted patient until `expires_at`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlotOffer {
+    pub doctor: Address,
+    pub datetime: u64,
+    pub patient: Address,
+    pub expires_at: u64,
+    pub offered_to: Vec<Address>, // Everyone offered this slot so far
+}
+
+// Totals over completed visits that went through check-in, for one page of
+// a doctor's appointments. Sum the pages and divide by `visits` to average.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VisitStatsPage {
+    pub visits: u32,
+    pub total_wait_seconds: u64,
+    pub total_visit_seconds: u64,
+    pub next_cursor: Option<u32>, // None once the appointments are exhausted
+}
+
+// Refundable deposit a doctor requires at booking, paid in a SEP-41 token
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositPolicy {
+    pub token: Address,
+    pub amount: i128,
+    pub beneficiary: Address, // Doctor or institution receiving forfeited deposits
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DepositStatus {
+    Held,
+    Refunded,
+    Forfeited,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Deposit {
+    pub token: Address,
+    pub amount: i128,
+    pub beneficiary: Address,
+    pub status: DepositStatus,
+}
+
+// Policy counters clinics can enforce against
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PatientAttendance {
+    pub late_cancellations: u32,
+    pub no_shows: u32,
+}
+
+// Weekly working hours in UTC, as minutes since midnight
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WorkingHours {
+    pub weekday: u32, // 0 = Monday .. 6 = Sunday
+    pub start_minute: u32,
+    pub end_minute: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Availability {
+    pub weekly_hours: Vec<WorkingHours>,
+    pub slot_minutes: u32,
+    pub blackout_dates: Vec<u64>, // UTC midnight timestamps of days off
 }
 
 #[contracttype]
 pub enum DataKey {
     Inst(Address),
-    Admin, // To manage the 'verifier' role
+    Admin,                      // Manages the verifying authorities
+    Verifier(Address),          // Verifying authority -> bool
+    InstitutionCount,           // Number of registered institutions
+    InstitutionAt(u32),         // Registration index -> institution wallet
+    DoctorInstitution(Address), // Doctor -> institution wallet
+}
+
+fn load_institution(env: &Env, wallet: &Address) -> InstitutionData {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Inst(wallet.clone()))
+        .expect("Not found")
+}
+
+fn require_admin(env: &Env, admin: &Address) {
+    admin.require_auth();
+    let stored: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+    if *admin != stored {
+        panic!("Not authorized");
+    }
+}
+
+// The admin is always a verifying authority
+fn require_verifier(env: &Env, verifier: &Address) {
+    verifier.require_auth();
+    let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+    if *verifier != admin
+        && !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Verifier(verifier.clone()))
+    {
+        panic!("Not authorized to verify");
+    }
+}
+
+fn set_institution_status(
+    env: &Env,
+    wallet: &Address,
+    mut data: InstitutionData,
+    status: InstitutionStatus,
+    by: &Address,
+) {
+    data.status = status;
+    data.status_changed_by = Some(by.clone());
+    data.status_changed_at = env.ledger().timestamp();
+    env.storage()
+        .persistent()
+        .set(&DataKey::Inst(wallet.clone()), &data);
+
+    env.events().publish(
+        (symbol_short!("inst_st"), wallet.clone()),
+        (status, by.clone()),
+    );
 }
 
 #[contracttype]
@@ -40,6 +242,535 @@ pub enum AppointmentKey {
     Appointment(u64),
     AppointmentCounter,
     UserAppointments(Address),
+    Availability(Address),
+    Bookings(Address), // Doctor -> BookedInterval list not yet ended, by start
+    CancellationWindow(Address), // Doctor -> seconds before the slot
+    PatientAttendance(Address),
+    WaitlistHead(Address),          // Doctor -> patient waiting longest
+    WaitlistTail(Address),          // Doctor -> patient who joined last
+    WaitlistLen(Address),           // Doctor -> number of waiting patients
+    WaitlistNode(Address, Address), // (doctor, patient) -> WaitlistNode
+    ClaimPeriod(Address),           // Doctor -> seconds a waitlist offer stays open
+    SlotOffer(Address, u64),        // (doctor, datetime) -> SlotOffer
+    OfferedSlots(Address),          // Doctor -> datetimes of future slots with an offer
+    FrontDesk(Address, Address),    // (doctor, desk) -> bool
+    ReminderLeadTimes(Address),     // Doctor -> Vec<u64> seconds, descending
+    DepositPolicy(Address),
+    Deposit(u64),         // Appointment id -> Deposit escrowed by this contract
+    SettledDeposits(u64), // Appointment id -> Vec<Deposit> replaced by a later deposit
+    InstitutionRegistry,  // HealthcareRegistry gating bookings, set at initialization
+    Admin,
+}
+
+const SECONDS_PER_DAY: u64 = 86_400;
+const MINUTES_PER_DAY: u32 = 1_440;
+const MAX_SLOT_QUERY_DAYS: u64 = 31;
+const DEFAULT_CLAIM_PERIOD: u64 = 3_600;
+const DEFAULT_REMINDER_LEAD_TIMES: [u64; 2] = [86_400, 3_600];
+const MAX_WAITLIST_LEN: u32 = 20;
+const DEPOSIT_RECLAIM_DELAY: u64 = 7 * SECONDS_PER_DAY;
+
+fn weekday(datetime: u64) -> u32 {
+    // 1970-01-01 was a Thursday
+    ((datetime / SECONDS_PER_DAY + 3) % 7) as u32
+}
+
+fn load_availability(env: &Env, doctor: &Address) -> Availability {
+    env.storage()
+        .persistent()
+        .get(&AppointmentKey::Availability(doctor.clone()))
+        .expect("Doctor has no availability")
+}
+
+// Whether `datetime` is the start of a slot in the doctor's template
+fn is_open_slot(availability: &Availability, datetime: u64) -> bool {
+    if !datetime.is_multiple_of(60) {
+        return false;
+    }
+    let day_start = datetime - datetime % SECONDS_PER_DAY;
+    if availability.blackout_dates.contains(day_start) {
+        return false;
+    }
+
+    let minute = ((datetime - day_start) / 60) as u32;
+    let day = weekday(datetime);
+    availability.weekly_hours.iter().any(|hours| {
+        hours.weekday == day
+            && minute >= hours.start_minute
+            && minute + availability.slot_minutes <= hours.end_minute
+            && (minute - hours.start_minute).is_multiple_of(availability.slot_minutes)
+    })
+}
+
+// Whether `[start, end)` lies inside one block of the template's weekly hours
+fn within_hours(availability: &Availability, start: u64, end: u64) -> bool {
+    let day_start = start - start % SECONDS_PER_DAY;
+    let from = (start - day_start) / 60;
+    let to = (end - day_start).div_ceil(60);
+    let day = weekday(start);
+    availability.weekly_hours.iter().any(|hours| {
+        hours.weekday == day && from >= hours.start_minute as u64 && to <= hours.end_minute as u64
+    })
+}
+
+// The doctor's booked intervals that have not ended yet, by start time
+fn load_bookings(env: &Env, doctor: &Address) -> Vec<BookedInterval> {
+    let now = env.ledger().timestamp();
+    let stored: Vec<BookedInterval> = env
+        .storage()
+        .persistent()
+        .get(&AppointmentKey::Bookings(doctor.clone()))
+        .unwrap_or(Vec::new(env));
+    let mut bookings = Vec::new(env);
+    for booking in stored.iter() {
+        if booking.end > now {
+            bookings.push_back(booking);
+        }
+    }
+    bookings
+}
+
+fn save_bookings(env: &Env, doctor: &Address, bookings: &Vec<BookedInterval>) {
+    let key = AppointmentKey::Bookings(doctor.clone());
+    if bookings.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, bookings);
+    }
+}
+
+fn overlaps_booking(bookings: &Vec<BookedInterval>, start: u64, end: u64) -> bool {
+    bookings
+        .iter()
+        .any(|booking| booking.start < end && start < booking.end)
+}
+
+fn load_offer(env: &Env, doctor: &Address, datetime: u64) -> Option<SlotOffer> {
+    env.storage()
+        .persistent()
+        .get(&AppointmentKey::SlotOffer(doctor.clone(), datetime))
+}
+
+// Panics unless `datetime` is an open future slot that overlaps no booking
+// and is not held for another waitlisted patient; returns the slot length
+fn require_bookable(env: &Env, patient: &Address, doctor: &Address, datetime: u64) -> u32 {
+    let availability = load_availability(env, doctor);
+    if datetime < env.ledger().timestamp() || !is_open_slot(&availability, datetime) {
+        panic!("Slot not available");
+    }
+    let ends_at = datetime + availability.slot_minutes as u64 * 60;
+    if overlaps_booking(&load_bookings(env, doctor), datetime, ends_at) {
+        panic!("Slot already booked");
+    }
+    let registry: Address = env
+        .storage()
+        .instance()
+        .get(&AppointmentKey::InstitutionRegistry)
+        .expect("Not initialized");
+    if !HealthcareRegistryClient::new(env, &registry).is_doctor_bookable(doctor) {
+        panic!("Doctor's institution is not verified");
+    }
+    if let Some(offer) = load_offer(env, doctor, datetime) {
+        if offer.patient != *patient && env.ledger().timestamp() < offer.expires_at {
+            panic!("Slot held for waitlisted patient");
+        }
+    }
+    availability.slot_minutes
+}
+
+fn load_waitlist_node(env: &Env, doctor: &Address, patient: &Address) -> Option<WaitlistNode> {
+    env.storage()
+        .persistent()
+        .get(&AppointmentKey::WaitlistNode(
+            doctor.clone(),
+            patient.clone(),
+        ))
+}
+
+fn waitlist_len(env: &Env, doctor: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&AppointmentKey::WaitlistLen(doctor.clone()))
+        .unwrap_or(0)
+}
+
+// Append a patient to the back of the doctor's waitlist
+fn waitlist_push(env: &Env, doctor: &Address, entry: WaitlistEntry) {
+    let len = waitlist_len(env, doctor);
+    if len >= MAX_WAITLIST_LEN {
+        panic!("Waitlist is full");
+    }
+    let patient = entry.patient.clone();
+    if load_waitlist_node(env, doctor, &patient).is_some() {
+        panic!("Already on waitlist");
+    }
+
+    let tail_key = AppointmentKey::WaitlistTail(doctor.clone());
+    let tail: Option<Address> = env.storage().persistent().get(&tail_key);
+    match &tail {
+        Some(tail) => {
+            let mut node = load_waitlist_node(env, doctor, tail).unwrap();
+            node.next = Some(patient.clone());
+            env.storage().persistent().set(
+                &AppointmentKey::WaitlistNode(doctor.clone(), tail.clone()),
+                &node,
+            );
+        }
+        None => env
+            .storage()
+            .persistent()
+            .set(&AppointmentKey::WaitlistHead(doctor.clone()), &patient),
+    }
+    env.storage().persistent().set(
+        &AppointmentKey::WaitlistNode(doctor.clone(), patient.clone()),
+        &WaitlistNode {
+            entry,
+            prev: tail,
+            next: None,
+        },
+    );
+    env.storage().persistent().set(&tail_key, &patient);
+    env.storage()
+        .persistent()
+        .set(&AppointmentKey::WaitlistLen(doctor.clone()), &(len + 1));
+}
+
+// Unlink a patient from the doctor's waitlist; false if they were not on it
+fn waitlist_remove(env: &Env, doctor: &Address, patient: &Address) -> bool {
+    let Some(node) = load_waitlist_node(env, doctor, patient) else {
+        return false;
+    };
+
+    match &node.prev {
+        Some(prev) => {
+            let mut prev_node = load_waitlist_node(env, doctor, prev).unwrap();
+            prev_node.next = node.next.clone();
+            env.storage().persistent().set(
+                &AppointmentKey::WaitlistNode(doctor.clone(), prev.clone()),
+                &prev_node,
+            );
+        }
+        None => match &node.next {
+            Some(next) => env
+                .storage()
+                .persistent()
+                .set(&AppointmentKey::WaitlistHead(doctor.clone()), next),
+            None => env
+                .storage()
+                .persistent()
+                .remove(&AppointmentKey::WaitlistHead(doctor.clone())),
+        },
+    }
+    match &node.next {
+        Some(next) => {
+            let mut next_node = load_waitlist_node(env, doctor, next).unwrap();
+            next_node.prev = node.prev.clone();
+            env.storage().persistent().set(
+                &AppointmentKey::WaitlistNode(doctor.clone(), next.clone()),
+                &next_node,
+            );
+        }
+        None => match &node.prev {
+            Some(prev) => env
+                .storage()
+                .persistent()
+                .set(&AppointmentKey::WaitlistTail(doctor.clone()), prev),
+            None => env
+                .storage()
+                .persistent()
+                .remove(&AppointmentKey::WaitlistTail(doctor.clone())),
+        },
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&AppointmentKey::WaitlistNode(
+            doctor.clone(),
+            patient.clone(),
+        ));
+    env.storage().persistent().set(
+        &AppointmentKey::WaitlistLen(doctor.clone()),
+        &(waitlist_len(env, doctor) - 1),
+    );
+    true
+}
+
+fn load_waitlist(env: &Env, doctor: &Address) -> Vec<WaitlistEntry> {
+    let mut entries = Vec::new(env);
+    let mut cursor: Option<Address> = env
+        .storage()
+        .persistent()
+        .get(&AppointmentKey::WaitlistHead(doctor.clone()));
+    while let Some(patient) = cursor {
+        let node = load_waitlist_node(env, doctor, &patient).unwrap();
+        entries.push_back(node.entry);
+        cursor = node.next;
+    }
+    entries
+}
+
+// The first waitlisted patient whose window covers `datetime` and who has
+// not been offered it yet. Drops entries whose window has passed on the way.
+fn next_waitlisted(
+    env: &Env,
+    doctor: &Address,
+    datetime: u64,
+    offered_to: &Vec<Address>,
+) -> Option<WaitlistEntry> {
+    let now = env.ledger().timestamp();
+    let mut cursor: Option<Address> = env
+        .storage()
+        .persistent()
+        .get(&AppointmentKey::WaitlistHead(doctor.clone()));
+    while let Some(patient) = cursor {
+        let node = load_waitlist_node(env, doctor, &patient).unwrap();
+        cursor = node.next;
+        let entry = node.entry;
+
+        if entry.latest < now {
+            waitlist_remove(env, doctor, &patient);
+            env.events()
+                .publish((symbol_short!("wl_exp"), doctor.clone()), patient);
+            continue;
+        }
+        if entry.earliest <= datetime
+            && datetime <= entry.latest
+            && !offered_to.contains(&entry.patient)
+        {
+            return Some(entry);
+        }
+    }
+    None
+}
+
+// Store or drop the offer on a slot, keeping the doctor's list of offered
+// slots in step and clear of slots already past
+fn save_offer(env: &Env, doctor: &Address, datetime: u64, offer: Option<&SlotOffer>) {
+    let now = env.ledger().timestamp();
+    let list_key = AppointmentKey::OfferedSlots(doctor.clone());
+    let stored: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&list_key)
+        .unwrap_or(Vec::new(env));
+    let mut offered = Vec::new(env);
+    for slot in stored.iter() {
+        if slot > now && slot != datetime {
+            offered.push_back(slot);
+        }
+    }
+
+    let key = AppointmentKey::SlotOffer(doctor.clone(), datetime);
+    match offer {
+        Some(offer) => {
+            env.storage().persistent().set(&key, offer);
+            offered.push_back(datetime);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+    if offered.is_empty() {
+        env.storage().persistent().remove(&list_key);
+    } else {
+        env.storage().persistent().set(&list_key, &offered);
+    }
+}
+
+// Offer the slot to the first waitlisted patient whose window covers it and
+// who has not been offered it yet. Drops the offer if nobody is left.
+fn offer_slot(env: &Env, doctor: &Address, datetime: u64, offered_to: Vec<Address>) {
+    let now = env.ledger().timestamp();
+    let next = if datetime > now {
+        next_waitlisted(env, doctor, datetime, &offered_to)
+    } else {
+        None
+    };
+
+    let Some(entry) = next else {
+        save_offer(env, doctor, datetime, None);
+        return;
+    };
+
+    let claim_period: u64 = env
+        .storage()
+        .persistent()
+        .get(&AppointmentKey::ClaimPeriod(doctor.clone()))
+        .unwrap_or(DEFAULT_CLAIM_PERIOD);
+    let mut offered_to = offered_to;
+    offered_to.push_back(entry.patient.clone());
+    let offer = SlotOffer {
+        doctor: doctor.clone(),
+        datetime,
+        patient: entry.patient.clone(),
+        expires_at: datetime.min(now + claim_period),
+        offered_to,
+    };
+    save_offer(env, doctor, datetime, Some(&offer));
+
+    env.events().publish(
+        (symbol_short!("slot_off"), doctor.clone()),
+        (entry.patient, datetime, offer.expires_at),
+    );
+}
+
+// Reserve a slot for the appointment, settling any waitlist offer on it
+fn reserve_slot(env: &Env, appointment: &Appointment) {
+    let doctor = &appointment.doctor;
+    let mut bookings = load_bookings(env, doctor);
+    let position = bookings
+        .iter()
+        .position(|booking| booking.start > appointment.datetime)
+        .map_or(bookings.len(), |index| index as u32);
+    bookings.insert(
+        position,
+        BookedInterval {
+            appointment_id: appointment.id,
+            start: appointment.datetime,
+            end: appointment.datetime + appointment.duration_minutes as u64 * 60,
+        },
+    );
+    save_bookings(env, doctor, &bookings);
+    save_offer(env, doctor, appointment.datetime, None);
+}
+
+// Free the appointment's slot and offer it to the doctor's waitlist
+fn release_slot(env: &Env, appointment: &Appointment) {
+    let mut bookings = load_bookings(env, &appointment.doctor);
+    if let Some(index) = bookings
+        .iter()
+        .position(|booking| booking.appointment_id == appointment.id)
+    {
+        bookings.remove(index as u32);
+    }
+    save_bookings(env, &appointment.doctor, &bookings);
+    offer_slot(
+        env,
+        &appointment.doctor,
+        appointment.datetime,
+        Vec::new(env),
+    );
+}
+
+fn load_appointment(env: &Env, appointment_id: u64) -> Appointment {
+    env.storage()
+        .persistent()
+        .get(&AppointmentKey::Appointment(appointment_id))
+        .ok_or(Error::AppointmentNotFound)
+        .unwrap()
+}
+
+fn save_appointment(env: &Env, appointment: &Appointment) {
+    env.storage()
+        .persistent()
+        .set(&AppointmentKey::Appointment(appointment.id), appointment);
+}
+
+// Whether a change made now falls inside the doctor's cancellation window
+fn is_late_change(env: &Env, appointment: &Appointment) -> bool {
+    let window: u64 = env
+        .storage()
+        .persistent()
+        .get(&AppointmentKey::CancellationWindow(
+            appointment.doctor.clone(),
+        ))
+        .unwrap_or(0);
+    env.ledger().timestamp() + window > appointment.datetime
+}
+
+fn reminder_lead_times(env: &Env, doctor: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&AppointmentKey::ReminderLeadTimes(doctor.clone()))
+        .unwrap_or(Vec::from_array(env, DEFAULT_REMINDER_LEAD_TIMES))
+}
+
+fn update_attendance(env: &Env, patient: &Address, update: impl FnOnce(&mut PatientAttendance)) {
+    let key = AppointmentKey::PatientAttendance(patient.clone());
+    let mut attendance: PatientAttendance =
+        env.storage().persistent().get(&key).unwrap_or_default();
+    update(&mut attendance);
+    env.storage().persistent().set(&key, &attendance);
+}
+
+// Escrow the doctor's deposit from the patient, if the doctor requires one
+fn hold_deposit(env: &Env, appointment_id: u64, patient: &Address, doctor: &Address) {
+    let Some(policy) = env
+        .storage()
+        .persistent()
+        .get::<_, DepositPolicy>(&AppointmentKey::DepositPolicy(doctor.clone()))
+    else {
+        return;
+    };
+
+    token::Client::new(env, &policy.token).transfer(
+        patient,
+        env.current_contract_address(),
+        &policy.amount,
+    );
+
+    // A late reschedule settles the old deposit before holding a new one;
+    // keep the settled record rather than overwriting it
+    let key = AppointmentKey::Deposit(appointment_id);
+    if let Some(previous) = env.storage().persistent().get::<_, Deposit>(&key) {
+        if previous.status == DepositStatus::Held {
+            panic!("Deposit already held");
+        }
+        let settled_key = AppointmentKey::SettledDeposits(appointment_id);
+        let mut settled: Vec<Deposit> = env
+            .storage()
+            .persistent()
+            .get(&settled_key)
+            .unwrap_or(Vec::new(env));
+        settled.push_back(previous);
+        env.storage().persistent().set(&settled_key, &settled);
+    }
+
+    let deposit = Deposit {
+        token: policy.token,
+        amount: policy.amount,
+        beneficiary: policy.beneficiary,
+        status: DepositStatus::Held,
+    };
+    env.storage().persistent().set(&key, &deposit);
+
+    env.events().publish(
+        (symbol_short!("dep_held"), appointment_id),
+        (patient.clone(), deposit.amount),
+    );
+}
+
+// Return a held deposit to the patient, or forfeit it to the beneficiary
+fn settle_deposit(env: &Env, appointment: &Appointment, refund: bool) {
+    let key = AppointmentKey::Deposit(appointment.id);
+    let Some(mut deposit) = env.storage().persistent().get::<_, Deposit>(&key) else {
+        return;
+    };
+    if deposit.status != DepositStatus::Held {
+        return;
+    }
+
+    let (recipient, status, topic) = if refund {
+        (
+            appointment.patient.clone(),
+            DepositStatus::Refunded,
+            symbol_short!("dep_ref"),
+        )
+    } else {
+        (
+            deposit.beneficiary.clone(),
+            DepositStatus::Forfeited,
+            symbol_short!("dep_fft"),
+        )
+    };
+    token::Client::new(env, &deposit.token).transfer(
+        &env.current_contract_address(),
+        &recipient,
+        &deposit.amount,
+    );
+    deposit.status = status;
+    env.storage().persistent().set(&key, &deposit);
+
+    env.events()
+        .publish((topic, appointment.id), (recipient, deposit.amount));
 }
 
 #[contracterror]
@@ -59,11 +790,41 @@ pub struct HealthcareRegistry;
 
 #[contractimpl]
 impl HealthcareRegistry {
-    // Set an admin/verifier during initialization
+    // Set the admin that manages verifying authorities
     pub fn init(env: Env, admin: Address) {
+        admin.require_auth();
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Already initialized");
+        }
         env.storage().instance().set(&DataKey::Admin, &admin);
     }
 
+    pub fn add_verifier(env: Env, admin: Address, verifier: Address) {
+        require_admin(&env, &admin);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Verifier(verifier.clone()), &true);
+
+        env.events()
+            .publish((symbol_short!("ver_add"), verifier), admin);
+    }
+
+    pub fn remove_verifier(env: Env, admin: Address, verifier: Address) {
+        require_admin(&env, &admin);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Verifier(verifier.clone()));
+
+        env.events()
+            .publish((symbol_short!("ver_rm"), verifier), admin);
+    }
+
+    pub fn is_verifier(env: Env, verifier: Address) -> bool {
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        admin == Some(verifier.clone())
+            || env.storage().persistent().has(&DataKey::Verifier(verifier))
+    }
+
     pub fn register_institution(
         env: Env,
         wallet: Address,
@@ -82,11 +843,25 @@ impl HealthcareRegistry {
             name,
             license_id,
             metadata,
-            is_verified: false,
+            status: InstitutionStatus::Pending,
+            status_changed_by: None,
+            status_changed_at: env.ledger().timestamp(),
         };
 
         env.storage().persistent().set(&key, &data);
 
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::InstitutionCount)
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::InstitutionAt(count), &wallet);
+        env.storage()
+            .persistent()
+            .set(&DataKey::InstitutionCount, &(count + 1));
+
         // Event emission
         env.events()
             .publish((symbol_short!("reg"), wallet), symbol_short!("success"));
@@ -110,91 +885,348 @@ impl HealthcareRegistry {
         env.storage().persistent().set(&key, &data);
     }
 
+    // Verify a pending institution, or reinstate a suspended one
     pub fn verify_institution(env: Env, verifier: Address, wallet: Address) {
-        verifier.require_auth();
+        // Access Control: Check if caller is a verifying authority
+        require_verifier(&env, &verifier);
 
-        // Access Control: Check if caller is the admin
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if verifier != admin {
-            panic!("Not authorized to verify");
+        let data = load_institution(&env, &wallet);
+        if !matches!(
+            data.status,
+            InstitutionStatus::Pending | InstitutionStatus::Suspended
+        ) {
+            panic!("Institution cannot be verified");
         }
-
-        let key = DataKey::Inst(wallet.clone());
-        let mut data: InstitutionData = env.storage().persistent().get(&key).expect("Not found");
-
-        data.is_verified = true;
-        env.storage().persistent().set(&key, &data);
+        set_institution_status(&env, &wallet, data, InstitutionStatus::Verified, &verifier);
     }
-}
-
-#[contract]
-pub struct AppointmentScheduling;
-
-#[contractimpl]
-impl AppointmentScheduling {
-    pub fn create_appointment(env: Env, patient: Address, doctor: Address, datetime: u64) -> u64 {
-        patient.require_auth();
 
-        // Get next appointment ID
-        let counter_key = AppointmentKey::AppointmentCounter;
-        let appointment_id = env.storage().persistent().get(&counter_key).unwrap_or(0u64) + 1;
-
-        // Create appointment
-        let appointment = Appointment {
-            id: appointment_id,
-            patient: patient.clone(),
-            doctor: doctor.clone(),
-            datetime,
-            status: AppointmentStatus::Scheduled,
-        };
+    pub fn suspend_institution(env: Env, verifier: Address, wallet: Address) {
+        require_verifier(&env, &verifier);
 
-        // Store appointment
-        let appointment_key = AppointmentKey::Appointment(appointment_id);
-        env.storage()
-            .persistent()
-            .set(&appointment_key, &appointment);
+        let data = load_institution(&env, &wallet);
+        if data.status != InstitutionStatus::Verified {
+            panic!("Only verified institutions can be suspended");
+        }
+        set_institution_status(&env, &wallet, data, InstitutionStatus::Suspended, &verifier);
+    }
 
-        // Update counter
-        env.storage()
-            .persistent()
-            .set(&counter_key, &appointment_id);
+    // Deregister an institution, by itself or by the admin. Closed is final.
+    pub fn close_institution(env: Env, caller: Address, wallet: Address) {
+        if caller == wallet {
+            caller.require_auth();
+        } else {
+            require_admin(&env, &caller);
+        }
 
-        // Add to patient's appointments
-        let patient_key = AppointmentKey::UserAppointments(patient.clone());
-        let mut patient_appointments: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&patient_key)
-            .unwrap_or(Vec::new(&env));
-        patient_appointments.push_back(appointment_id);
-        env.storage()
-            .persistent()
-            .set(&patient_key, &patient_appointments);
+        let data = load_institution(&env, &wallet);
+        if data.status == InstitutionStatus::Closed {
+            panic!("Institution already closed");
+        }
+        set_institution_status(&env, &wallet, data, InstitutionStatus::Closed, &caller);
+    }
 
-        // Add to doctor's appointments
-        let doctor_key = AppointmentKey::UserAppointments(doctor.clone());
-        let mut doctor_appointments: Vec<u64> = env
+    // Institutions with `status`, in registration order. A page covers
+    // `limit` registrations and may hold fewer matches.
+    pub fn list_institutions(
+        env: Env,
+        status: InstitutionStatus,
+        cursor: u32,
+        limit: u32,
+    ) -> InstitutionPage {
+        let limit = page_limit(limit);
+        let count: u32 = env
             .storage()
             .persistent()
-            .get(&doctor_key)
-            .unwrap_or(Vec::new(&env));
-        doctor_appointments.push_back(appointment_id);
-        env.storage()
-            .persistent()
-            .set(&doctor_key, &doctor_appointments);
+            .get(&DataKey::InstitutionCount)
+            .unwrap_or(0);
 
-        // Emit event
-        env.events().publish(
-            (symbol_short!("appt_cr"), appointment_id),
-            (patient, doctor),
-        );
+        let end = count.min(cursor.saturating_add(limit));
+        let mut institutions = Vec::new(&env);
+        for index in cursor..end {
+            let wallet: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::InstitutionAt(index))
+                .unwrap();
+            let data = load_institution(&env, &wallet);
+            if data.status == status {
+                institutions.push_back(InstitutionSummary { wallet, data });
+            }
+        }
 
-        appointment_id
+        InstitutionPage {
+            institutions,
+            next_cursor: if end < count { Some(end) } else { None },
+        }
     }
 
-    pub fn cancel_appointment(env: Env, patient: Address, appointment_id: u64) {
-        patient.require_auth();
-
+    // Add a doctor to a verified institution's roster. Both sign, and a
+    // doctor has one institution.
+    pub fn add_doctor(env: Env, wallet: Address, doctor: Address) {
+        wallet.require_auth();
+        doctor.require_auth();
+
+        if load_institution(&env, &wallet).status != InstitutionStatus::Verified {
+            panic!("Institution is not verified");
+        }
+        let key = DataKey::DoctorInstitution(doctor.clone());
+        if env.storage().persistent().has(&key) {
+            panic!("Doctor already affiliated");
+        }
+        env.storage().persistent().set(&key, &wallet);
+
+        env.events()
+            .publish((symbol_short!("doc_add"), wallet), doctor);
+    }
+
+    pub fn remove_doctor(env: Env, wallet: Address, doctor: Address) {
+        wallet.require_auth();
+
+        let key = DataKey::DoctorInstitution(doctor.clone());
+        let institution: Option<Address> = env.storage().persistent().get(&key);
+        if institution != Some(wallet.clone()) {
+            panic!("Doctor not affiliated with institution");
+        }
+        env.storage().persistent().remove(&key);
+
+        env.events()
+            .publish((symbol_short!("doc_rm"), wallet), doctor);
+    }
+
+    // The doctor leaves their institution's roster
+    pub fn leave_institution(env: Env, doctor: Address) {
+        doctor.require_auth();
+
+        let key = DataKey::DoctorInstitution(doctor.clone());
+        let wallet: Address = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Doctor not affiliated with institution");
+        env.storage().persistent().remove(&key);
+
+        env.events()
+            .publish((symbol_short!("doc_rm"), wallet), doctor);
+    }
+
+    pub fn get_doctor_institution(env: Env, doctor: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DoctorInstitution(doctor))
+    }
+
+    // Whether the doctor belongs to a currently verified institution
+    pub fn is_doctor_bookable(env: Env, doctor: Address) -> bool {
+        let Some(wallet) = Self::get_doctor_institution(env.clone(), doctor) else {
+            return false;
+        };
+        load_institution(&env, &wallet).status == InstitutionStatus::Verified
+    }
+}
+
+fn book_appointment(env: &Env, patient: Address, doctor: Address, datetime: u64) -> u64 {
+    // Only open, unbooked slots in the doctor's published template
+    let duration_minutes = require_bookable(env, &patient, &doctor, datetime);
+
+    // Get next appointment ID
+    let counter_key = AppointmentKey::AppointmentCounter;
+    let appointment_id = env.storage().persistent().get(&counter_key).unwrap_or(0u64) + 1;
+
+    // Create appointment
+    let appointment = Appointment {
+        id: appointment_id,
+        patient: patient.clone(),
+        doctor: doctor.clone(),
+        datetime,
+        duration_minutes,
+        status: AppointmentStatus::Scheduled,
+        reschedules: Vec::new(env),
+        canceled_by: None,
+        cancellation_reason: None,
+        checked_in_at: None,
+        checked_in_by: None,
+        started_at: None,
+        completed_at: None,
+        reminded_lead_times: Vec::new(env),
+    };
+
+    // Store appointment
+    let appointment_key = AppointmentKey::Appointment(appointment_id);
+    env.storage()
+        .persistent()
+        .set(&appointment_key, &appointment);
+
+    // Update counter
+    env.storage()
+        .persistent()
+        .set(&counter_key, &appointment_id);
+
+    hold_deposit(env, appointment_id, &patient, &doctor);
+    reserve_slot(env, &appointment);
+
+    // Add to patient's appointments
+    let patient_key = AppointmentKey::UserAppointments(patient.clone());
+    let mut patient_appointments: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&patient_key)
+        .unwrap_or(Vec::new(env));
+    patient_appointments.push_back(appointment_id);
+    env.storage()
+        .persistent()
+        .set(&patient_key, &patient_appointments);
+
+    // Add to doctor's appointments
+    let doctor_key = AppointmentKey::UserAppointments(doctor.clone());
+    let mut doctor_appointments: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&doctor_key)
+        .unwrap_or(Vec::new(env));
+    doctor_appointments.push_back(appointment_id);
+    env.storage()
+        .persistent()
+        .set(&doctor_key, &doctor_appointments);
+
+    // Emit event
+    env.events().publish(
+        (symbol_short!("appt_cr"), appointment_id),
+        (patient, doctor),
+    );
+
+    appointment_id
+}
+
+#[contract]
+pub struct AppointmentScheduling;
+
+#[contractimpl]
+impl AppointmentScheduling {
+    // Only book doctors whose institution is verified in the given
+    // HealthcareRegistry
+    pub fn initialize(env: Env, admin: Address, institution_registry: Address) {
+        admin.require_auth();
+        if env.storage().instance().has(&AppointmentKey::Admin) {
+            panic!("Already initialized");
+        }
+        env.storage().instance().set(&AppointmentKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&AppointmentKey::InstitutionRegistry, &institution_registry);
+
+        env.events()
+            .publish((symbol_short!("inst_reg"),), institution_registry);
+    }
+
+    pub fn get_institution_registry(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&AppointmentKey::InstitutionRegistry)
+            .expect("Not initialized")
+    }
+
+    // Publish the doctor's weekly hours, slot length and blackout dates,
+    // replacing any earlier template. Every upcoming booking must still fall
+    // within the new hours; new slots overlapping one stay unbookable until
+    // it is released. Blackout dates can change any time.
+    pub fn set_availability(env: Env, doctor: Address, availability: Availability) {
+        doctor.require_auth();
+
+        if availability.slot_minutes == 0 {
+            panic!("Slot length must be positive");
+        }
+        for hours in availability.weekly_hours.iter() {
+            if hours.weekday > 6
+                || hours.start_minute >= hours.end_minute
+                || hours.end_minute > MINUTES_PER_DAY
+            {
+                panic!("Invalid working hours");
+            }
+        }
+        for date in availability.blackout_dates.iter() {
+            if !date.is_multiple_of(SECONDS_PER_DAY) {
+                panic!("Blackout dates must be UTC midnight");
+            }
+        }
+        for booking in load_bookings(&env, &doctor).iter() {
+            if !within_hours(&availability, booking.start, booking.end) {
+                panic!("Doctor has upcoming bookings outside these hours");
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&AppointmentKey::Availability(doctor.clone()), &availability);
+
+        env.events()
+            .publish((symbol_short!("avail"), doctor), availability.slot_minutes);
+    }
+
+    pub fn get_availability(env: Env, doctor: Address) -> Option<Availability> {
+        env.storage()
+            .persistent()
+            .get(&AppointmentKey::Availability(doctor))
+    }
+
+    // Future slot start times in [start, end) that overlap no booking and are
+    // not held for a waitlisted patient, oldest first
+    pub fn get_free_slots(env: Env, doctor: Address, start: u64, end: u64) -> Vec<u64> {
+        if end <= start {
+            panic!("Invalid date range");
+        }
+        if end - start > MAX_SLOT_QUERY_DAYS * SECONDS_PER_DAY {
+            panic!("Date range too large");
+        }
+
+        let availability = load_availability(&env, &doctor);
+        let bookings = load_bookings(&env, &doctor);
+        let slot_length = availability.slot_minutes as u64 * 60;
+        let now = env.ledger().timestamp();
+        let mut held = Vec::new(&env);
+        let offered: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&AppointmentKey::OfferedSlots(doctor.clone()))
+            .unwrap_or(Vec::new(&env));
+        for datetime in offered.iter() {
+            if datetime >= start
+                && datetime < end
+                && load_offer(&env, &doctor, datetime).is_some_and(|offer| now < offer.expires_at)
+            {
+                held.push_back(datetime);
+            }
+        }
+
+        let mut slots = Vec::new(&env);
+        let mut day_start = start - start % SECONDS_PER_DAY;
+        while day_start < end {
+            let mut minute = 0;
+            while minute + availability.slot_minutes <= MINUTES_PER_DAY {
+                let datetime = day_start + minute as u64 * 60;
+                if datetime >= start.max(now)
+                    && datetime < end
+                    && !held.contains(datetime)
+                    && is_open_slot(&availability, datetime)
+                    && !overlaps_booking(&bookings, datetime, datetime + slot_length)
+                {
+                    slots.push_back(datetime);
+                }
+                minute += availability.slot_minutes;
+            }
+            day_start += SECONDS_PER_DAY;
+        }
+
+        slots
+    }
+
+    pub fn create_appointment(env: Env, patient: Address, doctor: Address, datetime: u64) -> u64 {
+        patient.require_auth();
+        book_appointment(&env, patient, doctor, datetime)
+    }
+
+    pub fn cancel_appointment(env: Env, patient: Address, appointment_id: u64) {
+        patient.require_auth();
+
         let appointment_key = AppointmentKey::Appointment(appointment_id);
         let mut appointment: Appointment = env
             .storage()
@@ -212,16 +1244,174 @@ impl AppointmentScheduling {
             panic!("Can only cancel scheduled appointments");
         }
 
+        // Cancelling inside the doctor's window counts against the patient
+        // and forfeits any deposit
+        let late = is_late_change(&env, &appointment);
+        if late {
+            update_attendance(&env, &patient, |attendance| {
+                attendance.late_cancellations += 1
+            });
+        }
+        settle_deposit(&env, &appointment, !late);
+
         appointment.status = AppointmentStatus::Canceled;
+        appointment.canceled_by = Some(patient.clone());
         env.storage()
             .persistent()
             .set(&appointment_key, &appointment);
 
+        // Release the slot
+        release_slot(&env, &appointment);
+
         // Emit event
         env.events()
             .publish((symbol_short!("appt_can"), appointment_id), patient);
     }
 
+    // Doctor-initiated cancellation; never counts against the patient
+    pub fn cancel_by_doctor(env: Env, doctor: Address, appointment_id: u64, reason: String) {
+        doctor.require_auth();
+
+        let mut appointment = load_appointment(&env, appointment_id);
+        if appointment.doctor != doctor {
+            panic!("Unauthorized to cancel this appointment");
+        }
+        if !matches!(appointment.status, AppointmentStatus::Scheduled) {
+            panic!("Can only cancel scheduled appointments");
+        }
+        if reason.is_empty() {
+            panic!("Cancellation reason required");
+        }
+
+        appointment.status = AppointmentStatus::Canceled;
+        appointment.canceled_by = Some(doctor.clone());
+        appointment.cancellation_reason = Some(reason.clone());
+        save_appointment(&env, &appointment);
+        release_slot(&env, &appointment);
+        settle_deposit(&env, &appointment, true);
+
+        env.events().publish(
+            (symbol_short!("appt_can"), appointment_id),
+            (doctor, reason),
+        );
+    }
+
+    // Move a scheduled appointment to another open slot, keeping its ID.
+    // A patient rescheduling inside the cancellation window is counted as a
+    // late cancellation.
+    pub fn reschedule_appointment(
+        env: Env,
+        caller: Address,
+        appointment_id: u64,
+        new_datetime: u64,
+    ) {
+        caller.require_auth();
+
+        let mut appointment = load_appointment(&env, appointment_id);
+        if caller != appointment.patient && caller != appointment.doctor {
+            panic!("Unauthorized to reschedule this appointment");
+        }
+        if !matches!(appointment.status, AppointmentStatus::Scheduled) {
+            panic!("Can only reschedule scheduled appointments");
+        }
+
+        // Free the current slot first so a move may overlap it
+        release_slot(&env, &appointment);
+        let duration_minutes = require_bookable(
+            &env,
+            &appointment.patient,
+            &appointment.doctor,
+            new_datetime,
+        );
+        // A late move by the patient counts like a late cancellation: the
+        // deposit is forfeited and a fresh one secures the new slot
+        let late = caller == appointment.patient && is_late_change(&env, &appointment);
+        if late {
+            update_attendance(&env, &caller, |attendance| {
+                attendance.late_cancellations += 1
+            });
+            settle_deposit(&env, &appointment, false);
+        }
+
+        appointment.reschedules.push_back(Reschedule {
+            previous_datetime: appointment.datetime,
+            rescheduled_by: caller,
+            timestamp: env.ledger().timestamp(),
+        });
+        appointment.datetime = new_datetime;
+        appointment.duration_minutes = duration_minutes;
+        appointment.reminded_lead_times = Vec::new(&env);
+        save_appointment(&env, &appointment);
+        reserve_slot(&env, &appointment);
+        if late {
+            hold_deposit(
+                &env,
+                appointment_id,
+                &appointment.patient,
+                &appointment.doctor,
+            );
+        }
+
+        env.events()
+            .publish((symbol_short!("appt_rsch"), appointment_id), new_datetime);
+    }
+
+    // Mark a patient who did not attend once the slot has passed. The slot
+    // stays booked.
+    pub fn mark_no_show(env: Env, doctor: Address, appointment_id: u64) {
+        doctor.require_auth();
+
+        let mut appointment = load_appointment(&env, appointment_id);
+        if appointment.doctor != doctor {
+            panic!("Unauthorized to update this appointment");
+        }
+        if !matches!(appointment.status, AppointmentStatus::Scheduled) {
+            panic!("Can only mark scheduled appointments as no-show");
+        }
+        let slot_end = appointment.datetime + appointment.duration_minutes as u64 * 60;
+        if env.ledger().timestamp() < slot_end {
+            panic!("Slot has not passed yet");
+        }
+
+        appointment.status = AppointmentStatus::NoShow;
+        save_appointment(&env, &appointment);
+        update_attendance(&env, &appointment.patient, |attendance| {
+            attendance.no_shows += 1
+        });
+        settle_deposit(&env, &appointment, false);
+
+        env.events().publish(
+            (symbol_short!("appt_ns"), appointment_id),
+            appointment.patient,
+        );
+    }
+
+    // Seconds before a slot within which patient cancellations are late
+    pub fn set_cancellation_window(env: Env, doctor: Address, window_seconds: u64) {
+        doctor.require_auth();
+        env.storage().persistent().set(
+            &AppointmentKey::CancellationWindow(doctor.clone()),
+            &window_seconds,
+        );
+
+        env.events()
+            .publish((symbol_short!("cxl_win"), doctor), window_seconds);
+    }
+
+    pub fn get_cancellation_window(env: Env, doctor: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&AppointmentKey::CancellationWindow(doctor))
+            .unwrap_or(0)
+    }
+
+    pub fn get_patient_attendance(env: Env, patient: Address) -> PatientAttendance {
+        env.storage()
+            .persistent()
+            .get(&AppointmentKey::PatientAttendance(patient))
+            .unwrap_or_default()
+    }
+
     pub fn complete_appointment(env: Env, doctor: Address, appointment_id: u64) {
         doctor.require_auth();
 
@@ -233,25 +1423,393 @@ impl AppointmentScheduling {
             .ok_or(Error::AppointmentNotFound)
             .unwrap();
 
-        // Only doctor can complete, and only if appointment is scheduled
+        // Only doctor can complete, and only if appointment is still open
         if appointment.doctor != doctor {
             panic!("Unauthorized to complete this appointment");
         }
 
-        if !matches!(appointment.status, AppointmentStatus::Scheduled) {
-            panic!("Can only complete scheduled appointments");
+        if !matches!(
+            appointment.status,
+            AppointmentStatus::Scheduled
+                | AppointmentStatus::CheckedIn
+                | AppointmentStatus::InProgress
+        ) {
+            panic!("Can only complete open appointments");
         }
 
         appointment.status = AppointmentStatus::Completed;
+        appointment.completed_at = Some(env.ledger().timestamp());
         env.storage()
             .persistent()
             .set(&appointment_key, &appointment);
+        settle_deposit(&env, &appointment, true);
 
         // Emit event
         env.events()
             .publish((symbol_short!("appt_cmp"), appointment_id), doctor);
     }
 
+    // Require patients to escrow `amount` of `token` when booking with this
+    // doctor. Appointments already booked keep the terms they were booked on.
+    pub fn set_deposit_policy(
+        env: Env,
+        doctor: Address,
+        token: Address,
+        amount: i128,
+        beneficiary: Address,
+    ) {
+        doctor.require_auth();
+        if amount <= 0 {
+            panic!("Deposit amount must be positive");
+        }
+
+        let policy = DepositPolicy {
+            token,
+            amount,
+            beneficiary,
+        };
+        env.storage()
+            .persistent()
+            .set(&AppointmentKey::DepositPolicy(doctor.clone()), &policy);
+
+        env.events()
+            .publish((symbol_short!("dep_pol"), doctor), amount);
+    }
+
+    pub fn clear_deposit_policy(env: Env, doctor: Address) {
+        doctor.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&AppointmentKey::DepositPolicy(doctor.clone()));
+
+        env.events()
+            .publish((symbol_short!("dep_pol"), doctor), 0i128);
+    }
+
+    pub fn get_deposit_policy(env: Env, doctor: Address) -> Option<DepositPolicy> {
+        env.storage()
+            .persistent()
+            .get(&AppointmentKey::DepositPolicy(doctor))
+    }
+
+    pub fn get_deposit(env: Env, appointment_id: u64) -> Option<Deposit> {
+        env.storage()
+            .persistent()
+            .get(&AppointmentKey::Deposit(appointment_id))
+    }
+
+    // Earlier deposits on an appointment, oldest first, settled when a late
+    // reschedule replaced them
+    pub fn get_settled_deposits(env: Env, appointment_id: u64) -> Vec<Deposit> {
+        env.storage()
+            .persistent()
+            .get(&AppointmentKey::SettledDeposits(appointment_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // Refund a deposit the doctor never settled. The patient can reclaim it
+    // once `DEPOSIT_RECLAIM_DELAY` has passed since the slot ended and the
+    // appointment was neither completed nor marked a no-show.
+    pub fn reclaim_deposit(env: Env, patient: Address, appointment_id: u64) {
+        patient.require_auth();
+
+        let appointment = load_appointment(&env, appointment_id);
+        if appointment.patient != patient {
+            panic!("Unauthorized to reclaim this deposit");
+        }
+        if !matches!(
+            appointment.status,
+            AppointmentStatus::Scheduled
+                | AppointmentStatus::CheckedIn
+                | AppointmentStatus::InProgress
+        ) {
+            panic!("Appointment already settled");
+        }
+        if !env
+            .storage()
+            .persistent()
+            .get::<_, Deposit>(&AppointmentKey::Deposit(appointment_id))
+            .is_some_and(|deposit| deposit.status == DepositStatus::Held)
+        {
+            panic!("No deposit held");
+        }
+        let slot_end = appointment.datetime + appointment.duration_minutes as u64 * 60;
+        if env.ledger().timestamp() < slot_end + DEPOSIT_RECLAIM_DELAY {
+            panic!("Deposit cannot be reclaimed yet");
+        }
+
+        settle_deposit(&env, &appointment, true);
+    }
+
+    // Let a front-desk address check patients in on the doctor's behalf
+    pub fn add_front_desk(env: Env, doctor: Address, desk: Address) {
+        doctor.require_auth();
+        env.storage().persistent().set(
+            &AppointmentKey::FrontDesk(doctor.clone(), desk.clone()),
+            &true,
+        );
+
+        env.events()
+            .publish((symbol_short!("desk_add"), doctor), desk);
+    }
+
+    pub fn remove_front_desk(env: Env, doctor: Address, desk: Address) {
+        doctor.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&AppointmentKey::FrontDesk(doctor.clone(), desk.clone()));
+
+        env.events()
+            .publish((symbol_short!("desk_rm"), doctor), desk);
+    }
+
+    // Record the patient's arrival, by the patient or the doctor's front desk
+    pub fn check_in(env: Env, caller: Address, appointment_id: u64) {
+        caller.require_auth();
+
+        let mut appointment = load_appointment(&env, appointment_id);
+        if caller != appointment.patient
+            && !env.storage().persistent().has(&AppointmentKey::FrontDesk(
+                appointment.doctor.clone(),
+                caller.clone(),
+            ))
+        {
+            panic!("Unauthorized to check in this appointment");
+        }
+        if !matches!(appointment.status, AppointmentStatus::Scheduled) {
+            panic!("Can only check in scheduled appointments");
+        }
+
+        let now = env.ledger().timestamp();
+        appointment.status = AppointmentStatus::CheckedIn;
+        appointment.checked_in_at = Some(now);
+        appointment.checked_in_by = Some(caller.clone());
+        save_appointment(&env, &appointment);
+
+        env.events()
+            .publish((symbol_short!("appt_chk"), appointment_id), (caller, now));
+    }
+
+    pub fn start_visit(env: Env, doctor: Address, appointment_id: u64) {
+        doctor.require_auth();
+
+        let mut appointment = load_appointment(&env, appointment_id);
+        if appointment.doctor != doctor {
+            panic!("Unauthorized to update this appointment");
+        }
+        if !matches!(appointment.status, AppointmentStatus::CheckedIn) {
+            panic!("Patient has not checked in");
+        }
+
+        let now = env.ledger().timestamp();
+        appointment.status = AppointmentStatus::InProgress;
+        appointment.started_at = Some(now);
+        save_appointment(&env, &appointment);
+
+        env.events()
+            .publish((symbol_short!("appt_st"), appointment_id), now);
+    }
+
+    // Seconds before an appointment at which reminders go out, largest first
+    pub fn set_reminder_lead_times(env: Env, doctor: Address, lead_times: Vec<u64>) {
+        doctor.require_auth();
+
+        for i in 1..lead_times.len() {
+            if lead_times.get_unchecked(i) >= lead_times.get_unchecked(i - 1) {
+                panic!("Lead times must be in descending order");
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&AppointmentKey::ReminderLeadTimes(doctor), &lead_times);
+    }
+
+    pub fn get_reminder_lead_times(env: Env, doctor: Address) -> Vec<u64> {
+        reminder_lead_times(&env, &doctor)
+    }
+
+    // Emit an `appt_rem` event for every lead time reached since the last
+    // call, until the appointment starts. Meant to be polled by an off-chain
+    // notifier; returns how many reminders went out.
+    pub fn send_due_reminders(env: Env, appointment_id: u64) -> u32 {
+        let mut appointment = load_appointment(&env, appointment_id);
+        let now = env.ledger().timestamp();
+        if !matches!(appointment.status, AppointmentStatus::Scheduled)
+            || now >= appointment.datetime
+        {
+            return 0;
+        }
+
+        // Track lead times by value so a doctor editing the list neither
+        // repeats nor skips a reminder
+        let mut sent = 0;
+        for lead_time in reminder_lead_times(&env, &appointment.doctor).iter() {
+            if now + lead_time < appointment.datetime {
+                break;
+            }
+            if appointment.reminded_lead_times.contains(lead_time) {
+                continue;
+            }
+            env.events().publish(
+                (symbol_short!("appt_rem"), appointment_id),
+                (appointment.patient.clone(), appointment.datetime, lead_time),
+            );
+            appointment.reminded_lead_times.push_back(lead_time);
+            sent += 1;
+        }
+
+        if sent > 0 {
+            save_appointment(&env, &appointment);
+        }
+        sent
+    }
+
+    // Total wait (from the later of check-in and the booked time until the
+    // visit starts) and visit length for appointments booked in [start, end).
+    // A page covers `limit` of the doctor's appointments and may count fewer
+    // visits.
+    pub fn get_visit_stats(
+        env: Env,
+        doctor: Address,
+        start: u64,
+        end: u64,
+        cursor: u32,
+        limit: u32,
+    ) -> VisitStatsPage {
+        let appointment_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&AppointmentKey::UserAppointments(doctor.clone()))
+            .unwrap_or(Vec::new(&env));
+        let page_end = appointment_ids
+            .len()
+            .min(cursor.saturating_add(page_limit(limit)));
+
+        let mut visits = 0;
+        let mut total_wait = 0;
+        let mut total_visit = 0;
+        for index in cursor..page_end {
+            let appointment = load_appointment(&env, appointment_ids.get_unchecked(index));
+            if appointment.doctor != doctor
+                || appointment.datetime < start
+                || appointment.datetime >= end
+            {
+                continue;
+            }
+            let (Some(checked_in_at), Some(started_at), Some(completed_at)) = (
+                appointment.checked_in_at,
+                appointment.started_at,
+                appointment.completed_at,
+            ) else {
+                continue;
+            };
+
+            visits += 1;
+            total_wait += started_at.saturating_sub(checked_in_at.max(appointment.datetime));
+            total_visit += completed_at - started_at;
+        }
+
+        VisitStatsPage {
+            visits,
+            total_wait_seconds: total_wait,
+            total_visit_seconds: total_visit,
+            next_cursor: if page_end < appointment_ids.len() {
+                Some(page_end)
+            } else {
+                None
+            },
+        }
+    }
+
+    // Wait for a freed slot with this doctor between `earliest` and `latest`.
+    // A doctor's waitlist holds at most `MAX_WAITLIST_LEN` patients.
+    pub fn join_waitlist(env: Env, patient: Address, doctor: Address, earliest: u64, latest: u64) {
+        patient.require_auth();
+
+        if latest < earliest || latest < env.ledger().timestamp() {
+            panic!("Invalid date range");
+        }
+        waitlist_push(
+            &env,
+            &doctor,
+            WaitlistEntry {
+                patient: patient.clone(),
+                earliest,
+                latest,
+                joined_at: env.ledger().timestamp(),
+            },
+        );
+
+        env.events().publish(
+            (symbol_short!("wl_join"), doctor),
+            (patient, earliest, latest),
+        );
+    }
+
+    pub fn leave_waitlist(env: Env, patient: Address, doctor: Address) {
+        patient.require_auth();
+
+        if !waitlist_remove(&env, &doctor, &patient) {
+            panic!("Not on waitlist");
+        }
+
+        env.events()
+            .publish((symbol_short!("wl_leave"), doctor), patient);
+    }
+
+    pub fn get_waitlist(env: Env, doctor: Address) -> Vec<WaitlistEntry> {
+        load_waitlist(&env, &doctor)
+    }
+
+    // How long a waitlisted patient has to claim an offered slot
+    pub fn set_claim_period(env: Env, doctor: Address, claim_seconds: u64) {
+        doctor.require_auth();
+        if claim_seconds == 0 {
+            panic!("Claim period must be positive");
+        }
+        env.storage()
+            .persistent()
+            .set(&AppointmentKey::ClaimPeriod(doctor.clone()), &claim_seconds);
+    }
+
+    pub fn get_slot_offer(env: Env, doctor: Address, datetime: u64) -> Option<SlotOffer> {
+        load_offer(&env, &doctor, datetime)
+    }
+
+    // Book a slot offered to the patient; takes them off the waitlist
+    pub fn claim_slot_offer(env: Env, patient: Address, doctor: Address, datetime: u64) -> u64 {
+        patient.require_auth();
+
+        let offer = load_offer(&env, &doctor, datetime).expect("Offer not found");
+        if offer.patient != patient {
+            panic!("Offer is for another patient");
+        }
+        if env.ledger().timestamp() >= offer.expires_at {
+            panic!("Offer expired");
+        }
+
+        let appointment_id = book_appointment(&env, patient.clone(), doctor.clone(), datetime);
+
+        waitlist_remove(&env, &doctor, &patient);
+
+        appointment_id
+    }
+
+    // Pass an unclaimed offer to the next eligible waitlisted patient. Anyone
+    // may call this once the claim period has run out.
+    pub fn advance_slot_offer(env: Env, doctor: Address, datetime: u64) {
+        let offer = load_offer(&env, &doctor, datetime).expect("Offer not found");
+        if env.ledger().timestamp() < offer.expires_at {
+            panic!("Offer still open");
+        }
+
+        offer_slot(&env, &doctor, datetime, offer.offered_to);
+    }
+
+    pub fn get_appointment(env: Env, appointment_id: u64) -> Appointment {
+        load_appointment(&env, appointment_id)
+    }
+
     pub fn get_appointments(env: Env, user: Address) -> Vec<Appointment> {
         let user_key = AppointmentKey::UserAppointments(user);
         let appointment_ids: Vec<u64> = env
@@ -1,11 +1,15 @@
 #[cfg(test)]
 mod test {
     use crate::{
-        AppointmentScheduling, AppointmentSchedulingClient, AppointmentStatus, HealthcareRegistry,
-        HealthcareRegistryClient,
+        AppointmentScheduling, AppointmentSchedulingClient, AppointmentStatus, Availability,
+        DepositStatus, HealthcareRegistry, HealthcareRegistryClient, InstitutionStatus,
+        WorkingHours, MAX_PAGE_SIZE,
     };
 
-    use soroban_sdk::{testutils::Address as _, Address, Env, String, Vec};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+        token, vec, Address, Env, IntoVal, String, Vec,
+    };
 
     fn setup_test(env: &Env) -> (HealthcareRegistryClient<'static>, Address, Address) {
         // Updated from register_contract to register
@@ -15,23 +19,79 @@ mod test {
         let admin = Address::generate(env);
         let institution = Address::generate(env);
 
+        env.mock_all_auths();
         client.init(&admin);
 
         (client, admin, institution)
     }
 
-    fn setup_appointment_test(
+    // Scheduling gated on `registry`, with `doctor` open around the clock
+    fn setup_scheduling(
         env: &Env,
-    ) -> (AppointmentSchedulingClient<'static>, Address, Address) {
+        registry: &Address,
+        doctor: &Address,
+    ) -> AppointmentSchedulingClient<'static> {
         let contract_id = env.register(AppointmentScheduling, ());
         let client = AppointmentSchedulingClient::new(env, &contract_id);
 
+        // Open around the clock so tests can book any half hour
+        let mut weekly_hours = Vec::new(env);
+        for weekday in 0..7 {
+            weekly_hours.push_back(WorkingHours {
+                weekday,
+                start_minute: 0,
+                end_minute: 1440,
+            });
+        }
+        env.mock_all_auths();
+        client.initialize(&Address::generate(env), registry);
+        client.set_availability(
+            doctor,
+            &Availability {
+                weekly_hours,
+                slot_minutes: 30,
+                blackout_dates: Vec::new(env),
+            },
+        );
+
+        client
+    }
+
+    // A doctor at a verified institution, open around the clock
+    fn setup_appointment_test(
+        env: &Env,
+    ) -> (AppointmentSchedulingClient<'static>, Address, Address) {
+        let (registry, admin, institution) = setup_test(env);
         let patient = Address::generate(env);
         let doctor = Address::generate(env);
 
+        let name = String::from_str(env, "General Hospital");
+        registry.register_institution(&institution, &name, &name, &name);
+        registry.verify_institution(&admin, &institution);
+        registry.add_doctor(&institution, &doctor);
+
+        let client = setup_scheduling(env, &registry.address, &doctor);
         (client, patient, doctor)
     }
 
+    // Monday 2022-01-03, 09:00-11:00 UTC in one-hour slots
+    const MONDAY: u64 = 1641168000;
+
+    fn monday_mornings(env: &Env) -> Availability {
+        Availability {
+            weekly_hours: vec![
+                env,
+                WorkingHours {
+                    weekday: 0,
+                    start_minute: 9 * 60,
+                    end_minute: 11 * 60,
+                },
+            ],
+            slot_minutes: 60,
+            blackout_dates: Vec::new(env),
+        }
+    }
+
     #[test]
     fn test_register_and_get() {
         let env = Env::default();
@@ -72,7 +132,7 @@ mod test {
         client.verify_institution(&admin, &inst_addr);
 
         let data = client.get_institution(&inst_addr);
-        assert_eq!(data.is_verified, true);
+        assert_eq!(data.status, InstitutionStatus::Verified);
     }
 
     #[test]
@@ -109,6 +169,98 @@ mod test {
         assert_eq!(data.metadata, new_meta);
     }
 
+    #[test]
+    fn test_verifying_authority_lifecycle() {
+        let env = Env::default();
+        let (client, admin, inst_addr) = setup_test(&env);
+        let authority = Address::generate(&env);
+        env.mock_all_auths();
+
+        let name = String::from_str(&env, "Clinic A");
+        client.register_institution(&inst_addr, &name, &name, &name);
+        client.add_verifier(&admin, &authority);
+        assert!(client.is_verifier(&authority));
+
+        client.verify_institution(&authority, &inst_addr);
+        client.suspend_institution(&authority, &inst_addr);
+        let data = client.get_institution(&inst_addr);
+        assert_eq!(data.status, InstitutionStatus::Suspended);
+        assert_eq!(data.status_changed_by, Some(authority.clone()));
+
+        // Reinstated
+        client.verify_institution(&authority, &inst_addr);
+        assert_eq!(
+            client.get_institution(&inst_addr).status,
+            InstitutionStatus::Verified
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Not authorized to verify")]
+    fn test_removed_verifier_cannot_verify() {
+        let env = Env::default();
+        let (client, admin, inst_addr) = setup_test(&env);
+        let authority = Address::generate(&env);
+        env.mock_all_auths();
+
+        let name = String::from_str(&env, "Clinic A");
+        client.register_institution(&inst_addr, &name, &name, &name);
+        client.add_verifier(&admin, &authority);
+        client.remove_verifier(&admin, &authority);
+
+        client.verify_institution(&authority, &inst_addr);
+    }
+
+    #[test]
+    #[should_panic(expected = "Institution cannot be verified")]
+    fn test_closed_institution_cannot_be_verified() {
+        let env = Env::default();
+        let (client, admin, inst_addr) = setup_test(&env);
+        env.mock_all_auths();
+
+        let name = String::from_str(&env, "Clinic A");
+        client.register_institution(&inst_addr, &name, &name, &name);
+        client.close_institution(&inst_addr, &inst_addr);
+
+        client.verify_institution(&admin, &inst_addr);
+    }
+
+    #[test]
+    fn test_list_institutions_by_status() {
+        let env = Env::default();
+        let (client, admin, _) = setup_test(&env);
+        env.mock_all_auths();
+
+        let name = String::from_str(&env, "Clinic");
+        let mut wallets = Vec::new(&env);
+        for _ in 0..3 {
+            let wallet = Address::generate(&env);
+            client.register_institution(&wallet, &name, &name, &name);
+            wallets.push_back(wallet);
+        }
+        client.verify_institution(&admin, &wallets.get(0).unwrap());
+        client.verify_institution(&admin, &wallets.get(2).unwrap());
+
+        let first = client.list_institutions(&InstitutionStatus::Verified, &0, &2);
+        assert_eq!(first.institutions.len(), 1);
+        assert_eq!(
+            first.institutions.get(0).unwrap().wallet,
+            wallets.get(0).unwrap()
+        );
+        assert_eq!(first.next_cursor, Some(2));
+
+        let second = client.list_institutions(&InstitutionStatus::Verified, &2, &2);
+        assert_eq!(second.institutions.len(), 1);
+        assert_eq!(second.next_cursor, None);
+
+        let pending = client.list_institutions(&InstitutionStatus::Pending, &0, &10);
+        assert_eq!(pending.institutions.len(), 1);
+        assert_eq!(
+            pending.institutions.get(0).unwrap().wallet,
+            wallets.get(1).unwrap()
+        );
+    }
+
     // Appointment Scheduling Tests
     #[test]
     fn test_create_appointment() {
@@ -219,9 +371,9 @@ mod test {
         let appointment_id1 = client.create_appointment(&patient, &doctor, &datetime1);
         let appointment_id2 = client.create_appointment(&patient, &doctor, &datetime2);
 
-        // Create appointment for patient2 with doctor
+        // Create appointment for patient2 with doctor, half an hour later
         env.mock_all_auths();
-        let appointment_id3 = client.create_appointment(&patient2, &doctor, &datetime1);
+        let appointment_id3 = client.create_appointment(&patient2, &doctor, &(datetime1 + 1800));
 
         // Check patient's appointments
         let patient_appointments = client.get_appointments(&patient);
@@ -282,6 +434,9 @@ mod test {
                 AppointmentStatus::Scheduled => scheduled_count += 1,
                 AppointmentStatus::Canceled => canceled_count += 1,
                 AppointmentStatus::Completed => completed_count += 1,
+                AppointmentStatus::NoShow
+                | AppointmentStatus::CheckedIn
+                | AppointmentStatus::InProgress => {}
             }
         }
 
@@ -289,4 +444,763 @@ mod test {
         assert_eq!(canceled_count, 1); // id2
         assert_eq!(completed_count, 1); // id1
     }
+
+    // Availability Tests
+    #[test]
+    #[should_panic(expected = "Slot already booked")]
+    fn test_double_booking_fails() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+        let patient2 = Address::generate(&env);
+
+        client.create_appointment(&patient, &doctor, &1640995200);
+        client.create_appointment(&patient2, &doctor, &1640995200);
+    }
+
+    #[test]
+    fn test_cancel_releases_slot() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+        let patient2 = Address::generate(&env);
+
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        client.cancel_appointment(&patient, &appointment_id);
+
+        let rebooked = client.create_appointment(&patient2, &doctor, &1640995200);
+        assert_eq!(rebooked, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Slot not available")]
+    fn test_booking_outside_working_hours_fails() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+        client.set_availability(&doctor, &monday_mornings(&env));
+
+        // 11:00 is the end of the working day
+        client.create_appointment(&patient, &doctor, &(MONDAY + 11 * 3600));
+    }
+
+    #[test]
+    #[should_panic(expected = "Slot not available")]
+    fn test_booking_off_slot_boundary_fails() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+        client.set_availability(&doctor, &monday_mornings(&env));
+
+        client.create_appointment(&patient, &doctor, &(MONDAY + 9 * 3600 + 1800));
+    }
+
+    #[test]
+    #[should_panic(expected = "Doctor has no availability")]
+    fn test_booking_without_availability_fails() {
+        let env = Env::default();
+        let (client, patient, _) = setup_appointment_test(&env);
+        let other_doctor = Address::generate(&env);
+
+        client.create_appointment(&patient, &other_doctor, &1640995200);
+    }
+
+    #[test]
+    fn test_free_slots_skip_bookings_and_blackouts() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+
+        let next_monday = MONDAY + 7 * 86400;
+        let mut availability = monday_mornings(&env);
+        availability.blackout_dates = vec![&env, next_monday];
+        client.set_availability(&doctor, &availability);
+
+        let slots = client.get_free_slots(&doctor, &MONDAY, &(next_monday + 86400));
+        assert_eq!(slots, vec![&env, MONDAY + 9 * 3600, MONDAY + 10 * 3600]);
+
+        client.create_appointment(&patient, &doctor, &(MONDAY + 9 * 3600));
+
+        let slots = client.get_free_slots(&doctor, &MONDAY, &(next_monday + 86400));
+        assert_eq!(slots, vec![&env, MONDAY + 10 * 3600]);
+    }
+
+    #[test]
+    fn test_template_change_keeps_booked_slots() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+        let patient2 = Address::generate(&env);
+
+        // A 09:30 half hour booked under the current template
+        let booked = MONDAY + 9 * 3600 + 1800;
+        let appointment_id = client.create_appointment(&patient, &doctor, &booked);
+
+        // Hour-long slots from 09:00 fit around it, but the one overlapping
+        // the booking cannot be taken
+        client.set_availability(&doctor, &monday_mornings(&env));
+        let slots = client.get_free_slots(&doctor, &MONDAY, &(MONDAY + 86400));
+        assert_eq!(slots, vec![&env, MONDAY + 10 * 3600]);
+        assert!(client
+            .try_create_appointment(&patient2, &doctor, &(MONDAY + 9 * 3600))
+            .is_err());
+
+        // Hours that no longer cover the booking are refused
+        let mut afternoons = monday_mornings(&env);
+        afternoons.weekly_hours = vec![
+            &env,
+            WorkingHours {
+                weekday: 0,
+                start_minute: 13 * 60,
+                end_minute: 17 * 60,
+            },
+        ];
+        assert!(client.try_set_availability(&doctor, &afternoons).is_err());
+
+        // Once the booking is cancelled nothing holds the template back
+        client.cancel_appointment(&patient, &appointment_id);
+        client.set_availability(&doctor, &afternoons);
+    }
+
+    // Rescheduling and Attendance Tests
+    #[test]
+    fn test_reschedule_keeps_id_and_history() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+        let patient2 = Address::generate(&env);
+
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        client.reschedule_appointment(&patient, &appointment_id, &1641081600);
+
+        let appointment = client.get_appointment(&appointment_id);
+        assert_eq!(appointment.datetime, 1641081600);
+        assert_eq!(appointment.reschedules.len(), 1);
+        let reschedule = appointment.reschedules.get(0).unwrap();
+        assert_eq!(reschedule.previous_datetime, 1640995200);
+        assert_eq!(reschedule.rescheduled_by, patient);
+
+        // The original slot is free again, the new one is taken
+        client.create_appointment(&patient2, &doctor, &1640995200);
+        let free = client.get_free_slots(&doctor, &1641081600, &(1641081600 + 3600));
+        assert_eq!(free, vec![&env, 1641081600 + 1800]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Slot already booked")]
+    fn test_reschedule_into_booked_slot_fails() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+        let patient2 = Address::generate(&env);
+
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        client.create_appointment(&patient2, &doctor, &1641081600);
+
+        client.reschedule_appointment(&doctor, &appointment_id, &1641081600);
+    }
+
+    #[test]
+    fn test_doctor_cancellation_records_reason() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+        env.ledger().set_timestamp(1640995200 - 60);
+
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        let reason = String::from_str(&env, "Doctor unwell");
+        client.cancel_by_doctor(&doctor, &appointment_id, &reason);
+
+        let appointment = client.get_appointment(&appointment_id);
+        assert!(matches!(appointment.status, AppointmentStatus::Canceled));
+        assert_eq!(appointment.canceled_by, Some(doctor));
+        assert_eq!(appointment.cancellation_reason, Some(reason));
+
+        // Late, but not the patient's doing
+        assert_eq!(
+            client.get_patient_attendance(&patient).late_cancellations,
+            0
+        );
+    }
+
+    #[test]
+    fn test_late_cancellation_is_counted() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+        client.set_cancellation_window(&doctor, &86400);
+
+        env.ledger().set_timestamp(1640995200 - 2 * 86400);
+        let early = client.create_appointment(&patient, &doctor, &1640995200);
+        let late = client.create_appointment(&patient, &doctor, &1641081600);
+        client.cancel_appointment(&patient, &early);
+
+        env.ledger().set_timestamp(1641081600 - 3600);
+        client.cancel_appointment(&patient, &late);
+
+        let attendance = client.get_patient_attendance(&patient);
+        assert_eq!(attendance.late_cancellations, 1);
+        assert_eq!(attendance.no_shows, 0);
+    }
+
+    #[test]
+    fn test_mark_no_show_after_slot() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        env.ledger().set_timestamp(1640995200 + 1800);
+        client.mark_no_show(&doctor, &appointment_id);
+
+        let appointment = client.get_appointment(&appointment_id);
+        assert!(matches!(appointment.status, AppointmentStatus::NoShow));
+        assert_eq!(client.get_patient_attendance(&patient).no_shows, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Slot has not passed yet")]
+    fn test_mark_no_show_before_slot_ends_fails() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        env.ledger().set_timestamp(1640995200 + 60);
+        client.mark_no_show(&doctor, &appointment_id);
+    }
+
+    // Waitlist Tests
+    fn setup_waitlist_test(env: &Env) -> (AppointmentSchedulingClient<'static>, Address, u64) {
+        let (client, patient, doctor) = setup_appointment_test(env);
+        env.ledger().set_timestamp(1640995200 - 86400);
+
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        (client, doctor, appointment_id)
+    }
+
+    #[test]
+    fn test_cancellation_offers_slot_to_waitlist() {
+        let env = Env::default();
+        let (client, doctor, appointment_id) = setup_waitlist_test(&env);
+        let patient = client.get_appointment(&appointment_id).patient;
+        let outside_window = Address::generate(&env);
+        let waiting = Address::generate(&env);
+        let also_waiting = Address::generate(&env);
+
+        client.join_waitlist(&outside_window, &doctor, &1641081600, &1641168000);
+        client.join_waitlist(&waiting, &doctor, &1640995200, &1641081600);
+        client.join_waitlist(&also_waiting, &doctor, &1640995200, &1641081600);
+
+        client.cancel_appointment(&patient, &appointment_id);
+
+        let offer = client.get_slot_offer(&doctor, &1640995200).unwrap();
+        assert_eq!(offer.patient, waiting);
+        assert_eq!(offer.expires_at, 1640995200 - 86400 + 3600);
+
+        let claimed = client.claim_slot_offer(&waiting, &doctor, &1640995200);
+        assert_eq!(client.get_appointment(&claimed).patient, waiting);
+        assert_eq!(client.get_slot_offer(&doctor, &1640995200), None);
+        assert_eq!(client.get_waitlist(&doctor).len(), 2);
+    }
+
+    #[test]
+    fn test_unclaimed_offer_moves_to_next_patient() {
+        let env = Env::default();
+        let (client, doctor, appointment_id) = setup_waitlist_test(&env);
+        let waiting = Address::generate(&env);
+        let also_waiting = Address::generate(&env);
+
+        client.set_claim_period(&doctor, &600);
+        client.join_waitlist(&waiting, &doctor, &0, &1641081600);
+        client.join_waitlist(&also_waiting, &doctor, &0, &1641081600);
+        client.cancel_by_doctor(
+            &doctor,
+            &appointment_id,
+            &String::from_str(&env, "Schedule change"),
+        );
+
+        env.ledger().set_timestamp(1640995200 - 86400 + 600);
+        client.advance_slot_offer(&doctor, &1640995200);
+
+        let offer = client.get_slot_offer(&doctor, &1640995200).unwrap();
+        assert_eq!(offer.patient, also_waiting);
+        assert_eq!(offer.offered_to, vec![&env, waiting.clone(), also_waiting]);
+
+        // Nobody left to offer it to once the second offer lapses too
+        env.ledger().set_timestamp(1640995200 - 86400 + 1200);
+        client.advance_slot_offer(&doctor, &1640995200);
+        assert_eq!(client.get_slot_offer(&doctor, &1640995200), None);
+        client.create_appointment(&waiting, &doctor, &1640995200);
+    }
+
+    #[test]
+    #[should_panic(expected = "Slot held for waitlisted patient")]
+    fn test_offered_slot_is_held() {
+        let env = Env::default();
+        let (client, doctor, appointment_id) = setup_waitlist_test(&env);
+        let patient = client.get_appointment(&appointment_id).patient;
+        let waiting = Address::generate(&env);
+
+        client.join_waitlist(&waiting, &doctor, &0, &1641081600);
+        client.cancel_appointment(&patient, &appointment_id);
+
+        client.create_appointment(&Address::generate(&env), &doctor, &1640995200);
+    }
+
+    #[test]
+    fn test_free_slots_skip_past_and_offered_slots() {
+        let env = Env::default();
+        let (client, doctor, appointment_id) = setup_waitlist_test(&env);
+        let patient = client.get_appointment(&appointment_id).patient;
+        let now = env.ledger().timestamp();
+        let slot = 1640995200;
+
+        client.join_waitlist(&Address::generate(&env), &doctor, &0, &1641081600);
+        client.cancel_appointment(&patient, &appointment_id);
+
+        let free = client.get_free_slots(&doctor, &(now - 3600), &(slot + 3600));
+        assert_eq!(free.first(), Some(now));
+        assert!(!free.contains(slot));
+        assert!(free.contains(slot + 1800));
+
+        // The slot is listed again once the offer lapses
+        env.ledger().set_timestamp(now + 3600);
+        let free = client.get_free_slots(&doctor, &now, &(slot + 3600));
+        assert_eq!(free.first(), Some(now + 3600));
+        assert!(free.contains(slot));
+    }
+
+    #[test]
+    #[should_panic(expected = "Offer expired")]
+    fn test_claim_after_expiry_fails() {
+        let env = Env::default();
+        let (client, doctor, appointment_id) = setup_waitlist_test(&env);
+        let patient = client.get_appointment(&appointment_id).patient;
+        let waiting = Address::generate(&env);
+
+        client.join_waitlist(&waiting, &doctor, &0, &1641081600);
+        client.cancel_appointment(&patient, &appointment_id);
+
+        env.ledger().set_timestamp(1640995200 - 86400 + 3600);
+        client.claim_slot_offer(&waiting, &doctor, &1640995200);
+    }
+
+    #[test]
+    fn test_waitlist_is_capped() {
+        let env = Env::default();
+        let (client, doctor, _) = setup_waitlist_test(&env);
+
+        for _ in 0..20 {
+            client.join_waitlist(&Address::generate(&env), &doctor, &0, &1641081600);
+        }
+        assert!(client
+            .try_join_waitlist(&Address::generate(&env), &doctor, &0, &1641081600)
+            .is_err());
+
+        // Leaving frees a place and keeps the order
+        let first = client.get_waitlist(&doctor).get(0).unwrap().patient;
+        let second = client.get_waitlist(&doctor).get(1).unwrap().patient;
+        client.leave_waitlist(&first, &doctor);
+        assert_eq!(client.get_waitlist(&doctor).get(0).unwrap().patient, second);
+        client.join_waitlist(&Address::generate(&env), &doctor, &0, &1641081600);
+        assert_eq!(client.get_waitlist(&doctor).len(), 20);
+    }
+
+    #[test]
+    fn test_offer_prunes_lapsed_waitlist_entries() {
+        let env = Env::default();
+        let (client, doctor, appointment_id) = setup_waitlist_test(&env);
+        let patient = client.get_appointment(&appointment_id).patient;
+        let lapsed = Address::generate(&env);
+        let waiting = Address::generate(&env);
+
+        client.join_waitlist(&lapsed, &doctor, &0, &(1640995200 - 80000));
+        client.join_waitlist(&waiting, &doctor, &0, &1641081600);
+
+        env.ledger().set_timestamp(1640995200 - 3600);
+        client.cancel_appointment(&patient, &appointment_id);
+
+        assert_eq!(
+            client.get_slot_offer(&doctor, &1640995200).unwrap().patient,
+            waiting
+        );
+        let waitlist = client.get_waitlist(&doctor);
+        assert_eq!(waitlist.len(), 1);
+        assert_eq!(waitlist.get(0).unwrap().patient, waiting);
+    }
+
+    // Check-in and Reminder Tests
+    #[test]
+    fn test_check_in_start_and_complete_timestamps() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+        let desk = Address::generate(&env);
+        client.add_front_desk(&doctor, &desk);
+
+        env.ledger().set_timestamp(1640995200 - 86400);
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+
+        env.ledger().set_timestamp(1640995200 - 300);
+        client.check_in(&desk, &appointment_id);
+        env.ledger().set_timestamp(1640995200 + 600);
+        client.start_visit(&doctor, &appointment_id);
+        env.ledger().set_timestamp(1640995200 + 1800);
+        client.complete_appointment(&doctor, &appointment_id);
+
+        let appointment = client.get_appointment(&appointment_id);
+        assert!(matches!(appointment.status, AppointmentStatus::Completed));
+        assert_eq!(appointment.checked_in_by, Some(desk));
+        assert_eq!(appointment.checked_in_at, Some(1640995200 - 300));
+        assert_eq!(appointment.started_at, Some(1640995200 + 600));
+        assert_eq!(appointment.completed_at, Some(1640995200 + 1800));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized to check in this appointment")]
+    fn test_check_in_by_stranger_fails() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        client.check_in(&Address::generate(&env), &appointment_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Patient has not checked in")]
+    fn test_start_visit_requires_check_in() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        client.start_visit(&doctor, &appointment_id);
+    }
+
+    #[test]
+    fn test_reminders_sent_once_per_lead_time() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+        client.set_reminder_lead_times(&doctor, &vec![&env, 86400, 3600]);
+
+        env.ledger().set_timestamp(1640995200 - 2 * 86400);
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        assert_eq!(client.send_due_reminders(&appointment_id), 0);
+
+        env.ledger().set_timestamp(1640995200 - 86400);
+        assert_eq!(client.send_due_reminders(&appointment_id), 1);
+        assert_eq!(client.send_due_reminders(&appointment_id), 0);
+
+        env.ledger().set_timestamp(1640995200 - 1800);
+        assert_eq!(client.send_due_reminders(&appointment_id), 1);
+        assert_eq!(
+            client.get_appointment(&appointment_id).reminded_lead_times,
+            vec![&env, 86400, 3600]
+        );
+
+        // Lead times are tracked by value, so a new one still goes out once
+        client.set_reminder_lead_times(&doctor, &vec![&env, 86400, 3600, 600]);
+        assert_eq!(client.send_due_reminders(&appointment_id), 0);
+        env.ledger().set_timestamp(1640995200 - 600);
+        assert_eq!(client.send_due_reminders(&appointment_id), 1);
+        assert_eq!(client.send_due_reminders(&appointment_id), 0);
+    }
+
+    #[test]
+    fn test_no_reminders_once_appointment_starts() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+
+        env.ledger().set_timestamp(1640995200 - 2 * 86400);
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+
+        env.ledger().set_timestamp(1640995200);
+        assert_eq!(client.send_due_reminders(&appointment_id), 0);
+        assert!(client
+            .get_appointment(&appointment_id)
+            .reminded_lead_times
+            .is_empty());
+    }
+
+    #[test]
+    fn test_visit_stats_average_wait_and_duration() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+        let patient2 = Address::generate(&env);
+
+        let first = client.create_appointment(&patient, &doctor, &1640995200);
+        let second = client.create_appointment(&patient2, &doctor, &(1640995200 + 3600));
+        client.create_appointment(&patient, &doctor, &(1640995200 + 7200));
+
+        // Early arrival: wait counts from the booked time
+        env.ledger().set_timestamp(1640995200 - 600);
+        client.check_in(&patient, &first);
+        env.ledger().set_timestamp(1640995200 + 300);
+        client.start_visit(&doctor, &first);
+        env.ledger().set_timestamp(1640995200 + 1500);
+        client.complete_appointment(&doctor, &first);
+
+        // Late arrival: wait counts from check-in
+        env.ledger().set_timestamp(1640995200 + 4200);
+        client.check_in(&patient2, &second);
+        env.ledger().set_timestamp(1640995200 + 4800);
+        client.start_visit(&doctor, &second);
+        env.ledger().set_timestamp(1640995200 + 7200);
+        client.complete_appointment(&doctor, &second);
+
+        let day = 1640995200 + 86400;
+        let stats = client.get_visit_stats(&doctor, &1640995200, &day, &0, &MAX_PAGE_SIZE);
+        assert_eq!(stats.visits, 2);
+        assert_eq!(stats.total_wait_seconds, 300 + 600);
+        assert_eq!(stats.total_visit_seconds, 1200 + 2400);
+        assert_eq!(stats.next_cursor, None);
+
+        let later = 1640995200 + 3600;
+        let stats = client.get_visit_stats(&doctor, &later, &day, &0, &MAX_PAGE_SIZE);
+        assert_eq!(stats.visits, 1);
+
+        // Pages split the doctor's appointments; their totals add up
+        let first_page = client.get_visit_stats(&doctor, &1640995200, &day, &0, &1);
+        assert_eq!(first_page.visits, 1);
+        assert_eq!(first_page.total_wait_seconds, 300);
+        assert_eq!(first_page.next_cursor, Some(1));
+        let rest = client.get_visit_stats(&doctor, &1640995200, &day, &1, &2);
+        assert_eq!(rest.visits, 1);
+        assert_eq!(rest.total_wait_seconds, 600);
+        assert_eq!(rest.next_cursor, None);
+    }
+
+    // Deposit Tests
+    fn setup_deposit_test(
+        env: &Env,
+    ) -> (
+        AppointmentSchedulingClient<'static>,
+        Address,
+        Address,
+        token::Client<'static>,
+        Address,
+    ) {
+        let (client, patient, doctor) = setup_appointment_test(env);
+        let clinic = Address::generate(env);
+
+        let asset = env.register_stellar_asset_contract_v2(Address::generate(env));
+        token::StellarAssetClient::new(env, &asset.address()).mint(&patient, &1000);
+        client.set_deposit_policy(&doctor, &asset.address(), &100, &clinic);
+        client.set_cancellation_window(&doctor, &86400);
+        env.ledger().set_timestamp(1640995200 - 2 * 86400);
+
+        let token = token::Client::new(env, &asset.address());
+        (client, patient, doctor, token, clinic)
+    }
+
+    #[test]
+    fn test_deposit_held_and_refunded_on_completion() {
+        let env = Env::default();
+        let (client, patient, doctor, token, _) = setup_deposit_test(&env);
+
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        assert_eq!(token.balance(&patient), 900);
+        assert_eq!(token.balance(&client.address), 100);
+        assert_eq!(
+            client.get_deposit(&appointment_id).unwrap().status,
+            DepositStatus::Held
+        );
+
+        client.complete_appointment(&doctor, &appointment_id);
+        assert_eq!(token.balance(&patient), 1000);
+        assert_eq!(
+            client.get_deposit(&appointment_id).unwrap().status,
+            DepositStatus::Refunded
+        );
+    }
+
+    #[test]
+    fn test_timely_cancel_refunds_and_late_cancel_forfeits() {
+        let env = Env::default();
+        let (client, patient, doctor, token, clinic) = setup_deposit_test(&env);
+
+        let timely = client.create_appointment(&patient, &doctor, &1640995200);
+        let late = client.create_appointment(&patient, &doctor, &1641081600);
+        client.cancel_appointment(&patient, &timely);
+        assert_eq!(token.balance(&patient), 900);
+
+        env.ledger().set_timestamp(1641081600 - 3600);
+        client.cancel_appointment(&patient, &late);
+        assert_eq!(token.balance(&patient), 900);
+        assert_eq!(token.balance(&clinic), 100);
+        assert_eq!(
+            client.get_deposit(&late).unwrap().status,
+            DepositStatus::Forfeited
+        );
+    }
+
+    #[test]
+    fn test_no_show_forfeits_and_doctor_cancel_refunds() {
+        let env = Env::default();
+        let (client, patient, doctor, token, clinic) = setup_deposit_test(&env);
+
+        let missed = client.create_appointment(&patient, &doctor, &1640995200);
+        let canceled = client.create_appointment(&patient, &doctor, &1641081600);
+
+        env.ledger().set_timestamp(1640995200 + 3600);
+        client.mark_no_show(&doctor, &missed);
+        assert_eq!(token.balance(&clinic), 100);
+
+        // The doctor's own cancellation never costs the patient
+        client.cancel_by_doctor(&doctor, &canceled, &String::from_str(&env, "Clinic closed"));
+        assert_eq!(token.balance(&patient), 900);
+        assert_eq!(token.balance(&client.address), 0);
+    }
+
+    #[test]
+    fn test_late_reschedule_forfeits_deposit() {
+        let env = Env::default();
+        let (client, patient, doctor, token, clinic) = setup_deposit_test(&env);
+
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        env.ledger().set_timestamp(1640995200 - 3600);
+        client.reschedule_appointment(&patient, &appointment_id, &1641081600);
+
+        // The old deposit goes to the clinic and a new one holds the new slot
+        assert_eq!(token.balance(&clinic), 100);
+        assert_eq!(token.balance(&patient), 800);
+        assert_eq!(
+            client.get_deposit(&appointment_id).unwrap().status,
+            DepositStatus::Held
+        );
+        assert_eq!(
+            client.get_patient_attendance(&patient).late_cancellations,
+            1
+        );
+
+        // The forfeited deposit stays on record
+        let settled = client.get_settled_deposits(&appointment_id);
+        assert_eq!(settled.len(), 1);
+        assert_eq!(settled.get(0).unwrap().status, DepositStatus::Forfeited);
+        assert_eq!(settled.get(0).unwrap().beneficiary, clinic);
+    }
+
+    #[test]
+    #[should_panic(expected = "No deposit held")]
+    fn test_reclaim_requires_held_deposit() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        env.ledger().set_timestamp(1640995200 + 1800 + 7 * 86400);
+        client.reclaim_deposit(&patient, &appointment_id);
+    }
+
+    #[test]
+    fn test_unsettled_deposit_reclaimed_after_delay() {
+        let env = Env::default();
+        let (client, patient, doctor, token, _) = setup_deposit_test(&env);
+
+        let appointment_id = client.create_appointment(&patient, &doctor, &1640995200);
+        client.check_in(&patient, &appointment_id);
+
+        // The slot ended but the doctor never settled the visit
+        env.ledger().set_timestamp(1640995200 + 1800);
+        assert!(client
+            .try_reclaim_deposit(&patient, &appointment_id)
+            .is_err());
+
+        env.ledger().set_timestamp(1640995200 + 1800 + 7 * 86400);
+        client.reclaim_deposit(&patient, &appointment_id);
+        assert_eq!(token.balance(&patient), 1000);
+        assert_eq!(
+            client.get_deposit(&appointment_id).unwrap().status,
+            DepositStatus::Refunded
+        );
+    }
+
+    // Institution Gating Tests
+    #[test]
+    fn test_booking_requires_verified_institution() {
+        let env = Env::default();
+        let (registry, admin, inst_addr) = setup_test(&env);
+        let patient = Address::generate(&env);
+        let doctor = Address::generate(&env);
+        let client = setup_scheduling(&env, &registry.address, &doctor);
+
+        let name = String::from_str(&env, "Clinic A");
+        registry.register_institution(&inst_addr, &name, &name, &name);
+
+        // Pending institutions cannot take on doctors
+        assert!(registry.try_add_doctor(&inst_addr, &doctor).is_err());
+
+        registry.verify_institution(&admin, &inst_addr);
+        registry.add_doctor(&inst_addr, &doctor);
+        client.create_appointment(&patient, &doctor, &1640995200);
+
+        registry.suspend_institution(&admin, &inst_addr);
+        assert!(client
+            .try_create_appointment(&patient, &doctor, &1641081600)
+            .is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Doctor's institution is not verified")]
+    fn test_booking_doctor_without_institution_fails() {
+        let env = Env::default();
+        let (registry, _, _) = setup_test(&env);
+        let patient = Address::generate(&env);
+        let doctor = Address::generate(&env);
+        let client = setup_scheduling(&env, &registry.address, &doctor);
+
+        client.create_appointment(&patient, &doctor, &1640995200);
+    }
+
+    #[test]
+    fn test_add_doctor_requires_doctor_consent() {
+        let env = Env::default();
+        let (registry, admin, inst_addr) = setup_test(&env);
+        let doctor = Address::generate(&env);
+
+        let name = String::from_str(&env, "Clinic A");
+        registry.register_institution(&inst_addr, &name, &name, &name);
+        registry.verify_institution(&admin, &inst_addr);
+
+        // The institution alone cannot claim the doctor
+        let result = registry
+            .mock_auths(&[MockAuth {
+                address: &inst_addr,
+                invoke: &MockAuthInvoke {
+                    contract: &registry.address,
+                    fn_name: "add_doctor",
+                    args: (&inst_addr, &doctor).into_val(&env),
+                    sub_invokes: &[],
+                },
+            }])
+            .try_add_doctor(&inst_addr, &doctor);
+        assert!(result.is_err());
+        assert_eq!(registry.get_doctor_institution(&doctor), None);
+    }
+
+    #[test]
+    fn test_doctor_leaves_institution() {
+        let env = Env::default();
+        let (client, patient, doctor) = setup_appointment_test(&env);
+        let registry = HealthcareRegistryClient::new(&env, &client.get_institution_registry());
+
+        registry.leave_institution(&doctor);
+        assert_eq!(registry.get_doctor_institution(&doctor), None);
+        assert!(client
+            .try_create_appointment(&patient, &doctor, &1640995200)
+            .is_err());
+    }
+
+    #[test]
+    fn test_init_requires_admin_auth() {
+        let env = Env::default();
+        let registry = HealthcareRegistryClient::new(&env, &env.register(HealthcareRegistry, ()));
+        assert!(registry.try_init(&Address::generate(&env)).is_err());
+
+        let scheduling =
+            AppointmentSchedulingClient::new(&env, &env.register(AppointmentScheduling, ()));
+        assert!(scheduling
+            .try_initialize(&Address::generate(&env), &registry.address)
+            .is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Not initialized")]
+    fn test_booking_requires_initialization() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client =
+            AppointmentSchedulingClient::new(&env, &env.register(AppointmentScheduling, ()));
+        let doctor = Address::generate(&env);
+        client.set_availability(&doctor, &monday_mornings(&env));
+
+        client.create_appointment(&Address::generate(&env), &doctor, &(MONDAY + 9 * 3600));
+    }
 }
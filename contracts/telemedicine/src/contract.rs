@@ -1,13 +1,69 @@
 use crate::types::{
     DataKey, EligibilityResult, Error, PrescriptionRequest, VirtualVisit, VisitStatus,
 };
+use access_control_interface::{patient_resource, AccessPolicyClient, Purpose, Scope};
+use doctor_registry_interface::DoctorDirectoryClient;
 use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
 
+fn doctor_registry(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::DoctorRegistry)
+}
+
+/// Ask the patient's AccessControl contract whether `provider` holds `scope`
+/// on their telemedicine records. Returns `None` when the patient has not set a policy.
+fn policy_allows(
+    env: &Env,
+    patient_id: &Address,
+    provider: &Address,
+    scope: Scope,
+) -> Option<bool> {
+    let policy: Address = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AccessPolicy(patient_id.clone()))?;
+    Some(AccessPolicyClient::new(env, &policy).check_access(
+        provider,
+        &patient_resource(env, patient_id, "telemedicine"),
+        &scope,
+        &Purpose::Treatment,
+    ))
+}
+
 #[contract]
 pub struct TelemedicineContract;
 
 #[contractimpl]
 impl TelemedicineContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+
+        Ok(())
+    }
+
+    /// Delegate access decisions for a patient's telemedicine records to an
+    /// AccessControl contract, or clear the delegation with `None`. While set,
+    /// providers need a write grant on `patient/<patient>/telemedicine` to
+    /// document a visit or prescribe during it.
+    pub fn set_access_policy(env: Env, patient_id: Address, policy: Option<Address>) {
+        patient_id.require_auth();
+        let key = DataKey::AccessPolicy(patient_id);
+        match policy {
+            Some(policy) => env.storage().persistent().set(&key, &policy),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    pub fn get_access_policy(env: Env, patient_id: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccessPolicy(patient_id))
+    }
+
     pub fn schedule_virtual_visit(
         env: Env,
         patient_id: Address,
@@ -79,7 +135,17 @@ impl TelemedicineContract {
             return Err(Error::InvalidStatusTransition);
         }
 
-        // Let's assume validation happened via verify_telemedicine_eligibility before calling
+        // Once a doctor registry is configured, the provider must be licensed
+        // for the patient's location now, whatever start time is reported
+        if let Some(registry) = doctor_registry(&env) {
+            if !DoctorDirectoryClient::new(&env, &registry).can_practice_in(
+                &provider_id,
+                &patient_location_state,
+                &env.ledger().timestamp(),
+            ) {
+                return Err(Error::IneligibleLocation);
+            }
+        }
 
         visit.status = VisitStatus::InProgress;
         visit.session_start = Some(session_start_time);
@@ -117,6 +183,9 @@ impl TelemedicineContract {
         if visit.provider_id != provider_id {
             return Err(Error::NotAuthorized);
         }
+        if !policy_allows(&env, &visit.patient_id, &provider_id, Scope::Write).unwrap_or(true) {
+            return Err(Error::NotAuthorized);
+        }
 
         env.events().publish(
             (Symbol::new(&env, "visit_documented"), visit_id),
@@ -165,15 +234,30 @@ impl TelemedicineContract {
 
     pub fn verify_telemedicine_eligibility(
         env: Env,
-        patient_id: Address,  // Unused in this mock, but present in signature
-        provider_id: Address, // Unused in this mock, but present in signature
+        patient_id: Address, // Unused, but present in signature
+        provider_id: Address,
         patient_state: String,
         provider_state: String,
     ) -> Result<EligibilityResult, Error> {
-        // Here we mock cross-state licensing validation.
-        // If states are identical, they are eligible.
-        // Otherwise, not eligible (in reality, would check a registry of allowed cross-state licenses).
+        // With a doctor registry configured, eligibility follows the provider's
+        // recorded licenses and compact memberships.
+        if let Some(registry) = doctor_registry(&env) {
+            let licensed = DoctorDirectoryClient::new(&env, &registry).can_practice_in(
+                &provider_id,
+                &patient_state,
+                &env.ledger().timestamp(),
+            );
+            return Ok(EligibilityResult {
+                is_eligible: licensed,
+                reason: if licensed {
+                    String::from_str(&env, "Licensed for patient's state")
+                } else {
+                    String::from_str(&env, "Provider not licensed for patient's state")
+                },
+            });
+        }
 
+        // Without a registry, fall back to same-state validation.
         if patient_state == provider_state {
             Ok(EligibilityResult {
                 is_eligible: true,
@@ -236,6 +320,9 @@ impl TelemedicineContract {
         if visit.patient_id != patient_id {
             return Err(Error::NotAuthorized); // Mismatch between requested prescription patient and visit patient
         }
+        if !policy_allows(&env, &patient_id, &provider_id, Scope::Write).unwrap_or(true) {
+            return Err(Error::NotAuthorized);
+        }
 
         // Mocking Rx ID generation
         let rx_id = env.ledger().timestamp() % 100000;
@@ -247,4 +334,30 @@ impl TelemedicineContract {
 
         Ok(rx_id)
     }
+    /// Point eligibility checks at a `DoctorRegistry` holding provider
+    /// licenses. Set once by the admin at deployment.
+    pub fn set_doctor_registry(env: Env, registry: Address) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        if env.storage().instance().has(&DataKey::DoctorRegistry) {
+            return Err(Error::AlreadyConfigured);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::DoctorRegistry, &registry);
+
+        env.events()
+            .publish((Symbol::new(&env, "doctor_registry_set"),), registry);
+
+        Ok(())
+    }
+
+    pub fn get_doctor_registry(env: Env) -> Option<Address> {
+        doctor_registry(&env)
+    }
 }
@@ -2,9 +2,11 @@
 
 use crate::contract::{TelemedicineContract, TelemedicineContractClient};
 use crate::types::{EligibilityResult, Error, PrescriptionRequest, VisitStatus};
+use access_control::{testutils::setup_access_policy, EntityType, GrantRequest};
+use access_control_interface::{patient_resource, Purpose, Scope};
 use soroban_sdk::{
-    testutils::{Address as _, Events},
-    Address, BytesN, Env, String, Symbol, Vec,
+    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
+    vec, Address, BytesN, Env, IntoVal, String, Symbol, Vec,
 };
 
 #[test]
@@ -147,3 +149,214 @@ fn test_auth_and_eligibility_failures() {
         client.try_prescribe_during_visit(&visit_id, &provider_id, &wrong_patient, &rx_request);
     assert!(rx_res.is_err());
 }
+
+#[test]
+fn test_eligibility_follows_registry_licenses() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, TelemedicineContract);
+    let client = TelemedicineContractClient::new(&env, &contract_id);
+
+    // Provider licensed in CO, which shares the PSYPACT with WI
+    let registry_id = env.register_contract(None, doctor_registry::DoctorRegistry);
+    let registry = doctor_registry::DoctorRegistryClient::new(&env, &registry_id);
+    let admin = Address::generate(&env);
+    let board = Address::generate(&env);
+    let patient_id = Address::generate(&env);
+    let provider_id = Address::generate(&env);
+
+    registry.initialize(&admin, &Address::generate(&env));
+    registry.add_license_verifier(
+        &admin,
+        &board,
+        &Vec::from_array(&env, [String::from_str(&env, "CO")]),
+    );
+    registry.create_doctor_profile(
+        &provider_id,
+        &String::from_str(&env, "Dr. Alice"),
+        &String::from_str(&env, "Psychiatry"),
+    );
+    registry.set_compact_jurisdictions(
+        &admin,
+        &doctor_registry::Compact::PsyPact,
+        &Vec::from_array(
+            &env,
+            [String::from_str(&env, "CO"), String::from_str(&env, "WI")],
+        ),
+        &true,
+    );
+    registry.record_license(
+        &board,
+        &provider_id,
+        &doctor_registry::LicenseRequest {
+            jurisdiction: String::from_str(&env, "CO"),
+            license_number_hash: BytesN::from_array(&env, &[1; 32]),
+            issued_at: 0,
+            expires_at: 1800000000,
+            compacts: Vec::from_array(&env, [doctor_registry::Compact::PsyPact]),
+        },
+    );
+
+    client.initialize(&admin);
+    client.set_doctor_registry(&registry_id);
+    assert_eq!(
+        client.try_set_doctor_registry(&registry_id),
+        Err(Ok(Error::AlreadyConfigured))
+    );
+
+    let eligibility = client.verify_telemedicine_eligibility(
+        &patient_id,
+        &provider_id,
+        &String::from_str(&env, "WI"),
+        &String::from_str(&env, "CO"),
+    );
+    assert!(eligibility.is_eligible);
+
+    let eligibility = client.verify_telemedicine_eligibility(
+        &patient_id,
+        &provider_id,
+        &String::from_str(&env, "CO"),
+        &String::from_str(&env, "CO"),
+    );
+    assert!(eligibility.is_eligible);
+
+    let eligibility = client.verify_telemedicine_eligibility(
+        &patient_id,
+        &provider_id,
+        &String::from_str(&env, "NY"),
+        &String::from_str(&env, "CO"),
+    );
+    assert!(!eligibility.is_eligible);
+
+    let visit_id = client.schedule_virtual_visit(
+        &patient_id,
+        &provider_id,
+        &1700000000,
+        &Symbol::new(&env, "Consult"),
+        &30,
+        &Symbol::new(&env, "ZoomHD"),
+        &true,
+    );
+
+    // Patient located outside the provider's licensure
+    let res = client.try_start_virtual_session(
+        &visit_id,
+        &provider_id,
+        &1700000010,
+        &String::from_str(&env, "NY"),
+    );
+    assert_eq!(res, Err(Ok(Error::IneligibleLocation)));
+
+    // License has lapsed by the time the session starts, whatever start
+    // time the provider reports
+    env.ledger().set_timestamp(1800000010);
+    let res = client.try_start_virtual_session(
+        &visit_id,
+        &provider_id,
+        &1700000010,
+        &String::from_str(&env, "WI"),
+    );
+    assert_eq!(res, Err(Ok(Error::IneligibleLocation)));
+
+    env.ledger().set_timestamp(1700000010);
+    client.start_virtual_session(
+        &visit_id,
+        &provider_id,
+        &1700000010,
+        &String::from_str(&env, "WI"),
+    );
+}
+
+#[test]
+fn test_only_admin_sets_doctor_registry() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, TelemedicineContract);
+    let client = TelemedicineContractClient::new(&env, &contract_id);
+
+    let registry_id = Address::generate(&env);
+    assert_eq!(
+        client.try_set_doctor_registry(&registry_id),
+        Err(Ok(Error::NotInitialized))
+    );
+
+    let admin = Address::generate(&env);
+    env.mock_all_auths();
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+
+    let attacker = Address::generate(&env);
+    let invoke = MockAuthInvoke {
+        contract: &contract_id,
+        fn_name: "set_doctor_registry",
+        args: (&registry_id,).into_val(&env),
+        sub_invokes: &[],
+    };
+    let res = client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &invoke,
+        }])
+        .try_set_doctor_registry(&registry_id);
+    assert!(res.is_err());
+    assert_eq!(client.get_doctor_registry(), None);
+}
+
+#[test]
+fn test_access_policy_gates_visit_documentation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, TelemedicineContract);
+    let client = TelemedicineContractClient::new(&env, &contract_id);
+
+    let patient_id = Address::generate(&env);
+    let provider_id = Address::generate(&env);
+    let visit_id = client.schedule_virtual_visit(
+        &patient_id,
+        &provider_id,
+        &1700000000,
+        &Symbol::new(&env, "Consult"),
+        &30,
+        &Symbol::new(&env, "ZoomHD"),
+        &true,
+    );
+
+    let (policy_id, policy) =
+        setup_access_policy(&env, &patient_id, &provider_id, EntityType::Doctor);
+    client.set_access_policy(&patient_id, &Some(policy_id.clone()));
+    assert_eq!(client.get_access_policy(&patient_id), Some(policy_id));
+
+    let document = |client: &TelemedicineContractClient| {
+        client.try_record_visit_documentation(
+            &visit_id,
+            &provider_id,
+            &BytesN::from_array(&env, &[4; 32]),
+            &Vec::new(&env),
+            &String::from_str(&env, "Stable"),
+            &String::from_str(&env, "Follow up in 2 weeks"),
+        )
+    };
+    assert_eq!(document(&client), Err(Ok(Error::NotAuthorized)));
+
+    let resource_id = patient_resource(&env, &patient_id, "telemedicine");
+    policy.grant_access(
+        &patient_id,
+        &provider_id,
+        &resource_id,
+        &vec![&env, Scope::Write],
+        &GrantRequest {
+            purpose: Purpose::Treatment,
+            consent_hash: BytesN::from_array(&env, &[7u8; 32]),
+            can_delegate: false,
+            expires_at: 0,
+        },
+    );
+    assert!(document(&client).is_ok());
+
+    policy.revoke_access(&patient_id, &provider_id, &resource_id);
+    assert_eq!(document(&client), Err(Ok(Error::NotAuthorized)));
+}
@@ -8,6 +8,9 @@ pub enum Error {
     VisitNotFound = 2,
     InvalidStatusTransition = 3,
     IneligibleLocation = 4,
+    AlreadyConfigured = 5,
+    AlreadyInitialized = 6,
+    NotInitialized = 7,
 }
 
 #[contracttype]
@@ -54,6 +57,9 @@ pub struct PrescriptionRequest {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
+    Admin,
     VirtualVisit(u64),
     VisitCount,
+    DoctorRegistry,        // Address of the DoctorRegistry holding provider licenses
+    AccessPolicy(Address), // patient -> AccessControl contract
 }